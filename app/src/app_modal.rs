@@ -1,8 +1,8 @@
 use iced::Element;
 use iced::widget::mouse_area;
 use iced::widget::opaque;
-use iced::widget::{Column, Container, Row, Text, button, container, text};
-use iced::{Background, Border, Color, Length, alignment};
+use iced::widget::{Column, Container, Row, Text, button, container, scrollable, text};
+use iced::{Background, Border, Color, Font, Length, alignment};
 
 use crate::ui_element::{ButtonThemeStyle, CustomStyle};
 
@@ -10,11 +10,14 @@ use crate::ui_element::{ButtonThemeStyle, CustomStyle};
 pub struct ModalAsk {
     pub show_modal: bool,
     pub message: String,
+    pub details: Vec<String>,
+    pub choices: Vec<(String, bool)>,
 }
 
 #[derive(Debug, Clone)]
 pub enum ModalAskMessage {
     ConfirmMsg(bool),
+    ToggleChoice(usize),
 }
 
 impl ModalAsk {
@@ -24,10 +27,31 @@ impl ModalAsk {
         self.show_modal = true;
     }
 
+    /// Sets detail lines shown below the message
+    pub fn set_details(&mut self, details: Vec<String>) {
+        self.details = details;
+    }
+
+    /// Sets choices the user can toggle, with their initial state
+    pub fn set_choices(&mut self, choices: Vec<(String, bool)>) {
+        self.choices = choices;
+    }
+
+    /// Returns the choices currently selected
+    pub fn selected_choices(&self) -> Vec<String> {
+        self.choices
+            .iter()
+            .filter(|(_, selected)| *selected)
+            .map(|(choice, _)| choice.clone())
+            .collect()
+    }
+
     /// Hides the modal
     pub fn hide(&mut self) {
         self.show_modal = false;
         self.message.clear();
+        self.details.clear();
+        self.choices.clear();
     }
 
     /// Updates based on the user answer
@@ -37,6 +61,12 @@ impl ModalAsk {
                 self.hide();
                 Some(answer)
             }
+            ModalAskMessage::ToggleChoice(index) => {
+                if let Some((_, selected)) = self.choices.get_mut(index) {
+                    *selected = !*selected;
+                }
+                None
+            }
         }
     }
 
@@ -77,12 +107,78 @@ impl ModalAsk {
         // buttons row
         let buttons_row = Row::new().spacing(10).push(yes_btn).push(no_btn);
 
+        // detail lines, e.g. the process tree
+        let details = (!self.details.is_empty()).then(|| {
+            let lines = self
+                .details
+                .iter()
+                .fold(Column::new().spacing(2), |col, line| {
+                    col.push(Text::new(line).size(11).font(Font::MONOSPACE))
+                });
+
+            Container::new(scrollable(lines).width(Length::Fill))
+                .max_height(220)
+                .padding(6)
+                .style(|_theme| container::Style {
+                    border: Border {
+                        color: Color::from_rgb8(70, 76, 86),
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                })
+        });
+
+        // choice toggles, e.g. the languages to keep
+        let choices = (!self.choices.is_empty()).then(|| {
+            let toggles = self.choices.iter().enumerate().fold(
+                Column::new().spacing(4),
+                |col, (index, (choice, selected))| {
+                    let mark = if *selected { "[x]" } else { "[ ]" };
+                    let style = if *selected {
+                        ButtonThemeStyle::CustomRounded
+                    } else {
+                        ButtonThemeStyle::BlankBorder
+                    };
+
+                    col.push(
+                        button(
+                            text(format!("{mark} {choice}"))
+                                .size(12)
+                                .font(Font::MONOSPACE),
+                        )
+                        .width(Length::Fill)
+                        .custom_style(style)
+                        .on_press(ModalAskMessage::ToggleChoice(index)),
+                    )
+                },
+            );
+
+            Container::new(scrollable(toggles).width(Length::Fill)).max_height(220)
+        });
+
         // modal content column
-        let modal_column = Column::new().push(modal_text).push(buttons_row).spacing(12);
+        let mut modal_column = Column::new().push(modal_text).spacing(12);
+
+        if let Some(details) = details {
+            modal_column = modal_column.push(details);
+        }
+
+        if let Some(choices) = choices {
+            modal_column = modal_column.push(choices);
+        }
+
+        let modal_column = modal_column.push(buttons_row);
+
+        let modal_width = if self.details.is_empty() && self.choices.is_empty() {
+            300
+        } else {
+            560
+        };
 
         let modal_container = Container::new(modal_column)
             .padding(15)
-            .width(300)
+            .width(modal_width)
             .align_x(alignment::Horizontal::Center)
             .align_y(alignment::Vertical::Center)
             .style(|_theme| container::Style {
@@ -4,7 +4,9 @@ use std::path::PathBuf;
 
 use crate::app_modal::{ModalAsk, ModalAskMessage};
 use cleaner::Cleaner;
+use cleaner::Localization;
 use cleaner::Result;
+use cleaner::SizeBreakdown;
 // use cleaner::TrashEntry;
 
 use crate::app_status::Status;
@@ -36,9 +38,19 @@ pub enum AppMessage {
     ExportBomFilesLoc,
     ExportBomFiles(PathBuf),
 
+    SaveReportLoc,
+    SaveReport(PathBuf),
+
     MoveToTrash,
     UpdateEntryFiles(Cleaner),
 
+    SlimApp,
+    ChooseSlimLanguages(Vec<Localization>),
+    ConfirmSlim(Vec<Localization>),
+    SlimFinished(Cleaner),
+
+    SizeBreakdownLoaded(SizeBreakdown),
+
     ClearList,
 
     ShowStatus(Status),
@@ -53,6 +65,10 @@ pub struct AppState {
     pub selected_file: Option<usize>,
     pub show_modal_ask: ModalAsk,
     pub pending_cleaner: Option<Cleaner>,
+    pub pending_name_matches: Option<Vec<i32>>,
+    pub pending_slim: Option<Vec<String>>,
+    pub choosing_slim: bool,
+    pub size_breakdown: Option<SizeBreakdown>,
 
     pub icon_cache: HashMap<String, image::Handle>,
 
@@ -66,6 +82,10 @@ impl AppState {
         let selected_file = None;
         let show_modal_ask = ModalAsk::default();
         let pending_cleaner = None;
+        let pending_name_matches = None;
+        let pending_slim = None;
+        let choosing_slim = false;
+        let size_breakdown = None;
 
         let icon_cache = HashMap::new();
 
@@ -77,6 +97,10 @@ impl AppState {
             selected_file,
             show_modal_ask,
             pending_cleaner,
+            pending_name_matches,
+            pending_slim,
+            choosing_slim,
+            size_breakdown,
 
             icon_cache,
 
@@ -89,6 +113,10 @@ impl AppState {
         self.cleaner.reset();
         self.selected_file = None;
         self.pending_cleaner = None;
+        self.pending_name_matches = None;
+        self.pending_slim = None;
+        self.choosing_slim = false;
+        self.size_breakdown = None;
         self.show_status = Status::default();
     }
 
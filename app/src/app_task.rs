@@ -1,11 +1,32 @@
+use mini_logger::debug;
 use rfd::AsyncFileDialog;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 
-use cleaner::{Cleaner, IconCache};
+use cleaner::{Cleaner, IconCache, Localization, RuleConfig, ScanIndex, SizeBreakdown};
 use cleaner::{ErrorKind, Result};
 use simple_status::{StatusEmitter, status_emit};
 
+/// Scan index shared by every scan of the session and stored in
+/// the user cache folder between launches.
+static SCAN_INDEX: OnceLock<Mutex<ScanIndex>> = OnceLock::new();
+
+fn scan_index_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"))
+        .join("Library/Caches/Bristo/scan-index.plist")
+}
+
+/// User matching rules, read before every scan when present.
+/// See `RuleConfig` for the format.
+fn rule_config_path() -> PathBuf {
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"))
+        .join("Library/Application Support/Bristo/rules.yaml")
+}
+
 pub async fn set_input_path() -> Result<PathBuf> {
     let file = AsyncFileDialog::new()
         .set_title("Browse App")
@@ -35,6 +56,23 @@ pub async fn set_output_path() -> Result<PathBuf> {
     Ok(folder.path().to_path_buf())
 }
 
+pub async fn set_report_path(app_name: String) -> Result<PathBuf> {
+    let file = AsyncFileDialog::new()
+        .set_title("Save Report")
+        .set_file_name(format!("{app_name} uninstall report.html"))
+        .add_filter("HTML", &["html"])
+        .add_filter("Markdown", &["md"])
+        .save_file()
+        .await
+        .ok_or_else(|| {
+            ErrorKind::skipped()
+                .with_summary("Selection Canceled")
+                .with_reason("No report file selected")
+        })?;
+
+    Ok(file.path().to_path_buf())
+}
+
 pub async fn process_app(path: PathBuf, emitter: Option<Arc<StatusEmitter>>) -> Result<Cleaner> {
     let cleaner = tokio::task::spawn_blocking(move || {
         let progress_hook = |msg: std::borrow::Cow<'static, str>| {
@@ -84,9 +122,11 @@ pub async fn find_app_process_async(
 }
 
 pub async fn kill_app_process_async(
-    cleaner: Arc<Cleaner>,
+    mut cleaner: Cleaner,
     emitter: Option<Arc<StatusEmitter>>,
-) -> Result<()> {
+) -> (Result<()>, Cleaner) {
+    let fallback = cleaner.clone();
+
     tokio::task::spawn_blocking(move || {
         let progress_hook = |cur: usize, total: usize| {
             status_emit!(
@@ -96,18 +136,18 @@ pub async fn kill_app_process_async(
                 total: total,
             );
         };
-        cleaner.kill_app_process(Some(progress_hook))?;
+        let result = cleaner.kill_app_process(Some(progress_hook));
 
-        Ok(cleaner)
+        (result, cleaner)
     })
     .await
-    .map_err(|e| {
-        ErrorKind::failed()
+    .unwrap_or_else(|e| {
+        let error = ErrorKind::failed()
             .with_summary("Confirm and kill process failed")
-            .with_reason(e.to_string())
-    })??;
+            .with_reason(e.to_string());
 
-    Ok(())
+        (Err(error), fallback)
+    })
 }
 
 pub async fn scan_app_async(
@@ -140,7 +180,26 @@ pub async fn scan_app_async(
             );
         };
 
-        cleaner.scan_app_profile(progress_hook)?;
+        let rules_path = rule_config_path();
+        if rules_path.exists() {
+            cleaner.set_rule_config(RuleConfig::from_path(&rules_path)?)?;
+        }
+
+        let index_path = scan_index_path();
+        let mut index = SCAN_INDEX
+            .get_or_init(|| Mutex::new(ScanIndex::load(&index_path).unwrap_or_default()))
+            .lock()
+            .map_err(|e| {
+                ErrorKind::failed()
+                    .with_summary("Scan failed")
+                    .with_reason(format!("Scan index unavailable: {}", e))
+            })?;
+
+        cleaner.scan_app_profile_indexed(&mut index, progress_hook)?;
+
+        if let Err(err) = index.save(&index_path) {
+            debug!("Scan index not saved: {}", err);
+        }
 
         Ok(cleaner)
     })
@@ -182,6 +241,27 @@ pub async fn save_bom_logs_async(cleaner: Cleaner, log_dir: PathBuf) -> Result<(
         })?
 }
 
+pub async fn save_report_async(cleaner: Cleaner, file: PathBuf) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+        let is_markdown = file
+            .extension()
+            .map(|ext| ext == "md" || ext == "markdown")
+            .unwrap_or(false);
+
+        if is_markdown {
+            cleaner.export_markdown(&file)
+        } else {
+            cleaner.export_html(&file)
+        }
+    })
+    .await
+    .map_err(|e| {
+        ErrorKind::failed()
+            .with_summary("Save report failed")
+            .with_reason(e.to_string())
+    })?
+}
+
 pub async fn trash_app_async(mut cleaner: Cleaner) -> Result<Cleaner> {
     tokio::task::spawn_blocking(move || {
         cleaner.move_to_trash()?;
@@ -195,6 +275,56 @@ pub async fn trash_app_async(mut cleaner: Cleaner) -> Result<Cleaner> {
     })?
 }
 
+pub async fn find_localizations_async(cleaner: Cleaner) -> Result<Vec<Localization>> {
+    tokio::task::spawn_blocking(move || cleaner.as_app_profile().find_localizations())
+        .await
+        .map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Find localizations failed")
+                .with_reason(e.to_string())
+        })
+}
+
+pub async fn find_unused_localizations_async(
+    cleaner: Cleaner,
+    keep: Vec<String>,
+) -> Result<Vec<Localization>> {
+    tokio::task::spawn_blocking(move || {
+        cleaner
+            .as_app_profile()
+            .unused_localizations_with(&cleaner::RealFileSystem::new(), &keep)
+    })
+    .await
+    .map_err(|e| {
+        ErrorKind::failed()
+            .with_summary("Find localizations failed")
+            .with_reason(e.to_string())
+    })
+}
+
+pub async fn slim_app_async(mut cleaner: Cleaner, keep: Vec<String>) -> Result<Cleaner> {
+    tokio::task::spawn_blocking(move || {
+        cleaner.slim_app(&keep)?;
+        Ok(cleaner)
+    })
+    .await
+    .map_err(|e| {
+        ErrorKind::failed()
+            .with_summary("Slim app failed")
+            .with_reason(e.to_string())
+    })?
+}
+
+pub async fn size_breakdown_async(cleaner: Cleaner) -> Result<SizeBreakdown> {
+    tokio::task::spawn_blocking(move || cleaner.as_app_profile().size_breakdown())
+        .await
+        .map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Size breakdown failed")
+                .with_reason(e.to_string())
+        })
+}
+
 pub async fn get_icon_asset_async(path: PathBuf, target_size: f64) -> Result<IconCache> {
     let path_for_error = path.clone();
     let cache_option = tokio::task::spawn_blocking(move || IconCache::new(&path, target_size))
@@ -2,22 +2,29 @@ use crate::app_modal::ModalAskMessage;
 use crate::app_state::{AppMessage, AppState};
 use crate::app_status::Status;
 use crate::app_task::find_app_process_async;
+use crate::app_task::find_localizations_async;
+use crate::app_task::find_unused_localizations_async;
 use crate::app_task::get_icon_asset_async;
 use crate::app_task::kill_app_process_async;
 use crate::app_task::open_loc_async;
 use crate::app_task::process_app;
 use crate::app_task::save_bom_logs_async;
+use crate::app_task::save_report_async;
 use crate::app_task::scan_app_async;
 use crate::app_task::set_input_path;
 use crate::app_task::set_output_path;
+use crate::app_task::set_report_path;
+use crate::app_task::size_breakdown_async;
+use crate::app_task::slim_app_async;
 use crate::app_task::trash_app_async;
 
-use cleaner::ErrorKind;
+use cleaner::utility::format_size;
+use cleaner::{Cleaner, ErrorKind, Localization};
 use iced::{Subscription, Task, futures::StreamExt};
 use mini_logger::debug;
 use simple_status::{ChannelKind, create_channels};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
     match message {
@@ -108,62 +115,139 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
         }
 
         AppMessage::ConfirmKill(cleaner) => {
-            state.pending_cleaner = Some(cleaner);
+            let app_name = cleaner.as_app_profile().as_metadata().as_name().to_string();
+            let process_entry = cleaner.as_app_profile().as_process_entry();
+            let name_matches = name_match_pids(&cleaner);
 
-            state.show_modal_ask.set_message(format!(
+            if name_matches.len() == process_entry.list().len() {
+                // Only name matches, ask about them directly.
+                state
+                    .show_modal_ask
+                    .set_message(name_match_message(&app_name));
+                state.pending_name_matches = Some(name_matches);
+            } else {
+                state.show_modal_ask.set_message(format!(
                     "The app '{}' is still running.\nDo you want to kill its running process?\nBe careful to save your work first before continuing.",
-                    state.pending_cleaner
-                        .as_ref()
-                        .unwrap()
-                        .as_app_profile()
-                        .as_metadata()
-                        .as_name()
+                    app_name
                 ));
+            }
+
+            state
+                .show_modal_ask
+                .set_details(process_tree_lines(&cleaner));
+            state.pending_cleaner = Some(cleaner);
 
             Task::none()
         }
 
         AppMessage::ModalAsk(msg) => match msg {
+            ModalAskMessage::ToggleChoice(index) => {
+                state
+                    .show_modal_ask
+                    .update(ModalAskMessage::ToggleChoice(index));
+                Task::none()
+            }
+
             ModalAskMessage::ConfirmMsg(answer) => {
+                let selected = state.show_modal_ask.selected_choices();
                 state
                     .show_modal_ask
                     .update(ModalAskMessage::ConfirmMsg(answer));
-                let channel = create_channels(100, ChannelKind::Mpsc);
 
-                let cleaner = state.pending_cleaner.take().unwrap();
-                if !answer {
+                // First slim question: the languages to keep.
+                if state.choosing_slim {
+                    state.choosing_slim = false;
+
+                    if !answer {
+                        let status = Status::new().with_status_error(
+                            ErrorKind::skipped().with_reason("Slim app cancelled"),
+                        );
+                        return Task::done(AppMessage::ShowStatus(status));
+                    }
+
+                    state.pending_slim = Some(selected.clone());
+                    return Task::perform(
+                        find_unused_localizations_async(state.cleaner.clone(), selected),
+                        |res| match res {
+                            Ok(unused) => AppMessage::ConfirmSlim(unused),
+                            Err(err) => {
+                                AppMessage::ShowStatus(Status::new().with_status_error(err))
+                            }
+                        },
+                    );
+                }
+
+                if let Some(keep) = state.pending_slim.take() {
+                    if !answer {
+                        let status = Status::new().with_status_error(
+                            ErrorKind::skipped().with_reason("Slim app cancelled"),
+                        );
+                        return Task::done(AppMessage::ShowStatus(status));
+                    }
+
+                    let cleaner = state.cleaner.clone();
+                    return Task::perform(slim_app_async(cleaner, keep), |res| match res {
+                        Ok(cleaner) => AppMessage::SlimFinished(cleaner),
+                        Err(err) => AppMessage::ShowStatus(Status::new().with_status_error(err)),
+                    });
+                }
+
+                let Some(mut cleaner) = state.pending_cleaner.take() else {
+                    return Task::none();
+                };
+
+                let cancelled = || {
                     let status = Status::new().with_status_error(
                         ErrorKind::skipped().with_reason("Process termination cancelled"),
                     );
 
+                    Task::done(AppMessage::ShowStatus(status))
+                };
+
+                // Second question: processes matched by name only.
+                if let Some(name_matches) = state.pending_name_matches.take() {
+                    let only_name_matches = name_matches.len()
+                        == cleaner.as_app_profile().as_process_entry().list().len();
+
+                    if answer {
+                        cleaner.confirm_processes(name_matches);
+                    } else if only_name_matches {
+                        return Task::batch(vec![
+                            cancelled(),
+                            Task::done(AppMessage::ScanApp(cleaner)),
+                        ]);
+                    }
+
+                    return kill_task(cleaner);
+                }
+
+                if !answer {
                     return Task::batch(vec![
-                        Task::done(AppMessage::ShowStatus(status)),
+                        cancelled(),
                         Task::done(AppMessage::ScanApp(cleaner)),
                     ]);
                 }
 
-                let emitter = channel.get_emitter();
-                let cleaner_arc = Arc::new(cleaner);
-
-                let confirm_task = Task::perform(
-                    kill_app_process_async(cleaner_arc.clone(), Some(emitter)),
-                    move |res| {
-                        let cleaner = Arc::try_unwrap(cleaner_arc).unwrap_or_else(|c| (*c).clone());
-                        AppMessage::KillFinished(res, cleaner)
-                    },
-                );
+                let name_matches = name_match_pids(&cleaner);
+                if !name_matches.is_empty() {
+                    let app_name = cleaner.as_app_profile().as_metadata().as_name();
+
+                    state
+                        .show_modal_ask
+                        .set_message(name_match_message(app_name));
+                    state.show_modal_ask.set_details(
+                        process_tree_lines(&cleaner)
+                            .into_iter()
+                            .filter(|line| line.contains(NAME_MATCH_MARK))
+                            .collect(),
+                    );
+                    state.pending_name_matches = Some(name_matches);
+                    state.pending_cleaner = Some(cleaner);
 
-                let status_task = channel
-                    .stream()
-                    .map(|s| {
-                        Task::stream(s.map(|status_event| {
-                            let wrapped_status = Status::new().with_status_event(status_event);
-                            AppMessage::ShowStatus(wrapped_status)
-                        }))
-                    })
-                    .unwrap_or_else(Task::none);
+                    return Task::none();
+                }
 
-                Task::batch(vec![confirm_task, status_task])
+                kill_task(cleaner)
             }
         },
 
@@ -209,7 +293,7 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
         AppMessage::UpdateCleaner(cleaner) => {
             state.cleaner = cleaner;
 
-            let mut tasks = Vec::new();
+            let mut tasks = vec![size_breakdown_task(&state.cleaner)];
 
             for (_i, entry) in state.cleaner.all_entries_enumerate() {
                 let path_buf = entry.as_path().to_path_buf();
@@ -291,6 +375,32 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
             })
         }
 
+        AppMessage::SaveReportLoc => {
+            let app_name = state.cleaner.as_app_profile().as_metadata().as_name();
+
+            Task::perform(set_report_path(app_name.to_string()), |res| match res {
+                Ok(file) => AppMessage::SaveReport(file),
+                Err(e) => {
+                    let event = Status::new().with_status_error(e);
+                    AppMessage::ShowStatus(event)
+                }
+            })
+        }
+
+        AppMessage::SaveReport(file) => {
+            let cleaner = state.cleaner.clone();
+            Task::perform(save_report_async(cleaner, file), |res| match res {
+                Ok(()) => {
+                    let event = Status::new().with_status_success("Report saved successfully");
+                    AppMessage::ShowStatus(event)
+                }
+                Err(err) => {
+                    let event = Status::new().with_status_error(err);
+                    AppMessage::ShowStatus(event)
+                }
+            })
+        }
+
         AppMessage::MoveToTrash => {
             let cleaner = std::mem::take(&mut state.cleaner);
 
@@ -304,9 +414,26 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
             state.cleaner = cleaner;
 
             let failed = state.cleaner.as_trash_entry().failed_path();
+            let admin_jobs = state
+                .cleaner
+                .as_control_entry()
+                .services_requiring_admin()
+                .len();
+            let admin_note = format!(
+                "{admin_jobs} launch {} administrator rights to unload",
+                if admin_jobs == 1 {
+                    "job needs"
+                } else {
+                    "jobs need"
+                }
+            );
 
             if failed.is_empty() {
-                state.show_status = Status::new().with_status_success("App moved to Trash");
+                state.show_status = match admin_jobs {
+                    0 => Status::new().with_status_success("App moved to Trash"),
+                    _ => Status::new()
+                        .with_status_success(format!("App moved to Trash, {admin_note}")),
+                };
             } else {
                 let mut missing = 0usize;
                 let mut grouped: HashMap<ErrorKind, usize> = HashMap::new();
@@ -331,6 +458,10 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
                     report.push(format!("{missing} items path not exist"));
                 }
 
+                if admin_jobs > 0 {
+                    report.push(admin_note);
+                }
+
                 report.extend(items.into_iter().map(|(error, count)| {
                     let items = if count == 1 { "item" } else { "items" };
 
@@ -356,6 +487,137 @@ pub fn update(state: &mut AppState, message: AppMessage) -> Task<AppMessage> {
             Task::none()
         }
 
+        AppMessage::SlimApp => {
+            if state.app_path.as_os_str().is_empty() {
+                return Task::none();
+            }
+
+            Task::perform(
+                find_localizations_async(state.cleaner.clone()),
+                |res| match res {
+                    Ok(localizations) => AppMessage::ChooseSlimLanguages(localizations),
+                    Err(err) => AppMessage::ShowStatus(Status::new().with_status_error(err)),
+                },
+            )
+        }
+
+        AppMessage::ChooseSlimLanguages(localizations) => {
+            let app_name = state.cleaner.as_app_profile().as_metadata().as_name();
+
+            // One choice per localization name, preselected from
+            // the user's preferred languages.
+            let preferred = Localization::preferred_languages();
+            let mut choices: Vec<(String, bool)> = Vec::new();
+            for localization in localizations.iter().filter(|l| l.as_name() != "Base") {
+                if choices
+                    .iter()
+                    .any(|(name, _)| name == localization.as_name())
+                {
+                    continue;
+                }
+                choices.push((
+                    localization.as_name().to_string(),
+                    localization.is_kept(&preferred),
+                ));
+            }
+            choices.sort();
+
+            if choices.is_empty() {
+                state.show_status =
+                    Status::new().with_status_success(format!("'{app_name}' has no localizations"));
+                return Task::none();
+            }
+
+            state.show_modal_ask.set_message(format!(
+                "Choose the languages to keep in '{app_name}'.\nBase and development languages are always kept."
+            ));
+            state.show_modal_ask.set_choices(choices);
+            state.choosing_slim = true;
+
+            Task::none()
+        }
+
+        AppMessage::ConfirmSlim(unused) => {
+            let Some(keep) = state.pending_slim.as_ref() else {
+                return Task::none();
+            };
+
+            let app_name = state.cleaner.as_app_profile().as_metadata().as_name();
+
+            if unused.is_empty() {
+                state.pending_slim = None;
+                state.show_status = Status::new()
+                    .with_status_success(format!("'{app_name}' has no unused localizations"));
+                return Task::none();
+            }
+
+            let total: u64 = unused.iter().map(|l| l.size()).sum();
+            let mut message = format!(
+                "Move {} unused localizations of '{}' ({}) to Trash?\nKeeping: {}",
+                unused.len(),
+                app_name,
+                format_size(total),
+                if keep.is_empty() {
+                    "Base and development languages only".to_string()
+                } else {
+                    keep.join(", ")
+                }
+            );
+
+            if let Some(warning) = state.cleaner.as_app_profile().signature_warning() {
+                message.push_str(&format!("\n\n{warning}"));
+            }
+
+            state.show_modal_ask.set_message(message);
+            state
+                .show_modal_ask
+                .set_details(localization_lines(&state.app_path, &unused));
+
+            Task::none()
+        }
+
+        AppMessage::SlimFinished(cleaner) => {
+            state.cleaner = cleaner;
+            let breakdown_task = size_breakdown_task(&state.cleaner);
+
+            let trash = state.cleaner.as_slim_entry();
+            let moved = trash.moved_path().len();
+            let failed = trash.failed_path().len();
+
+            state.show_status = if failed == 0 {
+                Status::new().with_status_success(format!("{moved} localizations moved to Trash"))
+            } else {
+                let reason = trash
+                    .failed_path()
+                    .iter()
+                    .map(|(path, error)| {
+                        format!(
+                            "{} - {}",
+                            path.as_name(),
+                            error.reason().unwrap_or("Unknown")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                Status::new().with_status_error(
+                    ErrorKind::failed()
+                        .with_summary(format!(
+                            "{failed} of {} localizations not moved",
+                            moved + failed
+                        ))
+                        .with_reason(reason),
+                )
+            };
+
+            breakdown_task
+        }
+
+        AppMessage::SizeBreakdownLoaded(breakdown) => {
+            state.size_breakdown = Some(breakdown);
+            Task::none()
+        }
+
         AppMessage::ClearList => {
             state.reset();
             Task::none()
@@ -378,3 +640,128 @@ pub fn subscription(_state: &AppState) -> Subscription<AppMessage> {
 
     Subscription::batch(vec![file_drop_sub])
 }
+
+/// Suffix marking processes matched by name or command line only.
+const NAME_MATCH_MARK: &str = "  [name match only]";
+
+/// Returns the PIDs of processes matched by name or command line
+/// only, which are killed only after a separate confirmation.
+fn name_match_pids(cleaner: &Cleaner) -> Vec<i32> {
+    cleaner
+        .as_app_profile()
+        .as_process_entry()
+        .requiring_confirmation()
+        .into_iter()
+        .map(|process| process.pid())
+        .collect()
+}
+
+fn name_match_message(app_name: &str) -> String {
+    format!(
+        "These processes only mention '{}' in their name or command line and may be unrelated.\nDo you want to kill them too?",
+        app_name
+    )
+}
+
+/// Kills the discovered processes, streaming progress as status
+/// events.
+fn kill_task(cleaner: Cleaner) -> Task<AppMessage> {
+    let channel = create_channels(100, ChannelKind::Mpsc);
+
+    let emitter = channel.get_emitter();
+
+    let confirm_task = Task::perform(
+        kill_app_process_async(cleaner, Some(emitter)),
+        |(res, cleaner)| AppMessage::KillFinished(res, cleaner),
+    );
+
+    let status_task = channel
+        .stream()
+        .map(|s| {
+            Task::stream(s.map(|status_event| {
+                let wrapped_status = Status::new().with_status_event(status_event);
+                AppMessage::ShowStatus(wrapped_status)
+            }))
+        })
+        .unwrap_or_else(Task::none);
+
+    Task::batch(vec![confirm_task, status_task])
+}
+
+/// Formats the discovered processes as an indented tree.
+///
+/// Each line shows the process name, PID, owner, CPU and memory
+/// usage, and how long the process has been running.
+fn process_tree_lines(cleaner: &Cleaner) -> Vec<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    cleaner
+        .as_app_profile()
+        .as_process_entry()
+        .tree()
+        .into_iter()
+        .map(|(depth, process)| {
+            let branch = if depth == 0 { "" } else { "└ " };
+            let indent = "   ".repeat(depth.saturating_sub(1));
+            let memory_mb = process.memory() as f64 / (1024.0 * 1024.0);
+            let running = now.saturating_sub(process.start_time());
+
+            let mark = match process.requires_confirmation() {
+                true => NAME_MATCH_MARK,
+                false => "",
+            };
+
+            format!(
+                "{indent}{branch}{} ({})  {}  CPU {:.1}%  {:.1} MB  up {}{mark}",
+                process.as_name(),
+                process.pid(),
+                process.as_user(),
+                process.cpu_usage(),
+                memory_mb,
+                format_elapsed(running),
+            )
+        })
+        .collect()
+}
+
+/// Formats localization folders with their size and location
+/// relative to the application bundle.
+fn localization_lines(app_path: &std::path::Path, unused: &[Localization]) -> Vec<String> {
+    unused
+        .iter()
+        .map(|localization| {
+            let path = localization.as_path_data().as_path();
+            let location = path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(app_path).ok())
+                .map(|parent| parent.display().to_string())
+                .unwrap_or_default();
+
+            format!(
+                "{:<16} {:>10}  {}",
+                localization.as_path_data().as_name(),
+                format_size(localization.size()),
+                location
+            )
+        })
+        .collect()
+}
+
+fn size_breakdown_task(cleaner: &Cleaner) -> Task<AppMessage> {
+    Task::perform(size_breakdown_async(cleaner.clone()), |res| match res {
+        Ok(breakdown) => AppMessage::SizeBreakdownLoaded(breakdown),
+        Err(err) => AppMessage::ShowStatus(Status::new().with_status_error(err)),
+    })
+}
+
+fn format_elapsed(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m {}s", seconds / 60, seconds % 60),
+        3600..86400 => format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60),
+        _ => format!("{}d {}h", seconds / 86400, (seconds % 86400) / 3600),
+    }
+}
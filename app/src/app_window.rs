@@ -9,6 +9,8 @@ use iced::{Element, Length};
 
 use crate::app_state::{AppMessage, AppState};
 use crate::ui_element::{ButtonThemeStyle, CustomStyle};
+use cleaner::SizeBreakdown;
+use cleaner::utility::format_size;
 // use crate::app_tree_view::TreeView;
 use crate::ui_element::DropFile;
 use crate::ui_element::{Cell, ContentCell, HeaderCell, Table};
@@ -153,6 +155,91 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
         list_view
     };
 
+    // ==================== app header ====================
+    let metadata = state.cleaner.as_app_profile().as_metadata();
+
+    let app_title = if metadata.as_short_version().is_empty() {
+        metadata.as_name().to_string()
+    } else {
+        format!("{} {}", metadata.as_name(), metadata.as_short_version())
+    };
+
+    let signing_summary = metadata
+        .as_signing_info()
+        .map(|info| info.to_string())
+        .unwrap_or_else(|| "Code signature unavailable".to_string());
+
+    let shared_libraries: Vec<&str> = state
+        .cleaner
+        .as_app_profile()
+        .as_path_entry()
+        .as_shared_libraries()
+        .iter()
+        .map(|library| library.as_name())
+        .collect();
+
+    let origin_files = state
+        .cleaner
+        .as_app_profile()
+        .as_path_entry()
+        .as_origin_files()
+        .len();
+
+    let header_column = Column::new()
+        .push(text(app_title).size(14).style(|_| text::Style {
+            color: Some(Color::WHITE),
+        }))
+        .push(text(signing_summary).size(11).style(|_| text::Style {
+            color: Some(Color::from_rgb8(160, 160, 160)),
+        }))
+        .spacing(2);
+
+    let header_column = if shared_libraries.is_empty() {
+        header_column
+    } else {
+        header_column.push(
+            text(format!(
+                "Shared libraries kept: {}",
+                shared_libraries.join(", ")
+            ))
+            .size(11)
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb8(160, 160, 160)),
+            }),
+        )
+    };
+
+    let header_column = if origin_files == 0 {
+        header_column
+    } else {
+        header_column.push(
+            text(format!(
+                "Origin matches kept: {} files downloaded or opened by the app",
+                origin_files
+            ))
+            .size(11)
+            .style(|_| text::Style {
+                color: Some(Color::from_rgb8(160, 160, 160)),
+            }),
+        )
+    };
+
+    let size_lines = state
+        .size_breakdown
+        .as_ref()
+        .map(size_breakdown_lines)
+        .unwrap_or_default();
+
+    let header_column = size_lines.into_iter().fold(header_column, |column, line| {
+        column.push(text(line).size(11).style(|_| text::Style {
+            color: Some(Color::from_rgb8(160, 160, 160)),
+        }))
+    });
+
+    let app_header = Container::new(header_column)
+        .width(Length::Fill)
+        .padding([0, 5]);
+
     let button_export_bom_files_active = button(text("Export Bom Logs").size(12))
         .width(Length::Shrink)
         .on_press(AppMessage::ExportBomFilesLoc)
@@ -175,6 +262,21 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
         Container::new(button_export_bom_files_disabled)
     };
 
+    let button_save_report = if !state.app_path.as_os_str().is_empty() {
+        Container::new(
+            button(text("Save report…").size(12))
+                .width(Length::Shrink)
+                .on_press(AppMessage::SaveReportLoc)
+                .custom_style(ButtonThemeStyle::CustomRounded),
+        )
+    } else {
+        Container::new(
+            button(text("Save report…").size(12))
+                .width(Length::Shrink)
+                .custom_style(ButtonThemeStyle::CustomRounded),
+        )
+    };
+
     let button_clear_list = Container::new(
         button(text("Clear list").size(12))
             .width(Length::Fill)
@@ -238,6 +340,24 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
         None => status,
     };
 
+    let button_slim = if has_real_items {
+        Container::new(
+            button(text("Slim app").size(12))
+                .width(Length::Fill)
+                .custom_style(ButtonThemeStyle::CustomRounded)
+                .on_press(AppMessage::SlimApp),
+        )
+    } else {
+        Container::new(
+            button(text("Slim app").size(12))
+                .width(Length::Fill)
+                .custom_style(ButtonThemeStyle::CustomRounded),
+        )
+    }
+    .width(Length::Shrink)
+    .align_x(alignment::Horizontal::Center)
+    .align_y(alignment::Vertical::Center);
+
     let button_delete = Container::new(
         button(text("Move to Trash").size(12))
             .width(Length::Fill)
@@ -258,6 +378,7 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
     let top = Container::new(
         Row::new()
             .push(button_export_bom_files)
+            .push(button_save_report)
             .push(Space::new().width(Length::Fill))
             .push(button_re_scan)
             .push(button_clear_list)
@@ -268,8 +389,14 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
             .height(Length::Shrink),
     );
 
-    let center = Container::new(
+    let center_column = if has_real_items {
+        Column::new().push(app_header)
+    } else {
         Column::new()
+    };
+
+    let center = Container::new(
+        center_column
             .push(center_view)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -293,6 +420,7 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
     let bottom = Container::new(
         Row::new()
             .push(status)
+            .push(button_slim)
             .push(button_delete)
             .align_y(alignment::Vertical::Center)
             .spacing(5),
@@ -324,3 +452,65 @@ pub fn view(state: &AppState) -> Element<'_, AppMessage> {
         content
     }
 }
+
+/// Summarizes a size breakdown in three lines: the bundle
+/// sections, the leftovers, and the largest files.
+///
+/// Empty sections and categories are left out.
+fn size_breakdown_lines(breakdown: &SizeBreakdown) -> Vec<String> {
+    let architectures = breakdown
+        .as_architectures()
+        .iter()
+        .map(|(arch, size)| format!("{arch} {}", format_size(*size)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let sections = breakdown
+        .as_sections()
+        .iter()
+        .filter(|(_, size)| *size > 0)
+        .map(|(section, size)| match section {
+            cleaner::BundleSection::Executables if !architectures.is_empty() => format!(
+                "{} {} ({architectures})",
+                section.as_str(),
+                format_size(*size)
+            ),
+            _ => format!("{} {}", section.as_str(), format_size(*size)),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    let mut lines = vec![format!(
+        "Bundle {}: {sections}",
+        format_size(breakdown.bundle_size())
+    )];
+
+    if breakdown.leftover_size() > 0 {
+        let leftovers = breakdown
+            .as_leftovers()
+            .iter()
+            .filter(|(_, size)| *size > 0)
+            .map(|(category, size)| format!("{} {}", category.as_str(), format_size(*size)))
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        lines.push(format!(
+            "Leftovers {}: {leftovers}",
+            format_size(breakdown.leftover_size())
+        ));
+    }
+
+    let largest = breakdown
+        .as_largest_files()
+        .iter()
+        .take(3)
+        .map(|(path, size)| format!("{} {}", path.as_name(), format_size(*size)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if !largest.is_empty() {
+        lines.push(format!("Largest files: {largest}"));
+    }
+
+    lines
+}
@@ -0,0 +1,147 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Matching benchmark over a synthetic 500k-entry tree.
+//!
+//! Compares rebuilding `MatchRules` for every visited path, as the
+//! scanners used to, with evaluating rules compiled once.
+//!
+//! Run with:
+//!
+//! ```text
+//! cargo bench -p cleaner --bench matcher_bench
+//! ```
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use cleaner::MemoryFileSystem;
+use cleaner::utility::{MatchRules, scan_general_with};
+
+const GROUPS: usize = 500;
+const DIRS_PER_GROUP: usize = 10;
+const FILES_PER_DIR: usize = 100;
+
+const IDENTIFIERS: [&str; 6] = [
+    "com.example.cafe",
+    "cafe",
+    "example",
+    "com.example.cafe.helper",
+    "com.example.cafe.launcher",
+    "com.example.cafe.share",
+];
+
+fn build_tree(root: &Path) -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+
+    for group in 0..GROUPS {
+        for dir in 0..DIRS_PER_GROUP {
+            let dir_path = root
+                .join(format!("Vendor {group}"))
+                .join(format!("com.vendor{group}.Product{dir}"));
+
+            for file in 0..FILES_PER_DIR {
+                let name = match file % 50 {
+                    0 => format!("com.example.cafe.{group}.{dir}.plist"),
+                    1 => format!("Café Cache {file}"),
+                    _ => format!("Résumé Data {group}-{dir}-{file}.db"),
+                };
+                fs.create_file(dir_path.join(name), Vec::new());
+            }
+        }
+    }
+
+    fs
+}
+
+fn rules() -> MatchRules<'static> {
+    IDENTIFIERS.iter().fold(
+        MatchRules::new().equal("Café").equal("Cafe Helper"),
+        |rules, id| rules.contain(id),
+    )
+}
+
+fn measure<F: FnMut() -> usize>(label: &str, mut run: F) -> Duration {
+    // Warm up once so allocation of the first run is not counted.
+    black_box(run());
+
+    let start = Instant::now();
+    let matches = black_box(run());
+    let elapsed = start.elapsed();
+
+    println!("{label:<28} {elapsed:>12.2?}  ({matches} matches)");
+    elapsed
+}
+
+fn main() {
+    let root = PathBuf::from("/Users/bench/Library");
+
+    let start = Instant::now();
+    let fs = build_tree(&root);
+    println!(
+        "built {} entries in {:.2?}",
+        GROUPS * DIRS_PER_GROUP * (FILES_PER_DIR + 1) + GROUPS,
+        start.elapsed()
+    );
+
+    let paths: Vec<PathBuf> = cleaner::FileSystem::walk(&fs, &root, 3)
+        .map(|entry| entry.into_path())
+        .collect();
+
+    println!("\nrule evaluation over {} paths", paths.len());
+
+    let rebuilt = measure("MatchRules per path", || {
+        paths.iter().filter(|path| rules().check_path(path)).count()
+    });
+
+    let compiled_rules = rules().compile();
+    let compiled = measure("CompiledRules", || {
+        paths
+            .iter()
+            .filter(|path| compiled_rules.check_path(path))
+            .count()
+    });
+
+    println!(
+        "speedup                      {:>11.1}x",
+        rebuilt.as_secs_f64() / compiled.as_secs_f64()
+    );
+
+    println!("\nscan_general_with over the in-memory tree");
+
+    let scan = |is_match: &(dyn Fn(&Path) -> bool + Send + Sync)| {
+        scan_general_with(
+            &fs,
+            std::slice::from_ref(&root),
+            3,
+            |_, _| {},
+            is_match,
+            |path| path,
+        )
+        .len()
+    };
+
+    let rebuilt = measure("MatchRules per path", || {
+        scan(&|path: &Path| rules().check_path(path))
+    });
+    let compiled = measure("CompiledRules", || {
+        scan(&|path: &Path| compiled_rules.check_path(path))
+    });
+
+    println!(
+        "speedup                      {:>11.1}x",
+        rebuilt.as_secs_f64() / compiled.as_secs_f64()
+    );
+}
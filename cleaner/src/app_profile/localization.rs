@@ -0,0 +1,353 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Application localizations.
+//!
+//! Bundles ship one `*.lproj` folder per supported language, in
+//! the application and in every nested framework:
+//!
+//! ```text
+//! Cafe.app/Contents/Resources/en.lproj
+//! Cafe.app/Contents/Resources/de.lproj
+//! Cafe.app/Contents/Frameworks/Kit.framework/Resources/ja.lproj
+//! ```
+//!
+//! Folders for languages the user never uses can be removed to
+//! slim the application without uninstalling it.
+//!
+//! Note:
+//! `Base.lproj` holds the base internationalization resources
+//! loaded for every language, and the folder named by
+//! `CFBundleDevelopmentRegion` is the fallback of every lookup.
+//! Both are always kept.
+//!..
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::path_data::PathData;
+use crate::utility::{FileSystem, FsEntry, RealFileSystem};
+
+/// Localization folder that is loaded for every language.
+const BASE_LOCALIZATION: &str = "Base";
+
+/// Global preferences file, relative to the home directory.
+const GLOBAL_PREFERENCES: &str = "Library/Preferences/.GlobalPreferences.plist";
+
+/// Language kept when no preference can be read.
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Bundle key naming the localization used as fallback.
+const DEVELOPMENT_REGION_KEY: &str = "CFBundleDevelopmentRegion";
+
+/// Legacy folder names used before ISO language codes.
+const LEGACY_NAMES: [(&str, &str); 7] = [
+    ("English", "en"),
+    ("French", "fr"),
+    ("German", "de"),
+    ("Japanese", "ja"),
+    ("Spanish", "es"),
+    ("Italian", "it"),
+    ("Dutch", "nl"),
+];
+
+/// Localization folder inside a bundle.
+///
+/// Doc:
+/// Each entry stores:
+///
+/// - The `*.lproj` folder path.
+/// - Its localization name, such as `en`, `pt_BR` or `zh-Hans`.
+/// - Its size in bytes.
+/// - The development region of the bundle holding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Localization {
+    path: PathData,
+    name: String,
+    size: u64,
+    development_region: Option<String>,
+}
+
+impl Localization {
+    /// Lists the localization folders of a bundle, nested bundles
+    /// included, sorted by path.
+    ///
+    /// Doc:
+    /// The development region of each folder is read from the
+    /// `Info.plist` of the bundle holding it, which sits next to
+    /// `Resources` in frameworks and one level above it in
+    /// applications:
+    ///
+    /// ```text
+    /// Kit.framework/Resources/Info.plist
+    /// Cafe.app/Contents/Info.plist
+    /// ```
+    pub fn find_with<FS>(fs: &FS, bundle_path: &Path) -> Vec<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let folders: Vec<PathBuf> = fs
+            .walk(bundle_path, usize::MAX)
+            .filter(|entry| entry.is_dir())
+            .map(FsEntry::into_path)
+            .filter(|path| path.extension().map(|ext| ext == "lproj").unwrap_or(false))
+            .collect();
+
+        let mut regions: HashMap<PathBuf, Option<String>> = HashMap::new();
+
+        let mut localizations: Vec<Self> = folders
+            .into_iter()
+            .map(|folder| {
+                let size = fs.total_size(&folder);
+
+                let resources = folder.parent().unwrap_or(Path::new("/")).to_path_buf();
+                let development_region = regions
+                    .entry(resources)
+                    .or_insert_with_key(|resources| Self::development_region_with(fs, resources))
+                    .clone();
+
+                let name = folder
+                    .file_stem()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let display = folder
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                Self {
+                    path: PathData::new(folder, display),
+                    name,
+                    size,
+                    development_region,
+                }
+            })
+            .collect();
+
+        localizations.sort_by(|a, b| a.path.as_path().cmp(b.path.as_path()));
+        localizations
+    }
+
+    /// Reads `CFBundleDevelopmentRegion` of the bundle owning a
+    /// `Resources` folder.
+    fn development_region_with<FS>(fs: &FS, resources: &Path) -> Option<String>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        [Some(resources), resources.parent()]
+            .into_iter()
+            .flatten()
+            .map(|dir| dir.join("Info.plist"))
+            .filter(|plist| fs.is_file(plist))
+            .find_map(|plist| {
+                let value = fs.read_plist(&plist).ok()?;
+                let region = value
+                    .as_dictionary()?
+                    .get(DEVELOPMENT_REGION_KEY)?
+                    .as_string()?
+                    .trim()
+                    .to_string();
+
+                (!region.is_empty()).then_some(region)
+            })
+    }
+
+    /// Returns the user's preferred languages.
+    ///
+    /// See `preferred_languages_with()`.
+    pub fn preferred_languages() -> Vec<String> {
+        Self::preferred_languages_with(&RealFileSystem::new())
+    }
+
+    /// Returns the user's preferred languages through the
+    /// provided `FileSystem`.
+    ///
+    /// Doc:
+    /// Reads `AppleLanguages` from the global preferences of the
+    /// current user, in order of preference:
+    ///
+    /// ```text
+    /// en-US, de-DE, ja
+    /// ```
+    ///
+    /// Note:
+    /// Returns `en` when the preference cannot be read, so that
+    /// an application is never left without a localization.
+    pub fn preferred_languages_with<FS>(fs: &FS) -> Vec<String>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let home = env::var("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"));
+
+        let languages: Vec<String> = fs
+            .read_plist(&home.join(GLOBAL_PREFERENCES))
+            .ok()
+            .and_then(|plist| {
+                plist
+                    .as_dictionary()?
+                    .get("AppleLanguages")?
+                    .as_array()
+                    .cloned()
+            })
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|language| language.as_string())
+            .map(ToOwned::to_owned)
+            .collect();
+
+        if languages.is_empty() {
+            return vec![FALLBACK_LANGUAGE.to_string()];
+        }
+
+        languages
+    }
+
+    /// get folder path reference
+    pub fn as_path_data(&self) -> &PathData {
+        &self.path
+    }
+
+    /// get localization name reference
+    pub fn as_name(&self) -> &str {
+        &self.name
+    }
+
+    /// get the copy of folder size in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// get development region reference
+    pub fn as_development_region(&self) -> Option<&str> {
+        self.development_region.as_deref()
+    }
+
+    /// Returns the language code of the localization.
+    ///
+    /// Doc:
+    /// Region and script suffixes are dropped and legacy names
+    /// are mapped to their code:
+    ///
+    /// ```text
+    /// pt_BR   -> pt
+    /// zh-Hans -> zh
+    /// English -> en
+    /// ```
+    pub fn language(&self) -> String {
+        LocaleTag::parse(&self.name).language
+    }
+
+    /// Returns whether the localization is kept for a language
+    /// set.
+    ///
+    /// Doc:
+    /// Languages in `keep` are resolved the way macOS picks a
+    /// localization, comparing the language, then the script,
+    /// then the region. A part missing on either side matches
+    /// any value:
+    ///
+    /// ```text
+    /// keep zh-Hans-CN -> zh.lproj, zh-Hans.lproj, zh_CN.lproj
+    /// keep pt         -> pt.lproj, pt_BR.lproj, pt_PT.lproj
+    /// ```
+    ///
+    /// `-` and `_` are equivalent and comparison ignores case.
+    ///
+    /// Note:
+    /// `Base.lproj` and the development region of the bundle
+    /// are always kept.
+    pub fn is_kept(&self, keep: &[String]) -> bool {
+        if self.name == BASE_LOCALIZATION {
+            return true;
+        }
+
+        let tag = LocaleTag::parse(&self.name);
+
+        keep.iter()
+            .map(String::as_str)
+            .chain(self.development_region.as_deref())
+            .any(|kept| tag.is_covered_by(&LocaleTag::parse(kept)))
+    }
+}
+
+/// Language, script and region of a localization name.
+///
+/// Doc:
+/// Parses BCP 47 tags and the older underscore form alike:
+///
+/// ```text
+/// zh-Hans-CN -> zh, Hans, CN
+/// pt_BR      -> pt, -, BR
+/// es-419     -> es, -, 419
+/// ```
+///
+/// Parts are stored in lowercase.
+struct LocaleTag {
+    language: String,
+    script: Option<String>,
+    region: Option<String>,
+}
+
+impl LocaleTag {
+    fn parse(name: &str) -> Self {
+        if let Some((_, code)) = LEGACY_NAMES.iter().find(|(legacy, _)| *legacy == name) {
+            return Self {
+                language: code.to_string(),
+                script: None,
+                region: None,
+            };
+        }
+
+        let mut parts = name.split(['_', '-']).map(str::to_lowercase);
+        let language = parts.next().unwrap_or_default();
+        let mut script = None;
+        let mut region = None;
+
+        for part in parts {
+            let is_alpha = part.chars().all(|c| c.is_ascii_alphabetic());
+            let is_digit = part.chars().all(|c| c.is_ascii_digit());
+
+            match part.len() {
+                4 if is_alpha && script.is_none() && region.is_none() => script = Some(part),
+                2 if is_alpha && region.is_none() => region = Some(part),
+                3 if is_digit && region.is_none() => region = Some(part),
+                _ => {}
+            }
+        }
+
+        Self {
+            language,
+            script,
+            region,
+        }
+    }
+
+    /// Returns whether a folder with this tag serves the kept
+    /// tag.
+    fn is_covered_by(&self, kept: &LocaleTag) -> bool {
+        let part_matches = |own: &Option<String>, kept: &Option<String>| match (own, kept) {
+            (Some(own), Some(kept)) => own == kept,
+            _ => true,
+        };
+
+        self.language == kept.language
+            && part_matches(&self.script, &kept.script)
+            && part_matches(&self.region, &kept.region)
+    }
+}
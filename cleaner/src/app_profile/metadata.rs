@@ -23,6 +23,10 @@
 //! - `AppMetadata` represents an application bundle together
 //!   with its parsed identity information.
 //!
+//! Supporting types describe structured plist entries:
+//!
+//! - `DocumentType` represents a `CFBundleDocumentTypes` entry.
+//!
 //! Application metadata serves as the foundation for all discovery
 //! operations throughout the crate.
 //!
@@ -51,13 +55,63 @@
 //!..
 
 use mini_logger::debug;
-use plist::Value;
+use plist::Dictionary;
 use rayon::prelude::*;
 use std::path::Path;
 use std::path::PathBuf;
-use walkdir::WalkDir;
 
+use crate::app_profile::nested_bundle::{NestedBundle, NestedBundleKind};
 use crate::errors::{ErrorKind, Result};
+use crate::macho::{Entitlements, LinkedLibraries, SigningInfo, SliceHeader};
+use crate::utility::{FileSystem, RealFileSystem};
+
+/// Declared document type.
+///
+/// Doc:
+/// Represents a single entry of the `CFBundleDocumentTypes`
+/// array found in an application's `Info.plist`.
+///
+/// Each entry stores:
+///
+/// - The document type name (`CFBundleTypeName`).
+/// - Uniform type identifiers (`LSItemContentTypes`).
+/// - Filename extensions (`CFBundleTypeExtensions`).
+///
+/// Note:
+/// Applications frequently declare their own type identifiers
+/// using the bundle identifier as a prefix, which makes them
+/// useful as additional matching signals.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocumentType {
+    name: String,
+    content_types: Vec<String>,
+    extensions: Vec<String>,
+}
+
+impl DocumentType {
+    pub fn new(name: String, content_types: Vec<String>, extensions: Vec<String>) -> Self {
+        Self {
+            name,
+            content_types,
+            extensions,
+        }
+    }
+
+    /// get document type name reference
+    pub fn as_name(&self) -> &str {
+        &self.name
+    }
+
+    /// get declared content types reference
+    pub fn as_content_types(&self) -> &[String] {
+        &self.content_types
+    }
+
+    /// get declared extensions reference
+    pub fn as_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+}
 
 /// Application bundle metadata.
 ///
@@ -72,6 +126,13 @@ use crate::errors::{ErrorKind, Result};
 /// - Bundle identifier.
 /// - Executable name.
 /// - Organization identifier.
+/// - Version information.
+/// - Declared URL schemes and document types.
+/// - Privileged helper labels and extension point.
+/// - Nested bundles such as helpers and extensions.
+/// - Code signature summary of the main executable.
+/// - Application groups from code-signing entitlements.
+/// - Libraries loaded from outside the bundle.
 ///
 /// The application path identifies the bundle on disk while
 /// the parsed metadata fields are used by discovery and
@@ -96,6 +157,19 @@ pub struct Metadata {
     bundle_executable_name: String,
     organization: String,
     alias_name: String,
+    bundle_name: String,
+    short_version: String,
+    bundle_version: String,
+    minimum_system_version: String,
+    url_schemes: Vec<String>,
+    document_types: Vec<DocumentType>,
+    privileged_executables: Vec<String>,
+    team_identifier: String,
+    extension_point: String,
+    nested_bundles: Vec<NestedBundle>,
+    signing_info: Option<SigningInfo>,
+    application_groups: Vec<String>,
+    external_libraries: Vec<PathBuf>,
 }
 
 impl Metadata {
@@ -115,6 +189,7 @@ impl Metadata {
             bundle_executable_name,
             organization,
             alias_name,
+            ..Default::default()
         }
     }
 
@@ -150,6 +225,12 @@ impl Metadata {
     /// The parsed metadata is then used to construct the
     /// resulting `AppMetadata`.
     ///
+    /// Nested bundles (login items, helpers, extensions, and XPC
+    /// services) are discovered afterwards and stored together
+    /// with the metadata. When the fallback search was used, every
+    /// other bundle `Info.plist` it found is kept as a nested
+    /// bundle as well.
+    ///
     /// Returns an error if:
     ///
     /// - No `Info.plist` can be found.
@@ -160,20 +241,62 @@ impl Metadata {
     /// Some applications use non-standard bundle layouts, which
     /// is why a fallback search is performed.
     pub fn from_path(app_path: &Path) -> Result<Self> {
+        Self::from_path_with(&RealFileSystem::new(), app_path)
+    }
+
+    /// Constructs application metadata through the provided
+    /// filesystem.
+    ///
+    /// Doc:
+    /// Same as `from_path()`, reading the bundle through the
+    /// provided `FileSystem`.
+    pub fn from_path_with<FS>(fs: &FS, app_path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
         let mut plist_path = app_path.join("Contents").join("Info.plist");
+        let mut nested_bundles = NestedBundle::discover_with(fs, app_path);
 
-        if !plist_path.exists() {
-            let found = WalkDir::new(app_path)
-                .into_iter()
-                .par_bridge()
-                .filter_map(|e| e.ok())
-                .filter(|entry| entry.file_type().is_file() && entry.file_name() == "Info.plist")
+        if !fs.exists(&plist_path) {
+            let found = fs
+                .walk(app_path, usize::MAX)
+                .filter(|entry| {
+                    !entry.is_dir()
+                        && entry
+                            .as_path()
+                            .file_name()
+                            .map(|name| name == "Info.plist")
+                            .unwrap_or(false)
+                })
                 .collect::<Vec<_>>();
 
             let upper = found
-                .into_par_iter()
+                .par_iter()
                 .min_by_key(|entry| entry.depth())
-                .map(|entry| entry.path().to_path_buf());
+                .map(|entry| entry.as_path().to_path_buf());
+
+            // Keep the remaining bundles instead of discarding them.
+            // Only plists stored as `<bundle>/Contents/Info.plist`
+            // describe a bundle; other matches are resources.
+            let others = found
+                .iter()
+                .map(|entry| entry.as_path())
+                .filter(|path| Some(*path) != upper.as_deref())
+                .filter_map(|path| {
+                    let contents = path.parent()?;
+                    (contents.file_name()? == "Contents").then_some((path, contents.parent()?))
+                })
+                .filter(|(_, bundle)| {
+                    !nested_bundles
+                        .iter()
+                        .any(|nested| nested.as_path() == *bundle)
+                })
+                .filter_map(|(plist, bundle)| {
+                    NestedBundle::from_info_plist_with(fs, plist, bundle, NestedBundleKind::Other)
+                })
+                .collect::<Vec<_>>();
+
+            nested_bundles.extend(others);
 
             let selected = upper.ok_or_else(|| {
                 ErrorKind::failed()
@@ -189,17 +312,38 @@ impl Metadata {
             plist_path = selected;
         }
 
-        let metadata = Self::parse_info_plist(&plist_path, app_path)?;
+        let mut metadata = Self::parse_info_plist(fs, &plist_path, app_path)?;
+
+        // The fallback search may select a nested bundle as the main
+        // plist. It must not be reported as its own nested bundle.
+        nested_bundles.retain(|nested| nested.as_bundle_id() != metadata.as_bundle_id());
+        metadata.nested_bundles = nested_bundles;
+        metadata.read_executables(fs);
 
         debug!(
-            "path: {}, name: {}, bundle_id: {}, bundle_name: {}, organization: {}",
+            "path: {}, name: {}, bundle_id: {}, bundle_name: {}, organization: {}, version: {}",
             app_path.display(),
             metadata.as_name(),
             metadata.as_bundle_id(),
             metadata.as_bundle_executable_name(),
             metadata.as_organization(),
+            metadata.as_version_string(),
         );
 
+        if let Some(signing) = metadata.as_signing_info() {
+            debug!("code signature: {}", signing);
+        }
+
+        for nested in metadata.as_nested_bundles() {
+            debug!(
+                "nested bundle: kind: {}, bundle_id: {}, executable: {}, path: {}",
+                nested.kind().as_str(),
+                nested.as_bundle_id(),
+                nested.as_executable_name(),
+                nested.as_path().display(),
+            );
+        }
+
         Ok(metadata)
     }
 
@@ -233,6 +377,247 @@ impl Metadata {
         &self.alias_name
     }
 
+    /// get `CFBundleName` reference
+    pub fn as_bundle_name(&self) -> &str {
+        &self.bundle_name
+    }
+
+    /// get `CFBundleShortVersionString` reference
+    pub fn as_short_version(&self) -> &str {
+        &self.short_version
+    }
+
+    /// get `CFBundleVersion` reference
+    pub fn as_bundle_version(&self) -> &str {
+        &self.bundle_version
+    }
+
+    /// get `LSMinimumSystemVersion` reference
+    pub fn as_minimum_system_version(&self) -> &str {
+        &self.minimum_system_version
+    }
+
+    /// get declared URL schemes reference
+    pub fn as_url_schemes(&self) -> &[String] {
+        &self.url_schemes
+    }
+
+    /// get declared document types reference
+    pub fn as_document_types(&self) -> &[DocumentType] {
+        &self.document_types
+    }
+
+    /// get `SMPrivilegedExecutables` labels reference
+    pub fn as_privileged_executables(&self) -> &[String] {
+        &self.privileged_executables
+    }
+
+    /// get team identifier reference
+    pub fn as_team_identifier(&self) -> &str {
+        &self.team_identifier
+    }
+
+    /// get `NSExtensionPointIdentifier` reference
+    pub fn as_extension_point(&self) -> &str {
+        &self.extension_point
+    }
+
+    /// get nested bundles reference
+    pub fn as_nested_bundles(&self) -> &[NestedBundle] {
+        &self.nested_bundles
+    }
+
+    /// get application groups reference
+    pub fn as_application_groups(&self) -> &[String] {
+        &self.application_groups
+    }
+
+    /// get external libraries reference
+    ///
+    /// Note:
+    /// Libraries inside a framework are listed as the framework
+    /// bundle.
+    pub fn as_external_libraries(&self) -> &[PathBuf] {
+        &self.external_libraries
+    }
+
+    /// get code signature summary reference
+    ///
+    /// Note:
+    /// `None` when the main executable could not be read, which
+    /// differs from an unsigned executable.
+    pub fn as_signing_info(&self) -> Option<&SigningInfo> {
+        self.signing_info.as_ref()
+    }
+
+    /// Returns the team identifier as a name prefix.
+    ///
+    /// Doc:
+    /// Vendors name group containers, and some launchd labels,
+    /// after their team:
+    ///
+    /// ```text
+    /// ABCDE12345.com.vendor.sync
+    /// ```
+    ///
+    /// Note:
+    /// Returns an empty string when the team is unknown.
+    pub fn team_prefix(&self) -> String {
+        if self.team_identifier.is_empty() {
+            return String::new();
+        }
+
+        format!("{}.", self.team_identifier)
+    }
+
+    /// Returns the bundle identifier prefixed with the team.
+    ///
+    /// Doc:
+    /// Group containers and launchd labels of the application
+    /// itself may carry the team identifier in front of the
+    /// bundle identifier:
+    ///
+    /// ```text
+    /// ABCDE12345.com.example.cafe
+    /// ```
+    ///
+    /// Design:
+    /// The team prefix alone identifies the vendor, not the
+    /// application. It is therefore only matched together with
+    /// the bundle identifier.
+    ///
+    /// Note:
+    /// Returns an empty string when the team is unknown.
+    pub fn team_bundle_id(&self) -> String {
+        if self.team_identifier.is_empty() {
+            return String::new();
+        }
+
+        format!("{}{}", self.team_prefix(), self.bundle_id)
+    }
+
+    /// Returns the identifiers of every nested bundle.
+    ///
+    /// Doc:
+    /// Identifiers equal to the main bundle identifier are
+    /// omitted because they are already matched directly.
+    ///
+    /// Note:
+    /// The returned list contains no duplicates.
+    pub fn nested_bundle_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+
+        for nested in &self.nested_bundles {
+            let id = nested.as_bundle_id();
+            if id != self.bundle_id && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        ids
+    }
+
+    /// Returns the executable names of every nested bundle.
+    ///
+    /// Note:
+    /// Names equal to the main executable are omitted and the
+    /// returned list contains no duplicates.
+    pub fn nested_executable_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+
+        for nested in &self.nested_bundles {
+            let name = nested.as_executable_name();
+            if !name.is_empty() && name != self.bundle_executable_name && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    /// Returns a user-facing version string.
+    ///
+    /// Doc:
+    /// Combines the marketing version and the build number
+    /// using the format shown by Finder:
+    ///
+    /// ```text
+    /// 1.4.2 (1042)
+    /// ```
+    ///
+    /// Note:
+    /// Missing components are omitted. An empty string is
+    /// returned when neither value is available.
+    pub fn as_version_string(&self) -> String {
+        match (
+            self.short_version.is_empty(),
+            self.bundle_version.is_empty(),
+        ) {
+            (false, false) if self.short_version != self.bundle_version => {
+                format!("{} ({})", self.short_version, self.bundle_version)
+            }
+            (false, _) => self.short_version.clone(),
+            (true, false) => self.bundle_version.clone(),
+            (true, true) => String::new(),
+        }
+    }
+
+    /// Returns additional identifiers declared by the bundle.
+    ///
+    /// Doc:
+    /// Collects identifiers that are not part of the primary
+    /// identity but still name resources owned by the
+    /// application:
+    ///
+    /// - Privileged helper labels (`SMPrivilegedExecutables`).
+    /// - Declared document content types prefixed with the
+    ///   application's organization.
+    ///
+    /// Design:
+    /// Document content types are restricted to the
+    /// application's own reverse-DNS namespace because
+    /// applications commonly declare system types such as
+    /// `public.plain-text`, which would otherwise match
+    /// unrelated files.
+    ///
+    /// The namespace is the bundle identifier without its last
+    /// component and must have at least two components, so
+    /// `com.example` does not claim every `com.*` type. Types
+    /// must continue it with a `.`, so `com.vendor` does not
+    /// claim `com.vendorcorp.*`.
+    ///
+    /// Note:
+    /// The returned list contains no duplicates and may be
+    /// empty.
+    pub fn extra_identifiers(&self) -> Vec<&str> {
+        let mut identifiers: Vec<&str> = Vec::new();
+
+        let own_prefix = self
+            .bundle_id
+            .rsplit_once('.')
+            .map(|(prefix, _)| prefix)
+            .filter(|prefix| prefix.contains('.'))
+            .map(|prefix| format!("{}.", prefix))
+            .unwrap_or_default();
+
+        let labels = self.privileged_executables.iter().map(String::as_str);
+
+        let content_types = self
+            .document_types
+            .iter()
+            .flat_map(|doc| doc.as_content_types())
+            .map(String::as_str)
+            .filter(|uti| !own_prefix.is_empty() && uti.starts_with(own_prefix.as_str()));
+
+        for identifier in labels.chain(content_types) {
+            if !identifier.trim().is_empty() && !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+
+        identifiers
+    }
+
     /// Parses application information from an Info.plist file.
     ///
     /// Doc:
@@ -247,7 +632,17 @@ impl Metadata {
     /// Application name is resolved using:
     ///
     /// 1. `CFBundleDisplayName`
-    /// 2. Application bundle filename
+    /// 2. `CFBundleName`
+    /// 3. Application bundle filename
+    ///
+    /// Optional descriptive fields are parsed when present:
+    ///
+    /// - `CFBundleShortVersionString` and `CFBundleVersion`.
+    /// - `LSMinimumSystemVersion`.
+    /// - `CFBundleURLTypes` URL schemes.
+    /// - `CFBundleDocumentTypes` entries.
+    /// - `SMPrivilegedExecutables` labels.
+    /// - `NSExtension` extension point identifier.
     ///
     /// The organization value is derived from the bundle
     /// identifier.
@@ -271,12 +666,20 @@ impl Metadata {
     /// Returns an error when required fields are missing or the
     /// plist structure is invalid.
     ///
+    /// The team identifier is extracted from the
+    /// `SMPrivilegedExecutables` code requirements, which embed
+    /// the signing team as `certificate leaf[subject.OU]`.
+    ///
     /// Note:
     /// Only a subset of available plist fields is parsed because
     /// the scanner requires application identity rather than
-    /// complete bundle metadata.
-    fn parse_info_plist(plist_path: &Path, app_path: &Path) -> Result<Self> {
-        let plist = Value::from_file(plist_path).map_err(|e| {
+    /// complete bundle metadata. Missing optional fields are
+    /// stored as empty values.
+    fn parse_info_plist<FS>(fs: &FS, plist_path: &Path, app_path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let plist = fs.read_plist(plist_path).map_err(|e| {
             ErrorKind::failed()
                 .with_summary("Failed to read plist file")
                 .with_reason(format!("{}: {}", plist_path.display(), e))
@@ -298,10 +701,15 @@ impl Metadata {
             })?
             .to_string();
 
+        let bundle_name = Self::string_field(dict, "CFBundleName");
+
         let name = dict
             .get("CFBundleDisplayName")
             .and_then(|v| v.as_string())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
             .map(ToOwned::to_owned)
+            .or_else(|| (!bundle_name.is_empty()).then(|| bundle_name.clone()))
             .or_else(|| {
                 app_path
                     .file_stem()
@@ -323,14 +731,11 @@ impl Metadata {
             None => {
                 let binary_dir = app_path.join("Contents").join("MacOS");
 
-                let executable = std::fs::read_dir(&binary_dir)
-                    .ok()
-                    .and_then(|entries| {
-                        entries
-                            .filter_map(|e| e.ok())
-                            .find(|e| e.path().is_file())
-                    })
-                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                let executable = fs
+                    .read_dir(&binary_dir)
+                    .into_iter()
+                    .find(|path| fs.is_file(path))
+                    .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()))
                     .ok_or_else(|| {
                         ErrorKind::failed()
                             .with_summary("Missing executable configuration")
@@ -351,6 +756,34 @@ impl Metadata {
             .unwrap_or_default()
             .to_string();
 
+        let short_version = Self::string_field(dict, "CFBundleShortVersionString");
+        let bundle_version = Self::string_field(dict, "CFBundleVersion");
+        let minimum_system_version = Self::string_field(dict, "LSMinimumSystemVersion");
+
+        let url_schemes = Self::parse_url_schemes(dict);
+        let document_types = Self::parse_document_types(dict);
+
+        let privileged = dict
+            .get("SMPrivilegedExecutables")
+            .and_then(|v| v.as_dictionary());
+
+        let privileged_executables: Vec<String> = privileged
+            .map(|helpers| helpers.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let team_identifier = privileged
+            .into_iter()
+            .flat_map(|helpers| helpers.values())
+            .filter_map(|requirement| requirement.as_string())
+            .find_map(Self::team_from_requirement)
+            .unwrap_or_default();
+
+        let extension_point = dict
+            .get("NSExtension")
+            .and_then(|v| v.as_dictionary())
+            .map(|extension| Self::string_field(extension, "NSExtensionPointIdentifier"))
+            .unwrap_or_default();
+
         Ok(Self {
             bundle_path: app_path.to_path_buf(),
             name,
@@ -358,6 +791,263 @@ impl Metadata {
             bundle_executable_name,
             organization,
             alias_name,
+            bundle_name,
+            short_version,
+            bundle_version,
+            minimum_system_version,
+            url_schemes,
+            document_types,
+            privileged_executables,
+            team_identifier,
+            extension_point,
+            nested_bundles: Vec::new(),
+            signing_info: None,
+            application_groups: Vec::new(),
+            external_libraries: Vec::new(),
         })
     }
+
+    /// Reads the code signatures and load commands of the bundle
+    /// executables.
+    ///
+    /// Doc:
+    /// Summarizes the signature of the main executable, then
+    /// inspects the main executable and every nested executable,
+    /// and collects:
+    ///
+    /// - Their `com.apple.security.application-groups`, which are
+    ///   the exact names of the group containers they use.
+    /// - The signing team. The team recorded in the signature
+    ///   takes precedence over the one derived from `Info.plist`.
+    /// - The libraries they load from outside the bundle, such
+    ///   as frameworks in `/Library/Frameworks`.
+    ///
+    /// Design:
+    /// Group containers are usually prefixed with the team
+    /// identifier and named after a vendor-wide group, such as
+    /// `ABCDE12345.group.com.vendor`, so name matching against
+    /// the application identity misses them. Shared libraries are
+    /// usually named after the vendor's toolkit rather than the
+    /// application, for the same reason.
+    ///
+    /// Only the slice headers, load commands, and code
+    /// signatures are read, never whole executables, since the
+    /// profile is created as soon as an application is dropped.
+    ///
+    /// Note:
+    /// Unsigned executables and executables that cannot be
+    /// parsed are skipped.
+    fn read_executables<FS>(&mut self, fs: &FS)
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let main_executable = self
+            .bundle_path
+            .join("Contents")
+            .join("MacOS")
+            .join(&self.bundle_executable_name);
+
+        let executables = std::iter::once(main_executable).chain(
+            self.nested_bundles
+                .iter()
+                .filter_map(|bundle| bundle.executable_path_with(fs)),
+        );
+
+        for (index, executable) in executables.enumerate() {
+            if !fs.is_file(&executable) {
+                continue;
+            }
+
+            let headers = match SliceHeader::read_with(fs, &executable) {
+                Ok(headers) => headers,
+                Err(err) => {
+                    debug!("executable skipped: {}: {}", executable.display(), err);
+                    continue;
+                }
+            };
+
+            let signatures: Vec<Vec<u8>> = headers
+                .iter()
+                .filter_map(
+                    |header| match header.read_code_signature_with(fs, &executable) {
+                        Ok(signature) => signature,
+                        Err(err) => {
+                            debug!("code signature skipped: {}: {}", executable.display(), err);
+                            None
+                        }
+                    },
+                )
+                .collect();
+
+            if index == 0 {
+                let info = signatures
+                    .first()
+                    .map(|signature| SigningInfo::from_signature(signature))
+                    .unwrap_or_else(|| Ok(SigningInfo::default()));
+
+                match info {
+                    Ok(info) => {
+                        if let Some(team) = info.as_team_identifier() {
+                            self.team_identifier = team.to_string();
+                        }
+                        self.signing_info = Some(info);
+                    }
+                    Err(err) => debug!("code signature skipped: {}: {}", executable.display(), err),
+                }
+            }
+
+            match LinkedLibraries::from_slice_headers(&headers) {
+                Ok(libraries) => {
+                    let bundle = &self.bundle_path;
+                    let external =
+                        libraries.external_paths(&executable, bundle, |path| fs.exists(path));
+
+                    for path in external {
+                        if !self.external_libraries.contains(&path) {
+                            self.external_libraries.push(path);
+                        }
+                    }
+                }
+                Err(err) => debug!("load commands skipped: {}: {}", executable.display(), err),
+            }
+
+            let entitlements = match signatures
+                .iter()
+                .find_map(|signature| Entitlements::from_signature(signature).transpose())
+            {
+                Some(Ok(entitlements)) => entitlements,
+                None => continue,
+                Some(Err(err)) => {
+                    debug!("entitlements skipped: {}: {}", executable.display(), err);
+                    continue;
+                }
+            };
+
+            for group in entitlements.as_application_groups() {
+                if !self.application_groups.contains(group) {
+                    self.application_groups.push(group.clone());
+                }
+            }
+
+            if self.team_identifier.is_empty()
+                && let Some(team) = entitlements.as_team_identifier()
+            {
+                self.team_identifier = team.to_string();
+            }
+        }
+    }
+
+    /// Reads an optional string field from a plist dictionary.
+    ///
+    /// Note:
+    /// Missing, non-string, or whitespace-only values produce an
+    /// empty string.
+    fn string_field(dict: &Dictionary, key: &str) -> String {
+        dict.get(key)
+            .and_then(|v| v.as_string())
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Reads an optional array of strings from a plist dictionary.
+    ///
+    /// Note:
+    /// Non-string and empty array items are ignored.
+    fn string_array_field(dict: &Dictionary, key: &str) -> Vec<String> {
+        dict.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_string())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Collects URL schemes declared in `CFBundleURLTypes`.
+    ///
+    /// Doc:
+    /// Each URL type may declare several schemes. All schemes
+    /// are flattened into a single list without duplicates.
+    fn parse_url_schemes(dict: &Dictionary) -> Vec<String> {
+        let mut schemes: Vec<String> = Vec::new();
+
+        let url_types = dict
+            .get("CFBundleURLTypes")
+            .and_then(|v| v.as_array())
+            .map(|types| types.as_slice())
+            .unwrap_or_default();
+
+        for url_type in url_types.iter().filter_map(|v| v.as_dictionary()) {
+            for scheme in Self::string_array_field(url_type, "CFBundleURLSchemes") {
+                if !schemes.contains(&scheme) {
+                    schemes.push(scheme);
+                }
+            }
+        }
+
+        schemes
+    }
+
+    /// Collects document types declared in `CFBundleDocumentTypes`.
+    ///
+    /// Note:
+    /// Entries that declare neither a name, a content type, nor
+    /// an extension are ignored.
+    fn parse_document_types(dict: &Dictionary) -> Vec<DocumentType> {
+        dict.get("CFBundleDocumentTypes")
+            .and_then(|v| v.as_array())
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|v| v.as_dictionary())
+                    .map(|doc| {
+                        DocumentType::new(
+                            Self::string_field(doc, "CFBundleTypeName"),
+                            Self::string_array_field(doc, "LSItemContentTypes"),
+                            Self::string_array_field(doc, "CFBundleTypeExtensions"),
+                        )
+                    })
+                    .filter(|doc| {
+                        !doc.as_name().is_empty()
+                            || !doc.as_content_types().is_empty()
+                            || !doc.as_extensions().is_empty()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extracts a team identifier from a code requirement string.
+    ///
+    /// Doc:
+    /// Privileged helper requirements usually look like:
+    ///
+    /// ```text
+    /// identifier "com.vendor.helper" and anchor apple generic
+    ///     and certificate leaf[subject.OU] = "ABCDE12345"
+    /// ```
+    ///
+    /// The value assigned to `subject.OU` is returned.
+    ///
+    /// Note:
+    /// Both quoted and unquoted values are accepted.
+    fn team_from_requirement(requirement: &str) -> Option<String> {
+        let (_, rest) = requirement.split_once("subject.OU]")?;
+        let (_, value) = rest.split_once('=')?;
+
+        let team = value
+            .trim_start()
+            .trim_start_matches('"')
+            .split(|c: char| c == '"' || c.is_whitespace() || c == ')')
+            .next()?
+            .trim();
+
+        (!team.is_empty()).then(|| team.to_string())
+    }
 }
@@ -39,11 +39,20 @@
 //! containers:
 //!
 //! - `Metadata` stores application information.
+//! - `NestedBundle` stores helpers and extensions embedded in the
+//!   application bundle.
 //! - `AppProcs` stores discovered running processes.
+//! - `ProcessSource` provides the process table used for process
+//!   discovery.
+//! - `Localization` describes a `*.lproj` folder of the bundle.
+//! - `SizeBreakdown` shows where the space of the application
+//!   and its leftovers goes.
 //! - `PathEntry` stores the application bundle together with
 //!   discovered filesystem entries, including associated files,
 //!   sandbox containers, background task files, and package
 //!   receipt BOM files.
+//! - `PathCategory` names the category of a discovered path and
+//!   why it was attributed to the application.
 //!
 //! `AppProfile` provides a single aggregate state used by the
 //! cleanup workflow while delegating discovery responsibilities
@@ -55,16 +64,28 @@
 //! organize discovery results and scanning logic.
 //!..
 
+mod localization;
 mod metadata;
+mod nested_bundle;
+mod path_category;
 mod path_entry;
 mod process_entry;
+mod process_source;
+mod size_breakdown;
 
-pub use metadata::Metadata;
+pub use localization::Localization;
+pub use metadata::{DocumentType, Metadata};
+pub use nested_bundle::{NestedBundle, NestedBundleKind};
+pub use path_category::PathCategory;
 pub use path_entry::PathEntry;
-pub use process_entry::ProcessEntry;
+pub use process_entry::{MatchConfidence, MatchReason, ProcessData, ProcessEntry};
+pub use process_source::{ProcessInfo, ProcessSource, StaticProcessSource, SysinfoProcessSource};
+pub use size_breakdown::{BundleSection, SizeBreakdown};
 
 use crate::errors::Result;
 use crate::path_data::PathData;
+use crate::path_data::trash_entry::TrashEntry;
+use crate::utility::{FileSystem, RealFileSystem, RuleConfig};
 use mini_logger::debug;
 use std::path::Path;
 
@@ -128,7 +149,16 @@ impl AppProfile {
     }
 
     pub fn from_path(app_path: &Path) -> Result<Self> {
-        let metadata = Metadata::from_path(app_path)?;
+        Self::from_path_with(&RealFileSystem::new(), app_path)
+    }
+
+    /// Constructs a profile reading the bundle through the provided
+    /// `FileSystem`.
+    pub fn from_path_with<FS>(fs: &FS, app_path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let metadata = Metadata::from_path_with(fs, app_path)?;
         let path_entry = PathEntry::from_metadata(&metadata);
 
         Ok(Self {
@@ -152,8 +182,11 @@ impl AppProfile {
 
     // =================Scanner========================================
     // Scan processed
-    pub fn find_pid_and_command(&mut self) {
-        self.process_entry = ProcessEntry::find_app_processes(&self.metadata);
+    pub fn find_pid_and_command<S>(&mut self, source: &S)
+    where
+        S: ProcessSource + ?Sized,
+    {
+        self.process_entry = ProcessEntry::find_app_processes_with(&self.metadata, source);
 
         // debug list of the app process
         for _p in self.process_entry.list() {
@@ -190,10 +223,130 @@ impl AppProfile {
     where
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
-        self.path_entry.scan_path_entry(&self.metadata, progress)
+        self.find_path_entry_with(&RealFileSystem::new(), progress)
+    }
+
+    /// Scans for associated filesystem paths through the provided
+    /// `FileSystem`.
+    pub fn find_path_entry_with<FS, F>(&mut self, fs: &FS, progress: F)
+    where
+        FS: FileSystem + ?Sized,
+        F: Fn(usize, &Path) + Send + Sync + Clone,
+    {
+        self.path_entry
+            .scan_path_entry_with(fs, &self.metadata, progress)
+    }
+
+    // =================Localizations================================
+    /// Lists the localization folders of the application bundle.
+    ///
+    /// See `Localization::find_with()`.
+    pub fn find_localizations(&self) -> Vec<Localization> {
+        self.find_localizations_with(&RealFileSystem::new())
+    }
+
+    /// Lists the localization folders through the provided
+    /// `FileSystem`.
+    pub fn find_localizations_with<FS>(&self, fs: &FS) -> Vec<Localization>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        Localization::find_with(fs, self.metadata.as_bundle_path())
+    }
+
+    /// Returns the localizations removed when keeping only the
+    /// `keep` languages.
+    pub fn unused_localizations_with<FS>(&self, fs: &FS, keep: &[String]) -> Vec<Localization>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        self.find_localizations_with(fs)
+            .into_iter()
+            .filter(|localization| !localization.is_kept(keep))
+            .collect()
+    }
+
+    /// Moves every localization folder outside the `keep`
+    /// languages to Trash.
+    ///
+    /// Doc:
+    /// Folders are moved through `TrashEntry`, so they can be put
+    /// back from the Trash.
+    ///
+    /// Note:
+    /// Removing files from a signed bundle invalidates its code
+    /// signature. Callers should show `signature_warning()` before
+    /// removing anything.
+    pub fn remove_localizations(&self, keep: &[String]) -> Result<TrashEntry> {
+        self.remove_localizations_with(&RealFileSystem::new(), keep)
+    }
+
+    /// Moves every localization folder outside the `keep`
+    /// languages, found through the provided `FileSystem`, to
+    /// Trash.
+    pub fn remove_localizations_with<FS>(&self, fs: &FS, keep: &[String]) -> Result<TrashEntry>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let unused: Vec<PathData> = self
+            .unused_localizations_with(fs, keep)
+            .into_iter()
+            .map(|localization| localization.as_path_data().clone())
+            .collect();
+
+        debug!("Removing {} localization folders", unused.len());
+
+        TrashEntry::moved_path_to_trash(&unused)
+    }
+
+    /// Returns a warning when modifying the bundle invalidates
+    /// its code signature.
+    ///
+    /// Note:
+    /// Returns `None` for unsigned bundles.
+    pub fn signature_warning(&self) -> Option<String> {
+        self.metadata
+            .as_signing_info()
+            .filter(|info| info.is_signed())
+            .map(|_| {
+                format!(
+                    "Changing '{}' invalidates its code signature. macOS may refuse to open it or ask again for permissions.",
+                    self.metadata.as_name()
+                )
+            })
+    }
+
+    // =================Size=========================================
+    /// Computes the size breakdown of the application bundle and
+    /// of the discovered leftovers.
+    ///
+    /// Leftovers are only known after `find_path_entry()`.
+    pub fn size_breakdown(&self) -> SizeBreakdown {
+        self.size_breakdown_with(&RealFileSystem::new())
+    }
+
+    /// Computes the size breakdown through the provided
+    /// `FileSystem`.
+    pub fn size_breakdown_with<FS>(&self, fs: &FS) -> SizeBreakdown
+    where
+        FS: FileSystem + ?Sized,
+    {
+        SizeBreakdown::new_with(fs, self.metadata.as_bundle_path(), &self.path_entry)
     }
 
     // ========================Setter==========================================
+    /// Enables or disables path-segment matching for later path
+    /// scans. See `PathEntry::set_match_segments()`.
+    pub fn set_match_segments(&mut self, enabled: bool) {
+        self.path_entry.set_match_segments(enabled);
+    }
+
+    /// Replaces the user matching rules used by later path scans.
+    /// See `PathEntry::set_rule_config()`.
+    pub fn set_rule_config(&mut self, config: RuleConfig) -> Result<()> {
+        self.path_entry.set_rule_config(config)
+    }
+
     /// Updates stored path information after a cleanup attempt.
     ///
     /// Paths provided in `failed` represent entries that could not
@@ -216,10 +369,14 @@ impl AppProfile {
     ///
     /// Note:
     /// After calling this method the profile behaves as if it
-    /// had never been scanned.
+    /// had never been scanned. The path-segment matching mode is
+    /// a setting rather than scan state and is kept.
     pub fn reset(&mut self) {
+        let match_segments = self.path_entry.match_segments();
+
         self.metadata = Metadata::default();
         self.process_entry = ProcessEntry::default();
-        self.path_entry = PathEntry::default()
+        self.path_entry = PathEntry::default();
+        self.path_entry.set_match_segments(match_segments);
     }
 }
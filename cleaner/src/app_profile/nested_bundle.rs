@@ -0,0 +1,395 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Nested bundle discovery.
+//!
+//! Modern macOS applications rarely consist of a single bundle.
+//! Helpers, login items, extensions, and XPC services are shipped
+//! inside the main application bundle and each of them carries
+//! its own bundle identifier.
+//!
+//! The module is built around two types:
+//!
+//! - `NestedBundle` represents a bundle embedded inside the
+//!   application.
+//! - `NestedBundleKind` classifies where the bundle was found.
+//!
+//! Nested bundle identifiers are used as additional matching
+//! signals when locating:
+//!
+//! - Associated files.
+//! - Sandbox containers.
+//! - Background task files.
+//! - Running processes.
+//!
+//! Note:
+//! Discovery only reads bundle metadata. Nested bundles are
+//! removed together with the application bundle and are never
+//! cleaned up individually.
+//!..
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::utility::{FileSystem, RealFileSystem};
+
+/// Location category of a nested bundle.
+///
+/// Doc:
+/// Describes the conventional bundle location a nested bundle
+/// was discovered in.
+///
+/// Note:
+/// `Other` is used for bundles found outside the conventional
+/// locations, for example by the `Info.plist` fallback search
+/// performed for non-standard application layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NestedBundleKind {
+    /// `Contents/Library/LoginItems`
+    LoginItem,
+    /// `Contents/Library/LaunchServices`
+    LaunchService,
+    /// `Contents/PlugIns`
+    PlugIn,
+    /// `Contents/XPCServices`
+    XpcService,
+    /// Helper applications inside `Contents/Frameworks`
+    Helper,
+    /// Any other embedded bundle.
+    Other,
+}
+
+impl NestedBundleKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::LoginItem => "Login item",
+            Self::LaunchService => "Launch service",
+            Self::PlugIn => "Plug-in",
+            Self::XpcService => "XPC service",
+            Self::Helper => "Helper",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// Bundle embedded inside an application.
+///
+/// Doc:
+/// Stores the identity of a helper, extension, or service that
+/// ships inside the main application bundle.
+///
+/// Each entry stores:
+///
+/// - The nested bundle path.
+/// - The nested bundle identifier.
+/// - The nested executable name.
+/// - The location category.
+///
+/// Design:
+/// Privileged helper tools in `Contents/Library/LaunchServices`
+/// are usually bare executables rather than bundles. Their file
+/// name is the launchd label of the installed helper and is
+/// therefore used as both identifier and executable name.
+///
+/// Note:
+/// Only entries with a non-empty identifier are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedBundle {
+    path: PathBuf,
+    bundle_id: String,
+    executable_name: String,
+    kind: NestedBundleKind,
+}
+
+impl NestedBundle {
+    pub fn new(
+        path: PathBuf,
+        bundle_id: String,
+        executable_name: String,
+        kind: NestedBundleKind,
+    ) -> Self {
+        Self {
+            path,
+            bundle_id,
+            executable_name,
+            kind,
+        }
+    }
+
+    /// get nested bundle path reference
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// get nested bundle identifier reference
+    pub fn as_bundle_id(&self) -> &str {
+        &self.bundle_id
+    }
+
+    /// get nested executable name reference
+    pub fn as_executable_name(&self) -> &str {
+        &self.executable_name
+    }
+
+    /// get the copy of the nested bundle kind
+    pub fn kind(&self) -> NestedBundleKind {
+        self.kind
+    }
+
+    /// Returns the path of the nested executable.
+    ///
+    /// Doc:
+    /// Bare launch service executables are their own executable.
+    /// Bundles store it as `Contents/MacOS/<executable>`.
+    ///
+    /// Note:
+    /// Returns `None` when the executable name is unknown.
+    pub fn executable_path_with<FS>(&self, fs: &FS) -> Option<PathBuf>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        if self.kind == NestedBundleKind::LaunchService && fs.is_file(&self.path) {
+            return Some(self.path.clone());
+        }
+
+        (!self.executable_name.is_empty()).then(|| {
+            self.path
+                .join("Contents")
+                .join("MacOS")
+                .join(&self.executable_name)
+        })
+    }
+
+    /// Discovers nested bundles inside an application bundle.
+    ///
+    /// Doc:
+    /// Inspects the conventional locations used for embedded
+    /// components:
+    ///
+    /// ```text
+    /// Contents/Library/LoginItems/*.app
+    /// Contents/Library/LaunchServices/*
+    /// Contents/PlugIns/*.appex
+    /// Contents/XPCServices/*.xpc
+    /// Contents/Frameworks/**/*Helper*.app
+    /// ```
+    ///
+    /// Design:
+    /// Only known locations are inspected instead of walking the
+    /// complete bundle. Large applications contain tens of
+    /// thousands of resource files, while embedded components
+    /// live in a handful of predictable directories.
+    ///
+    /// Helper applications inside `Contents/Frameworks` are
+    /// searched a few levels deep because Chromium-based
+    /// applications place them inside framework versions:
+    ///
+    /// ```text
+    /// Contents/Frameworks/App Framework.framework/Versions/A/Helpers/App Helper.app
+    /// ```
+    ///
+    /// Note:
+    /// Unreadable or malformed bundles are skipped silently.
+    pub fn discover(app_path: &Path) -> Vec<Self> {
+        Self::discover_with(&RealFileSystem::new(), app_path)
+    }
+
+    /// Discovers nested bundles through the provided filesystem.
+    ///
+    /// Doc:
+    /// Same as `discover()`.
+    pub fn discover_with<FS>(fs: &FS, app_path: &Path) -> Vec<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let contents = app_path.join("Contents");
+        let mut bundles = Vec::new();
+
+        let login_items = contents.join("Library").join("LoginItems");
+        bundles.extend(
+            Self::bundle_children(fs, &login_items, "app")
+                .filter_map(|path| Self::from_bundle(fs, &path, NestedBundleKind::LoginItem)),
+        );
+
+        let launch_services = contents.join("Library").join("LaunchServices");
+        bundles.extend(Self::launch_services(fs, &launch_services));
+
+        let plugins = contents.join("PlugIns");
+        bundles.extend(
+            Self::bundle_children(fs, &plugins, "appex")
+                .filter_map(|path| Self::from_bundle(fs, &path, NestedBundleKind::PlugIn)),
+        );
+
+        let xpc_services = contents.join("XPCServices");
+        bundles.extend(
+            Self::bundle_children(fs, &xpc_services, "xpc")
+                .filter_map(|path| Self::from_bundle(fs, &path, NestedBundleKind::XpcService)),
+        );
+
+        let frameworks = contents.join("Frameworks");
+        bundles.extend(
+            fs.walk(&frameworks, 5)
+                // Skip the contents of application bundles that are
+                // not helpers.
+                .filter(|entry| {
+                    entry
+                        .as_path()
+                        .strip_prefix(&frameworks)
+                        .map(|relative| !relative.ancestors().skip(1).any(Self::is_non_helper_app))
+                        .unwrap_or(false)
+                })
+                .filter(|entry| entry.is_dir() && Self::is_helper_app(entry.as_path()))
+                .filter_map(|entry| {
+                    Self::from_bundle(fs, entry.as_path(), NestedBundleKind::Helper)
+                }),
+        );
+
+        bundles
+    }
+
+    /// Constructs a nested bundle from an `Info.plist` file.
+    ///
+    /// Doc:
+    /// Reads `CFBundleIdentifier` and `CFBundleExecutable` from
+    /// the provided plist. When the executable is not declared,
+    /// the bundle file stem is used instead.
+    ///
+    /// Note:
+    /// Returns `None` when the plist cannot be read or does not
+    /// declare a bundle identifier.
+    pub fn from_info_plist(
+        plist_path: &Path,
+        bundle_path: &Path,
+        kind: NestedBundleKind,
+    ) -> Option<Self> {
+        Self::from_info_plist_with(&RealFileSystem::new(), plist_path, bundle_path, kind)
+    }
+
+    /// Constructs a nested bundle from an `Info.plist` file read
+    /// through the provided filesystem.
+    ///
+    /// Doc:
+    /// Same as `from_info_plist()`.
+    pub fn from_info_plist_with<FS>(
+        fs: &FS,
+        plist_path: &Path,
+        bundle_path: &Path,
+        kind: NestedBundleKind,
+    ) -> Option<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let plist = fs.read_plist(plist_path).ok()?;
+        let dict = plist.as_dictionary()?;
+
+        let bundle_id = dict
+            .get("CFBundleIdentifier")
+            .and_then(|v| v.as_string())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())?
+            .to_string();
+
+        let executable_name = dict
+            .get("CFBundleExecutable")
+            .and_then(|v| v.as_string())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(ToOwned::to_owned)
+            .or_else(|| {
+                bundle_path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+            })
+            .unwrap_or_default();
+
+        Some(Self::new(
+            bundle_path.to_path_buf(),
+            bundle_id,
+            executable_name,
+            kind,
+        ))
+    }
+
+    fn from_bundle<FS>(fs: &FS, bundle_path: &Path, kind: NestedBundleKind) -> Option<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let plist_path = bundle_path.join("Contents").join("Info.plist");
+        Self::from_info_plist_with(fs, &plist_path, bundle_path, kind)
+    }
+
+    /// Lists direct children of a directory with the given
+    /// bundle extension.
+    fn bundle_children<FS>(
+        fs: &FS,
+        dir: &Path,
+        extension: &'static str,
+    ) -> impl Iterator<Item = PathBuf>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        fs.read_dir(dir).into_iter().filter(move |path| {
+            fs.is_dir(path)
+                && path
+                    .extension()
+                    .map(|ext| ext == extension)
+                    .unwrap_or(false)
+        })
+    }
+
+    /// Collects privileged helpers from `Contents/Library/LaunchServices`.
+    ///
+    /// Doc:
+    /// Bundled entries are parsed like any other nested bundle,
+    /// while bare executables are recorded using their file name.
+    fn launch_services<FS>(fs: &FS, dir: &Path) -> Vec<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        fs.read_dir(dir)
+            .into_iter()
+            .filter_map(|path| {
+                if fs.is_dir(&path) {
+                    return Self::from_bundle(fs, &path, NestedBundleKind::LaunchService);
+                }
+
+                let name = path.file_name()?.to_string_lossy().into_owned();
+
+                if name.starts_with('.') || name.ends_with(".plist") {
+                    return None;
+                }
+
+                Some(Self::new(
+                    path.clone(),
+                    name.clone(),
+                    name,
+                    NestedBundleKind::LaunchService,
+                ))
+            })
+            .collect()
+    }
+
+    fn is_non_helper_app(path: &Path) -> bool {
+        path.extension().map(|ext| ext == "app").unwrap_or(false) && !Self::is_helper_app(path)
+    }
+
+    fn is_helper_app(path: &Path) -> bool {
+        path.extension().map(|ext| ext == "app").unwrap_or(false)
+            && path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().contains("Helper"))
+                .unwrap_or(false)
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Categories of discovered application paths.
+//!
+//! Every path stored in `PathEntry` belongs to one category,
+//! which also states how paths of that category are attributed
+//! to the application.
+//!..
+
+/// Category of a discovered path.
+///
+/// Doc:
+/// - `Application`: the application bundle itself.
+/// - `Receipts`: installer receipt BOM files.
+/// - `AssociatedFiles`: preferences, caches, logs and other
+///   application data.
+/// - `SandboxContainers`: sandbox and group containers.
+/// - `BackgroundTasks`: launchd job definitions and privileged
+///   helpers.
+/// - `ExternalLibraries`: libraries loaded from outside the
+///   bundle, installed by the vendor and used by no other
+///   application.
+/// - `SharedLibraries`: libraries loaded from outside the
+///   bundle that other software may use.
+/// - `OriginFiles`: files whose name does not match, but whose
+///   origin attributes name the application.
+///
+/// Note:
+/// Shared libraries and origin files are reported only. They are
+/// never moved to Trash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathCategory {
+    Application,
+    Receipts,
+    AssociatedFiles,
+    SandboxContainers,
+    BackgroundTasks,
+    ExternalLibraries,
+    SharedLibraries,
+    OriginFiles,
+}
+
+impl PathCategory {
+    /// Every category, in display order.
+    pub const ALL: [PathCategory; 8] = [
+        Self::Application,
+        Self::Receipts,
+        Self::AssociatedFiles,
+        Self::SandboxContainers,
+        Self::BackgroundTasks,
+        Self::ExternalLibraries,
+        Self::SharedLibraries,
+        Self::OriginFiles,
+    ];
+
+    /// Categories moved to Trash before the application bundle,
+    /// in order.
+    ///
+    /// Note:
+    /// The application bundle is moved last, and only when every
+    /// path of these categories was moved.
+    pub const REMOVAL_ORDER: [PathCategory; 5] = [
+        Self::AssociatedFiles,
+        Self::SandboxContainers,
+        Self::BackgroundTasks,
+        Self::Receipts,
+        Self::ExternalLibraries,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Application => "Application",
+            Self::Receipts => "Receipts",
+            Self::AssociatedFiles => "Associated files",
+            Self::SandboxContainers => "Sandbox containers",
+            Self::BackgroundTasks => "Background tasks",
+            Self::ExternalLibraries => "External libraries",
+            Self::SharedLibraries => "Shared libraries",
+            Self::OriginFiles => "Origin matches",
+        }
+    }
+
+    /// Returns how paths of the category are attributed to the
+    /// application.
+    ///
+    /// Note:
+    /// Scanners record the rule or origin matching a single path
+    /// on its `PathData`. This reason describes the category as a
+    /// whole and is used for paths without a recorded reason.
+    pub fn match_reason(&self) -> &str {
+        match self {
+            Self::Application => "Selected application bundle",
+            Self::Receipts => "Receipt name matches the application",
+            Self::AssociatedFiles => "Name or identifier matches the application",
+            Self::SandboxContainers => "Container identifier matches the application",
+            Self::BackgroundTasks => "Job definition or name matches the application",
+            Self::ExternalLibraries => "Installed by the vendor and loaded by the application only",
+            Self::SharedLibraries => {
+                "Loaded from outside the bundle and possibly used by other software"
+            }
+            Self::OriginFiles => "Downloaded or opened by the application",
+        }
+    }
+
+    /// Returns whether paths of the category are moved to Trash.
+    pub fn is_removed(&self) -> bool {
+        !matches!(self, Self::SharedLibraries | Self::OriginFiles)
+    }
+}
@@ -44,20 +44,40 @@
 //! management.
 //!..
 
+use rayon::prelude::*;
+use std::env;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mini_logger::debug;
 
 use crate::app_profile::metadata::Metadata;
+use crate::app_profile::path_category::PathCategory;
+use crate::errors::Result;
+use crate::macho::Entitlements;
+use crate::macho::LinkedLibraries;
 use crate::path_data::PathData;
 
+use crate::utility::ApplicationLocations;
 use crate::utility::BackgroundTaskLocations;
+use crate::utility::CONTAINER_METADATA_FILE;
+use crate::utility::CompiledRules;
+use crate::utility::FileOrigin;
+use crate::utility::FileSystem;
 use crate::utility::GeneralLocations;
+use crate::utility::LaunchdPlist;
 use crate::utility::MatchRules;
+use crate::utility::RealFileSystem;
 use crate::utility::ReceiptsLocations;
+use crate::utility::RuleConfig;
 use crate::utility::SandboxLocations;
 use crate::utility::construct_and_deduplicate_paths;
-use crate::utility::scan_container;
-use crate::utility::scan_general;
+use crate::utility::container_identifier_with;
+use crate::utility::scan_container_with;
+use crate::utility::scan_general_find_with;
+use crate::utility::scan_general_split_with;
+use crate::utility::scan_general_with;
 
 /// Application path inventory.
 ///
@@ -72,6 +92,7 @@ use crate::utility::scan_general;
 /// - Sandbox container directories.
 /// - Background Task Management files.
 /// - Installer receipt BOM (Bill of Materials) files.
+/// - Libraries loaded from outside the bundle.
 ///
 /// Each category is maintained independently to allow different
 /// discovery, reporting, and cleanup behavior.
@@ -160,9 +181,89 @@ pub struct PathEntry {
     /// traditional application data and are therefore discovered
     /// independently.
     sandbox_container: Vec<PathData>,
+
+    /// External libraries owned by the application.
+    ///
+    /// Doc:
+    /// Stores frameworks and dylibs that the application's
+    /// executables load from outside the bundle, such as
+    /// `/Library/Frameworks/Vendor.framework`, that are named
+    /// after the application or its vendor and that no other
+    /// installed application loads.
+    ///
+    /// Note:
+    /// System and package manager libraries are never included.
+    external_libraries: Vec<PathData>,
+
+    /// External libraries that may be used by other software.
+    ///
+    /// Doc:
+    /// Stores external libraries that are also loaded by another
+    /// installed application, or that are not installed in a
+    /// location named after the application or its vendor.
+    ///
+    /// Note:
+    /// Shared libraries are reported only. They are excluded
+    /// from `all_paths()` and are never moved to the Trash.
+    shared_libraries: Vec<PathData>,
+
+    /// Files attributed by their origin attributes only.
+    ///
+    /// Doc:
+    /// Stores application-data entries whose names do not match,
+    /// but whose `com.apple.quarantine` agent or
+    /// `com.apple.LaunchServices.OpenWith` bundle identifier
+    /// names the application.
+    ///
+    /// Note:
+    /// Origin files are reported only. They are excluded from
+    /// `all_paths()` and are never moved to the Trash, since a
+    /// browser or mail client is the quarantine agent of every
+    /// file it downloaded.
+    origin_files: Vec<PathData>,
+
+    /// Path-segment matching mode.
+    ///
+    /// Doc:
+    /// When enabled, general associated files match on any path
+    /// component below the scan root, and the highest directory
+    /// owning only the application's data is proposed for
+    /// removal. See `scan_general_segments_with()`.
+    ///
+    /// Note:
+    /// Disabled by default. Only the final component is matched
+    /// then.
+    match_segments: bool,
+    /// User matching rules.
+    ///
+    /// Doc:
+    /// Include rules extend, and exclude rules restrict, the name
+    /// rules of general associated files and Background Task
+    /// Management files.
+    ///
+    /// Note:
+    /// Empty by default. The configuration is validated when it
+    /// is set.
+    rule_config: RuleConfig,
 }
 
 impl PathEntry {
+    /// Traversal depth of general application-data locations.
+    const GENERAL_DEPTH: usize = 3;
+
+    /// Traversal depth of Background Task Management locations.
+    const BACKGROUND_TASK_DEPTH: usize = 2;
+
+    /// Traversal depth of sandbox container locations.
+    const SANDBOX_DEPTH: usize = 1;
+
+    /// Traversal depth of installer receipt locations.
+    const RECEIPT_DEPTH: usize = 1;
+
+    /// User-content directories below `~/Library`, never
+    /// attributed by origin attributes.
+    const USER_CONTENT_DIRS: [&str; 4] = ["Mobile Documents", "CloudStorage", "Mail", "Messages"];
+
     /// Creates a new path entry for an application.
     ///
     /// Doc:
@@ -183,6 +284,11 @@ impl PathEntry {
             general_associated_files: Vec::new(),
             background_task_files: Vec::new(),
             sandbox_container: Vec::new(),
+            external_libraries: Vec::new(),
+            shared_libraries: Vec::new(),
+            origin_files: Vec::new(),
+            match_segments: false,
+            rule_config: RuleConfig::new(),
         }
     }
 
@@ -211,6 +317,31 @@ impl PathEntry {
         &self.sandbox_container
     }
 
+    /// get external libraries reference
+    pub fn as_external_libraries(&self) -> &[PathData] {
+        &self.external_libraries
+    }
+
+    /// get shared libraries reference
+    pub fn as_shared_libraries(&self) -> &[PathData] {
+        &self.shared_libraries
+    }
+
+    /// get origin files reference
+    pub fn as_origin_files(&self) -> &[PathData] {
+        &self.origin_files
+    }
+
+    /// Returns whether path-segment matching is enabled.
+    pub fn match_segments(&self) -> bool {
+        self.match_segments
+    }
+
+    /// get rule configuration reference
+    pub fn as_rule_config(&self) -> &RuleConfig {
+        &self.rule_config
+    }
+
     /// Returns all discovered paths.
     ///
     /// Doc:
@@ -224,6 +355,10 @@ impl PathEntry {
     /// - Sandbox containers.
     /// - background task management entries.
     /// - Installer receipt BOM (Bill of Materials) files.
+    /// - External libraries owned by the application.
+    ///
+    /// Note:
+    /// Shared libraries and origin files are not included.
     pub fn all_paths(&self) -> Vec<PathData> {
         let mut paths = Vec::new();
 
@@ -235,12 +370,44 @@ impl PathEntry {
         paths.extend(self.general_associated_files.iter().cloned());
         paths.extend(self.background_task_files.iter().cloned());
         paths.extend(self.sandbox_container.iter().cloned());
+        paths.extend(self.external_libraries.iter().cloned());
 
         let all_paths = construct_and_deduplicate_paths(paths, |item: &PathData| item.as_path());
 
         all_paths
     }
 
+    /// Returns the paths stored for a category.
+    pub fn paths_in(&self, category: PathCategory) -> Vec<PathData> {
+        match category {
+            PathCategory::Application => self.app_path.iter().cloned().collect(),
+            PathCategory::Receipts => self.bom_files.clone(),
+            PathCategory::AssociatedFiles => self.general_associated_files.clone(),
+            PathCategory::SandboxContainers => self.sandbox_container.clone(),
+            PathCategory::BackgroundTasks => self.background_task_files.clone(),
+            PathCategory::ExternalLibraries => self.external_libraries.clone(),
+            PathCategory::SharedLibraries => self.shared_libraries.clone(),
+            PathCategory::OriginFiles => self.origin_files.clone(),
+        }
+    }
+
+    /// Returns every stored path together with its category.
+    ///
+    /// Doc:
+    /// Paths are listed in `PathCategory::ALL` order. Unlike
+    /// `all_paths()`, report-only categories are included and paths
+    /// are not deduplicated across categories.
+    pub fn categorized_paths(&self) -> Vec<(PathCategory, PathData)> {
+        PathCategory::ALL
+            .iter()
+            .flat_map(|category| {
+                self.paths_in(*category)
+                    .into_iter()
+                    .map(move |path| (*category, path))
+            })
+            .collect()
+    }
+
     // ===========================Scanner===============================
     /// Executes every path-discovery scanner and stores the
     /// resulting path collections.
@@ -251,6 +418,7 @@ impl PathEntry {
     /// - Sandbox container directories.
     /// - Background Task Management files.
     /// - Installer receipt BOM (Bill of Materials) files.
+    /// - External libraries.
     ///
     /// Each scanner returns a normalized and deduplicated result for
     /// its own category. Those results are then stored
@@ -266,16 +434,79 @@ impl PathEntry {
     where
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
-        let bom_files = self.scan_bom_files(metadata, progress.clone());
-        let general_associated_files =
-            self.scan_general_associated_files(metadata, progress.clone());
-        let background_task_files = self.scan_background_task_files(metadata, progress.clone());
-        let sandbox_container = self.scan_sandbox_container(metadata, progress.clone());
+        self.scan_path_entry_with(&RealFileSystem::new(), metadata, progress)
+    }
+
+    /// Discovers every category of associated paths through the
+    /// provided filesystem.
+    ///
+    /// Doc:
+    /// Same as `scan_path_entry()`, reading every scan location
+    /// through the provided `FileSystem`.
+    ///
+    /// Note:
+    /// Scan locations are not affected by the filesystem. An
+    /// in-memory filesystem must therefore contain the regular
+    /// locations, such as `$HOME/Library/Preferences`.
+    pub fn scan_path_entry_with<FS, F>(&mut self, fs: &FS, metadata: &Metadata, progress: F)
+    where
+        FS: FileSystem + ?Sized,
+        F: Fn(usize, &Path) + Send + Sync + Clone,
+    {
+        let bom_files = self.scan_bom_files(fs, metadata, progress.clone());
+        let (general_associated_files, origin_files) =
+            self.scan_general_associated_files(fs, metadata, progress.clone());
+        let background_task_files = self.scan_background_task_files(fs, metadata, progress.clone());
+        let sandbox_container = self.scan_sandbox_container(fs, metadata, progress.clone());
+        let (external_libraries, shared_libraries) =
+            self.scan_external_libraries(fs, metadata, progress.clone());
 
         self.set_bom_files(bom_files);
         self.set_general_associated_files(general_associated_files);
         self.set_background_task_files(background_task_files);
         self.set_sandbox_container(sandbox_container);
+        self.set_external_libraries(external_libraries);
+        self.set_shared_libraries(shared_libraries);
+        self.set_origin_files(origin_files);
+    }
+
+    /// Returns every scan root together with its traversal depth.
+    ///
+    /// Doc:
+    /// Lists the locations walked by `scan_path_entry()`. Callers
+    /// that cache the filesystem, such as `ScanIndex`, use it to
+    /// cover exactly what the scanners read.
+    ///
+    /// Note:
+    /// Roots are not deduplicated. A root shared by several
+    /// categories appears once per category.
+    pub fn scan_roots() -> Vec<(PathBuf, usize)> {
+        let with_depth = |roots: Vec<PathBuf>, depth: usize| {
+            roots
+                .into_iter()
+                .map(move |root| (root, depth))
+                .collect::<Vec<_>>()
+        };
+
+        let mut roots = Vec::new();
+        roots.extend(with_depth(
+            GeneralLocations::new().location_roots(),
+            Self::GENERAL_DEPTH,
+        ));
+        roots.extend(with_depth(
+            BackgroundTaskLocations::new().all_location_roots(),
+            Self::BACKGROUND_TASK_DEPTH,
+        ));
+        roots.extend(with_depth(
+            SandboxLocations::new().location_roots(),
+            Self::SANDBOX_DEPTH,
+        ));
+        roots.extend(with_depth(
+            ReceiptsLocations::new().location_roots(),
+            Self::RECEIPT_DEPTH,
+        ));
+
+        roots
     }
 
     // ====================Setter====================
@@ -290,6 +521,22 @@ impl PathEntry {
         self.bom_files = btm_data;
     }
 
+    /// Enables or disables path-segment matching for general
+    /// associated files.
+    pub fn set_match_segments(&mut self, enabled: bool) {
+        self.match_segments = enabled;
+    }
+
+    /// Replaces the user matching rules used by later scans.
+    ///
+    /// Returns an error if a glob or regex rule is invalid. The
+    /// previous configuration is kept then.
+    pub fn set_rule_config(&mut self, config: RuleConfig) -> Result<()> {
+        config.to_match_rules()?;
+        self.rule_config = config;
+        Ok(())
+    }
+
     /// Replaces the stored associated paths.
     pub fn set_general_associated_files(&mut self, paths: Vec<PathData>) {
         self.general_associated_files = paths;
@@ -305,6 +552,21 @@ impl PathEntry {
         self.sandbox_container = paths;
     }
 
+    /// Replaces the stored external libraries.
+    pub fn set_external_libraries(&mut self, paths: Vec<PathData>) {
+        self.external_libraries = paths;
+    }
+
+    /// Replaces the stored shared libraries.
+    pub fn set_shared_libraries(&mut self, paths: Vec<PathData>) {
+        self.shared_libraries = paths;
+    }
+
+    /// Replaces the stored origin files.
+    pub fn set_origin_files(&mut self, paths: Vec<PathData>) {
+        self.origin_files = paths;
+    }
+
     /// Updates stored discovery results.
     ///
     /// Doc:
@@ -345,6 +607,12 @@ impl PathEntry {
             .map(|item| item.as_path().to_path_buf())
             .collect();
 
+        let current_library_paths: Vec<PathBuf> = self
+            .external_libraries
+            .iter()
+            .map(|item| item.as_path().to_path_buf())
+            .collect();
+
         let app_path = current_app_path
             .as_ref()
             .and_then(|app| failed.iter().find(|item| item.as_path() == app).cloned());
@@ -373,11 +641,18 @@ impl PathEntry {
             .cloned()
             .collect();
 
+        let external_libraries = failed
+            .iter()
+            .filter(|item| current_library_paths.contains(&item.as_path().to_path_buf()))
+            .cloned()
+            .collect();
+
         self.app_path = app_path;
         self.bom_files = bom_files;
         self.general_associated_files = general_associated_files;
         self.background_task_files = background_task_files;
         self.sandbox_container = sandbox_container;
+        self.external_libraries = external_libraries;
     }
 
     // ==================Internal Scanner=============
@@ -409,6 +684,26 @@ impl PathEntry {
     /// - Executable name.
     /// - Organization identifier.
     /// - Bundle identifier.
+    /// - Additional identifiers declared in `Info.plist`, such as
+    ///   privileged helper labels.
+    /// - Nested bundle identifiers.
+    ///
+    /// Names and identifiers of at most `SHORT_NEEDLE_LEN`
+    /// characters are matched as whole tokens.
+    ///
+    /// Property lists stored directly inside `LaunchAgents` and
+    /// `LaunchDaemons` are parsed as launchd job definitions and
+    /// matched by content instead of by file name. A job belongs
+    /// to the application when:
+    ///
+    /// - Its executable lives inside the application bundle.
+    /// - Its label contains the bundle identifier or one of the
+    ///   additional identifiers.
+    /// - Its `AssociatedBundleIdentifiers` list one of those
+    ///   identifiers.
+    ///
+    /// Job definitions that cannot be parsed fall back to
+    /// file-name matching.
     ///
     /// Matching results are normalized, deduplicated, and stored
     /// internally.
@@ -418,38 +713,185 @@ impl PathEntry {
     ///
     /// Note:
     /// Existing Background Task Management results are replaced when the scan completes.
-    fn scan_background_task_files<F>(&mut self, metadata: &Metadata, progress: F) -> Vec<PathData>
+    fn scan_background_task_files<FS, F>(
+        &mut self,
+        fs: &FS,
+        metadata: &Metadata,
+        progress: F,
+    ) -> Vec<PathData>
     where
+        FS: FileSystem + ?Sized,
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
-        let locations_scan: Vec<PathBuf> = BackgroundTaskLocations::new().all_location_roots();
-
-        let matcher = |path: &Path| {
-            MatchRules::new()
-                .equal(metadata.as_bundle_executable_name())
-                .equal(metadata.as_name())
-                .contain(metadata.as_bundle_id())
-                .contain(metadata.as_alias_name())
-                .contain(metadata.as_organization())
-                .check_path(path)
+        let background_locations = BackgroundTaskLocations::new();
+        let locations_scan: Vec<PathBuf> = background_locations.all_location_roots();
+        let launchd_roots: Vec<PathBuf> = background_locations.launchd_location_roots();
+
+        // Privileged helpers are installed into
+        // `/Library/PrivilegedHelperTools/<label>` together with a
+        // `/Library/LaunchDaemons/<label>.plist`, so their labels
+        // identify background items even when the vendor uses a
+        // different naming scheme from the application itself.
+        let mut extra_identifiers = metadata.extra_identifiers();
+        extra_identifiers.extend(metadata.nested_bundle_ids());
+
+        let name_rules = self
+            .with_rule_config(
+                extra_identifiers.iter().fold(
+                    MatchRules::new()
+                        .equal(metadata.as_bundle_executable_name())
+                        .equal(metadata.as_name())
+                        .contain(metadata.as_bundle_id())
+                        .contain_or_token(metadata.as_alias_name())
+                        .contain_or_token(metadata.as_organization()),
+                    |rules, identifier| rules.contain_or_token(identifier),
+                ),
+            )
+            .compile();
+
+        let job_rules = LaunchdJobRules::new(metadata, &extra_identifiers);
+
+        let match_reason = |path: &Path| {
+            let is_job_definition = path.extension().map(|ext| ext == "plist").unwrap_or(false)
+                && path
+                    .parent()
+                    .map(|parent| launchd_roots.iter().any(|root| root == parent))
+                    .unwrap_or(false);
+
+            if is_job_definition {
+                match LaunchdPlist::from_path_with(fs, path) {
+                    Ok(job) => return Self::launchd_job_reason(&job, metadata, &job_rules),
+                    Err(err) => debug!("launchd plist fallback to name matching: {}", err),
+                }
+            }
+
+            name_rules
+                .explain_path(path)
+                .map(|rule| format!("File {}", rule))
         };
 
-        let builder = |path_buf: PathBuf| {
-            let name = path_buf
+        let find = |path: &Path| {
+            let reason = match_reason(path)?;
+            let name = path
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            PathData::new(path_buf, name)
+            Some(PathData::new(path.to_path_buf(), name).with_reason(reason))
         };
 
-        let results: Vec<PathData> = scan_general(&locations_scan, 2, progress, matcher, builder);
+        let results: Vec<PathData> = scan_general_find_with(
+            fs,
+            &locations_scan,
+            Self::BACKGROUND_TASK_DEPTH,
+            progress,
+            find,
+        );
 
         let filtered = construct_and_deduplicate_paths(results, |item: &PathData| item.as_path());
 
         filtered
     }
 
+    /// Explains why a parsed launchd job belongs to the application.
+    ///
+    /// Doc:
+    /// A job is owned when it executes code from the application
+    /// bundle, or when its label or associated bundle identifiers
+    /// name the application or one of its additional identifiers.
+    ///
+    /// Returns `None` when the job is not owned.
+    ///
+    /// Design:
+    /// Name-derived signals such as the alias or organization are
+    /// deliberately excluded. They are useful for locating files
+    /// but produce false positives for jobs that merely share a
+    /// vendor prefix, while the job definition already states what
+    /// it runs and on whose behalf.
+    fn launchd_job_reason(
+        job: &LaunchdPlist,
+        metadata: &Metadata,
+        rules: &LaunchdJobRules,
+    ) -> Option<String> {
+        if job.references_bundle(metadata.as_bundle_path(), metadata.as_bundle_id()) {
+            debug!("launchd job '{}' runs from the app bundle", job.as_label());
+            return Some("launchd job runs code from the application bundle".to_string());
+        }
+
+        if let Some(rule) = rules.label.explain_string(job.as_label()) {
+            return Some(format!("launchd label {}", rule));
+        }
+
+        job.as_associated_bundle_ids()
+            .iter()
+            .find(|id| rules.associated_id.check_string(id))
+            .map(|id| format!("launchd job is associated with \"{}\"", id))
+    }
+
+    /// Adds the user matching rules to scanner rules.
+    ///
+    /// Note:
+    /// The configuration is validated by `set_rule_config()`, so
+    /// the scanner rules are only returned unchanged when that
+    /// validation was bypassed.
+    fn with_rule_config<'a>(&'a self, rules: MatchRules<'a>) -> MatchRules<'a> {
+        rules
+            .clone()
+            .with_config(&self.rule_config)
+            .unwrap_or_else(|err| {
+                debug!("rule configuration ignored: {}", err);
+                rules
+            })
+    }
+
+    /// Explains how a file's origin attributes name the
+    /// application.
+    ///
+    /// Doc:
+    /// The quarantine agent is compared with the application
+    /// name, executable name, and bundle identifiers. The
+    /// LaunchServices bundle identifier is compared with the
+    /// bundle identifiers only.
+    ///
+    /// Returns `None` when neither attribute names the
+    /// application.
+    ///
+    /// Note:
+    /// Attributes are read one at a time, and the second only
+    /// when the first does not match.
+    fn origin_match_reason<FS>(
+        fs: &FS,
+        path: &Path,
+        agent_rules: &CompiledRules,
+        bundle_rules: &CompiledRules,
+    ) -> Option<String>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        if let Some(agent) = FileOrigin::quarantine_agent_with(fs, path)
+            .filter(|agent| agent_rules.check_string(agent))
+        {
+            return Some(format!("Downloaded by \"{}\"", agent));
+        }
+
+        FileOrigin::origin_bundle_id_with(fs, path)
+            .filter(|id| bundle_rules.check_string(id))
+            .map(|id| format!("Opened with \"{}\"", id))
+    }
+
+    /// Returns whether a path lies in a user-content directory.
+    ///
+    /// Doc:
+    /// iCloud Drive, cloud storage providers, Mail, and Messages
+    /// keep user documents below `~/Library`. Their origin
+    /// attributes describe how a document arrived, not which
+    /// application owns it.
+    fn is_user_content(path: &Path, home_library: &Path) -> bool {
+        Self::USER_CONTENT_DIRS
+            .iter()
+            .any(|dir| path.starts_with(home_library.join(dir)))
+    }
+
     /// Discovers traditional application files.
     ///
     /// Doc:
@@ -463,45 +905,139 @@ impl PathEntry {
     /// - Cache files.
     /// - Logs.
     ///
-    /// Matching is performed using application metadata.
+    /// Matching is performed using application metadata,
+    /// including the identifiers of nested bundles. Short names
+    /// are matched as whole tokens, so "Arc" does not claim
+    /// `search` caches.
+    ///
+    /// Entries whose names do not match, and that do not lie
+    /// below a matching entry, are returned separately as origin
+    /// files when their `com.apple.quarantine` agent or
+    /// `com.apple.LaunchServices.OpenWith` bundle identifier names
+    /// the application. See `FileOrigin`. User-content
+    /// directories such as iCloud Drive are skipped.
+    ///
+    /// Rules set with `set_rule_config()` join the name rules.
+    ///
+    /// With `set_match_segments(true)`, results are proposed as
+    /// their highest owning directory, such as a vendor directory
+    /// holding only this application's data.
     ///
     /// Note:
     /// Sandbox containers and background task management entries are discovered through
     /// separate scanners.
-    fn scan_general_associated_files<F>(
+    fn scan_general_associated_files<FS, F>(
         &mut self,
+        fs: &FS,
         metadata: &Metadata,
         progress: F,
-    ) -> Vec<PathData>
+    ) -> (Vec<PathData>, Vec<PathData>)
     where
+        FS: FileSystem + ?Sized,
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
         let locations_scan: Vec<PathBuf> = GeneralLocations::new().location_roots();
+        let nested_ids = metadata.nested_bundle_ids();
+
+        let rules = self
+            .with_rule_config(
+                nested_ids.iter().fold(
+                    MatchRules::new()
+                        .equal(metadata.as_bundle_executable_name())
+                        .equal(metadata.as_name())
+                        .contain(metadata.as_bundle_id())
+                        .contain_or_token(metadata.as_alias_name()),
+                    |rules, id| rules.contain_or_token(id),
+                ),
+            )
+            .compile();
+
+        let bundle_rules = nested_ids
+            .iter()
+            .fold(
+                MatchRules::new().equal(metadata.as_bundle_id()),
+                |rules, id| rules.equal(id),
+            )
+            .compile();
 
-        let matcher = |path: &Path| {
-            MatchRules::new()
-                .equal(metadata.as_bundle_executable_name())
-                .equal(metadata.as_name())
-                .contain(metadata.as_bundle_id())
-                .contain(metadata.as_alias_name())
-                .check_path(path)
+        let agent_rules = nested_ids
+            .iter()
+            .fold(
+                MatchRules::new()
+                    .equal(metadata.as_name())
+                    .equal(metadata.as_bundle_executable_name())
+                    .equal(metadata.as_bundle_id()),
+                |rules, id| rules.equal(id),
+            )
+            .compile();
+
+        let matcher = |path: &Path| rules.check_path(path);
+
+        // Caches, saved state, and thumbnails are not always named
+        // after the application, but their origin attributes may
+        // still name it. Attributes are only read, in the same
+        // walk, for entries that neither match nor lie below a
+        // match.
+        let home_library = env::var("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"))
+            .join("Library");
+        let find_origin = |path: &Path| {
+            if Self::is_user_content(path, &home_library) {
+                return None;
+            }
+
+            let reason = Self::origin_match_reason(fs, path, &agent_rules, &bundle_rules)?;
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            Some(PathData::new(path.to_path_buf(), name).with_reason(reason))
         };
+
+        // Owning directories proposed in segment mode do not match
+        // by name themselves.
         let builder = |path_buf: PathBuf| {
             let name = path_buf
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
+            let reason = rules
+                .explain_path(&path_buf)
+                .map(|rule| format!("File {}", rule))
+                .unwrap_or_else(|| "Directory holding only matching entries".to_string());
 
-            PathData::new(path_buf, name)
+            PathData::new(path_buf, name).with_reason(reason)
         };
 
-        let asc_results: Vec<PathData> =
-            scan_general(&locations_scan, 3, progress, matcher, builder);
+        let (asc_results, origin_results) = scan_general_split_with(
+            fs,
+            &locations_scan,
+            Self::GENERAL_DEPTH,
+            self.match_segments,
+            progress,
+            matcher,
+            builder,
+            find_origin,
+        );
 
         let results =
             construct_and_deduplicate_paths(asc_results, |item: &PathData| item.as_path());
 
-        results
+        // Owning directories proposed in segment mode may hold
+        // entries that only matched by origin.
+        let origin_files: Vec<PathData> =
+            construct_and_deduplicate_paths(origin_results, |item: &PathData| item.as_path())
+                .into_iter()
+                .filter(|origin| {
+                    !results
+                        .iter()
+                        .any(|item| origin.as_path().starts_with(item.as_path()))
+                })
+                .collect();
+
+        (results, origin_files)
     }
 
     /// Discovers sandbox container directories.
@@ -511,7 +1047,17 @@ impl PathEntry {
     /// identify containers belonging to the provided application.
     ///
     /// Matching is primarily performed using the application's
-    /// bundle identifier.
+    /// bundle identifier and the identifiers of its nested
+    /// bundles. Group containers named in the application's
+    /// entitlements are matched by their exact name.
+    ///
+    /// Containers carrying `containermanagerd` metadata are
+    /// attributed by the identifier it declares, so containers
+    /// with UUID names are matched exactly.
+    ///
+    /// Group containers of application groups also entitled by
+    /// another installed application are kept, since removing
+    /// them would lose that application's data.
     ///
     /// When a matching container is found, the container root
     /// directory is returned as the discovered path.
@@ -520,35 +1066,125 @@ impl PathEntry {
     /// Container scanning is separate from general associated-file
     /// scanning because sandboxed applications use a different
     /// filesystem layout.
-    fn scan_sandbox_container<F>(&mut self, metadata: &Metadata, progress: F) -> Vec<PathData>
+    fn scan_sandbox_container<FS, F>(
+        &mut self,
+        fs: &FS,
+        metadata: &Metadata,
+        progress: F,
+    ) -> Vec<PathData>
     where
+        FS: FileSystem + ?Sized,
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
         let containers_dir = SandboxLocations::new();
-        let locations_scan = containers_dir.location_roots();
-        let patterns = containers_dir.as_pattern();
-
-        let is_container_match = |path: &Path| {
-            MatchRules::new()
-                .contain(metadata.as_bundle_id())
-                .contain(metadata.as_alias_name())
-                .contain(metadata.as_bundle_executable_name())
-                .check_path(path)
+
+        // Helpers and extensions are sandboxed on their own and get
+        // containers named after their own bundle identifiers.
+        let nested_ids = metadata.nested_bundle_ids();
+
+        // Entitled application groups are the exact names of the
+        // group containers, which rarely mention the application.
+        // Other group containers of the vendor are not claimed.
+        let container_rules = metadata
+            .as_application_groups()
+            .iter()
+            .fold(
+                nested_ids.iter().fold(
+                    MatchRules::new()
+                        .contain(metadata.as_bundle_id())
+                        .contain(metadata.as_alias_name())
+                        .contain(metadata.as_bundle_executable_name()),
+                    |rules, id| rules.contain(id),
+                ),
+                |rules, group| rules.equal(group),
+            )
+            .compile();
+
+        let file_rules = nested_ids
+            .iter()
+            .fold(
+                MatchRules::new()
+                    .contain(metadata.as_bundle_id())
+                    .contain(metadata.as_alias_name()),
+                |rules, id| rules.contain(id),
+            )
+            .compile();
+
+        // Container metadata names the owner exactly, so only
+        // identifiers are compared, never display names, and
+        // `com.vendor.app2` does not match `com.vendor.app`. Team
+        // prefixed identifiers match only with the bundle
+        // identifier, as in `ABCDE12345.com.vendor.app.shared`.
+        let team_bundle_id = metadata.team_bundle_id();
+        let team_bundle_scope = if team_bundle_id.is_empty() {
+            String::new()
+        } else {
+            format!("{}.", team_bundle_id)
         };
+        let identifier_rules = metadata
+            .as_application_groups()
+            .iter()
+            .fold(
+                nested_ids.iter().fold(
+                    MatchRules::new()
+                        .equal(metadata.as_bundle_id())
+                        .equal(&team_bundle_id)
+                        .prefix(&team_bundle_scope),
+                    |rules, id| rules.equal(id),
+                ),
+                |rules, group| rules.equal(group),
+            )
+            .compile();
+
+        // A group also entitled by another installed application
+        // holds that application's data too, and is kept.
+        let shared_rules = Self::shared_application_groups(fs, metadata)
+            .iter()
+            .fold(MatchRules::new(), |rules, group| rules.equal(group))
+            .compile();
 
+        let is_identifier_match = |identifier: &str| {
+            !shared_rules.check_string(identifier) && identifier_rules.check_string(identifier)
+        };
+        let is_container_match =
+            |path: &Path| !shared_rules.check_path(path) && container_rules.check_path(path);
         let is_file_match = |path: &Path| {
-            MatchRules::new()
-                .contain(metadata.as_bundle_id())
-                .contain(metadata.as_alias_name())
-                .check_path(path)
+            !path
+                .ancestors()
+                .any(|ancestor| shared_rules.check_path(ancestor))
+                && file_rules.check_path(path)
         };
 
-        let builder = |container_dir: &Path, _file_path: &Path| {
+        // The scanner passes the container itself for name matches
+        // and its metadata file for identifier matches, otherwise
+        // the file that matched inside the container.
+        let builder = |container_dir: &Path, file_path: &Path| {
             let folder_name = container_dir
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
 
+            let reason = if file_path == container_dir {
+                container_rules
+                    .explain_path(container_dir)
+                    .map(|rule| format!("Container {}", rule))
+            } else if file_path == container_dir.join(CONTAINER_METADATA_FILE) {
+                container_identifier_with(fs, container_dir)
+                    .and_then(|identifier| identifier_rules.explain_string(&identifier))
+                    .map(|rule| format!("Container metadata identifier {}", rule))
+            } else {
+                file_rules.explain_path(file_path).map(|rule| {
+                    format!(
+                        "Holds \"{}\" whose {}",
+                        file_path
+                            .file_name()
+                            .map(|n| n.to_string_lossy())
+                            .unwrap_or_default(),
+                        rule
+                    )
+                })
+            };
+
             let display_name = if folder_name == metadata.as_bundle_id() {
                 folder_name
             } else {
@@ -556,13 +1192,15 @@ impl PathEntry {
             };
 
             PathData::new(container_dir.to_path_buf(), display_name)
+                .with_reason(reason.unwrap_or_default())
         };
 
-        let container_results: Vec<PathData> = scan_container(
-            &locations_scan,
-            1,
-            &patterns,
+        let container_results: Vec<PathData> = scan_container_with(
+            fs,
+            containers_dir.as_locations(),
+            Self::SANDBOX_DEPTH,
             progress,
+            is_identifier_match,
             is_container_match,
             is_file_match,
             builder,
@@ -574,6 +1212,262 @@ impl PathEntry {
         results
     }
 
+    /// Discovers libraries loaded from outside the bundle.
+    ///
+    /// Doc:
+    /// Starts from the external libraries recorded in the
+    /// metadata and splits the ones present on disk into:
+    ///
+    /// - Libraries owned by the application.
+    /// - Libraries that may be used by other software.
+    ///
+    /// A library is owned only when it lies in a vendor location
+    /// and the folder it was installed as names the application
+    /// or its organization, and no other installed application
+    /// loads it:
+    ///
+    /// ```text
+    /// /Library/Frameworks/Vendor.framework
+    /// /Library/Application Support/Vendor/lib/libvendor.dylib
+    /// ```
+    ///
+    /// Other applications are found in `ApplicationLocations`,
+    /// two levels deep, and the load commands of their main
+    /// executables are inspected the same way.
+    ///
+    /// Design:
+    /// Shared frameworks, such as a vendor runtime installed
+    /// into `/Library/Frameworks`, are commonly used by several
+    /// applications of the same vendor, and command line tools
+    /// or plug-ins load libraries without being applications.
+    /// Such consumers cannot all be found, so any library not
+    /// clearly owned by the vendor is reported without being
+    /// selected for removal.
+    ///
+    /// Note:
+    /// Installed applications are only inspected when the
+    /// application owns at least one external library. Only
+    /// their slice headers and load commands are read.
+    fn scan_external_libraries<FS, F>(
+        &mut self,
+        fs: &FS,
+        metadata: &Metadata,
+        progress: F,
+    ) -> (Vec<PathData>, Vec<PathData>)
+    where
+        FS: FileSystem + ?Sized,
+        F: Fn(usize, &Path) + Send + Sync + Clone,
+    {
+        let libraries: Vec<&PathBuf> = metadata
+            .as_external_libraries()
+            .iter()
+            .filter(|path| fs.exists(path))
+            .collect();
+
+        let vendor_rules = MatchRules::new()
+            .equal(metadata.as_bundle_executable_name())
+            .equal(metadata.as_name())
+            .contain_or_token(metadata.as_alias_name())
+            .contain_or_token(metadata.as_organization())
+            .compile();
+        let vendor_roots = Self::vendor_library_roots();
+
+        let vendor_reason = |library: &Path| {
+            vendor_roots.iter().find_map(|root| {
+                let installed = library.strip_prefix(root).ok()?.components().next()?;
+                let installed = Path::new(installed.as_os_str());
+
+                vendor_rules.explain_path(installed).map(|rule| {
+                    format!(
+                        "Installed in {} as \"{}\" whose {}",
+                        root.display(),
+                        installed.display(),
+                        rule
+                    )
+                })
+            })
+        };
+
+        let (candidates, shared): (Vec<&PathBuf>, Vec<&PathBuf>) = libraries
+            .into_iter()
+            .partition(|library| vendor_reason(library).is_some());
+
+        let used_elsewhere: Vec<PathBuf> = if candidates.is_empty() {
+            Vec::new()
+        } else {
+            let counter = AtomicUsize::new(0);
+
+            Self::other_applications(fs, metadata)
+                .par_iter()
+                .flat_map_iter(|app| {
+                    let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                    progress(n, app);
+
+                    Self::main_executable(fs, app)
+                        .and_then(|executable| {
+                            LinkedLibraries::from_executable_with(fs, &executable)
+                                .map(|libraries| {
+                                    libraries
+                                        .external_paths(&executable, app, |path| fs.exists(path))
+                                })
+                                .ok()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        };
+
+        let (used, owned): (Vec<&PathBuf>, Vec<&PathBuf>) = candidates
+            .into_iter()
+            .partition(|library| used_elsewhere.contains(library));
+
+        let to_path_data = |path: &PathBuf, reason: String| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            PathData::new(path.clone(), name).with_reason(reason)
+        };
+
+        let owned: Vec<PathData> = owned
+            .into_iter()
+            .map(|library| {
+                let reason = vendor_reason(library).unwrap_or_default();
+                to_path_data(
+                    library,
+                    format!("{}, loaded by no other application", reason),
+                )
+            })
+            .collect();
+
+        let shared: Vec<PathData> = shared
+            .into_iter()
+            .map(|library| {
+                to_path_data(
+                    library,
+                    "Not installed in a vendor location named after the application".to_string(),
+                )
+            })
+            .chain(used.into_iter().map(|library| {
+                to_path_data(library, "Also loaded by another application".to_string())
+            }))
+            .collect();
+
+        for library in &shared {
+            debug!("external library kept as shared: {}", library);
+        }
+
+        (owned, shared)
+    }
+
+    /// Returns the directories vendors install libraries into.
+    fn vendor_library_roots() -> Vec<PathBuf> {
+        let home = env::var("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"));
+
+        vec![
+            PathBuf::from("/Library/Frameworks"),
+            PathBuf::from("/Library/Application Support"),
+            home.join("Library/Frameworks"),
+            home.join("Library/Application Support"),
+        ]
+    }
+
+    /// Lists the other installed applications.
+    ///
+    /// Doc:
+    /// Walks `ApplicationLocations` two levels deep, so grouped
+    /// applications such as `/Applications/Utilities/*.app` are
+    /// found. The application itself and bundles nested in
+    /// another application are skipped.
+    fn other_applications<FS>(fs: &FS, metadata: &Metadata) -> Vec<PathBuf>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let own_bundle = metadata.as_bundle_path();
+
+        ApplicationLocations::new()
+            .location_roots()
+            .iter()
+            .flat_map(|root| fs.walk(root, 2))
+            .filter(|entry| entry.depth() > 0 && entry.is_dir())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().map(|ext| ext == "app").unwrap_or(false))
+            .filter(|path| {
+                path != own_bundle
+                    && !path
+                        .parent()
+                        .and_then(|parent| parent.extension())
+                        .map(|ext| ext == "app")
+                        .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Returns the main executable of an application bundle.
+    ///
+    /// Note:
+    /// Returns `None` when `Info.plist` cannot be read or does
+    /// not name an executable.
+    fn main_executable<FS>(fs: &FS, app: &Path) -> Option<PathBuf>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let plist = fs
+            .read_plist(&app.join("Contents").join("Info.plist"))
+            .ok()?;
+        let name = plist
+            .as_dictionary()?
+            .get("CFBundleExecutable")?
+            .as_string()?
+            .trim();
+
+        (!name.is_empty()).then(|| app.join("Contents").join("MacOS").join(name))
+    }
+
+    /// Returns the application groups also entitled by another
+    /// installed application.
+    ///
+    /// Doc:
+    /// Reads the entitlements of the main executable of every
+    /// other installed application, for example Word and Excel
+    /// both entitled to `UBF8T346G9.Office`.
+    ///
+    /// Note:
+    /// Other applications are only inspected when the
+    /// application declares at least one group. Only their slice
+    /// headers and code signatures are read.
+    fn shared_application_groups<'a, FS>(fs: &FS, metadata: &'a Metadata) -> Vec<&'a str>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let groups = metadata.as_application_groups();
+        if groups.is_empty() {
+            return Vec::new();
+        }
+
+        let declared_elsewhere: Vec<String> = Self::other_applications(fs, metadata)
+            .par_iter()
+            .filter_map(|app| Self::main_executable(fs, app))
+            .flat_map_iter(|executable| {
+                Entitlements::from_executable_with(fs, &executable)
+                    .ok()
+                    .flatten()
+                    .map(|entitlements| entitlements.as_application_groups().to_vec())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        groups
+            .iter()
+            .filter(|group| declared_elsewhere.contains(group))
+            .inspect(|group| debug!("application group shared with another app: {}", group))
+            .map(String::as_str)
+            .collect()
+    }
+
     /// Discovers BOM receipt files.
     ///
     /// Doc:
@@ -617,8 +1511,9 @@ impl PathEntry {
     /// Note:
     /// Existing receipt records are replaced when scanning
     /// completes.
-    fn scan_bom_files<F>(&mut self, metadata: &Metadata, progress: F) -> Vec<PathData>
+    fn scan_bom_files<FS, F>(&mut self, fs: &FS, metadata: &Metadata, progress: F) -> Vec<PathData>
     where
+        FS: FileSystem + ?Sized,
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
         self.bom_files.clear();
@@ -629,14 +1524,15 @@ impl PathEntry {
             .map(|location| location.as_root().to_path_buf())
             .collect();
 
+        let rules = MatchRules::new()
+            .contain(metadata.as_name())
+            .contain(metadata.as_bundle_executable_name())
+            .contain(metadata.as_organization())
+            .contain(metadata.as_bundle_id())
+            .compile();
+
         let matcher = |path: &Path| {
-            path.extension().map(|ext| ext == "bom").unwrap_or(false)
-                && MatchRules::new()
-                    .contain(metadata.as_name())
-                    .contain(metadata.as_bundle_executable_name())
-                    .contain(metadata.as_organization())
-                    .contain(metadata.as_bundle_id())
-                    .check_path(&path)
+            path.extension().map(|ext| ext == "bom").unwrap_or(false) && rules.check_path(path)
         };
 
         let builder = |path_buf: PathBuf| {
@@ -644,13 +1540,63 @@ impl PathEntry {
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
-            PathData::new(path_buf, name)
+            let reason = rules
+                .explain_path(&path_buf)
+                .map(|rule| format!("Receipt {}", rule))
+                .unwrap_or_default();
+            PathData::new(path_buf, name).with_reason(reason)
         };
 
-        let results: Vec<PathData> = scan_general(&locations_scan, 1, progress, matcher, builder);
+        let results: Vec<PathData> = scan_general_with(
+            fs,
+            &locations_scan,
+            Self::RECEIPT_DEPTH,
+            progress,
+            matcher,
+            builder,
+        );
 
         let filtered = construct_and_deduplicate_paths(results, |item: &PathData| item.as_path());
 
         filtered
     }
 }
+
+/// Compiled rules attributing launchd jobs.
+///
+/// Doc:
+/// Matches job labels containing, and associated bundle
+/// identifiers equal to, the bundle identifier or one of the
+/// additional identifiers. See `PathEntry::launchd_job_reason()`.
+///
+/// Note:
+/// Compiled once per scan rather than for every job definition.
+struct LaunchdJobRules {
+    label: CompiledRules,
+    associated_id: CompiledRules,
+}
+
+impl LaunchdJobRules {
+    fn new(metadata: &Metadata, extra_identifiers: &[&str]) -> Self {
+        let label = extra_identifiers
+            .iter()
+            .fold(
+                MatchRules::new().contain(metadata.as_bundle_id()),
+                |rules, identifier| rules.contain(identifier),
+            )
+            .compile();
+
+        let associated_id = extra_identifiers
+            .iter()
+            .fold(
+                MatchRules::new().equal(metadata.as_bundle_id()),
+                |rules, identifier| rules.equal(identifier),
+            )
+            .compile();
+
+        Self {
+            label,
+            associated_id,
+        }
+    }
+}
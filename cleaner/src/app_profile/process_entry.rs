@@ -29,10 +29,16 @@
 //! Matching is performed using information derived from the
 //! target application, including:
 //!
+//! - Executable paths inside the application bundle.
+//! - Parent and child process relationships.
 //! - Executable name.
 //! - Bundle identifier.
 //! - Common helper process names.
 //!
+//! Every discovered process records the reason it matched,
+//! so callers can distinguish certain matches from heuristic
+//! ones.
+//!
 //! Runtime process information is used by higher-level cleanup
 //! operations to determine whether an application is currently
 //! active and whether running processes should be terminated
@@ -44,11 +50,78 @@
 //!..
 
 use crate::app_profile::metadata::Metadata;
+use crate::app_profile::process_source::{ProcessInfo, ProcessSource, SysinfoProcessSource};
 use crate::utility::MatchRules;
 use mini_logger::debug;
 use rayon::prelude::*;
-use std::ffi::OsString;
-use sysinfo::{ProcessesToUpdate, System};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Confidence of a process match.
+///
+/// Doc:
+/// Ordered from the weakest to the strongest signal.
+///
+/// Note:
+/// `Low` matches are never terminated without explicit
+/// confirmation. See `ProcessData::requires_confirmation()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl MatchConfidence {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+}
+
+/// Reason a process was attributed to an application.
+///
+/// Doc:
+/// - `ExecutablePath`: the process executable is located inside
+///   the application bundle.
+/// - `ChildOf`: the process descends from a process whose
+///   executable is inside the bundle. Stores the parent PID.
+/// - `NameOrCommand`: the process name or command line matched
+///   the application metadata.
+///
+/// Note:
+/// `NameOrCommand` is a heuristic. Unrelated processes that
+/// mention the bundle identifier in their arguments, such as
+/// `grep com.vendor.app`, are matched as well.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchReason {
+    ExecutablePath,
+    ChildOf(i32),
+    #[default]
+    NameOrCommand,
+}
+
+impl MatchReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ExecutablePath => "Executable inside bundle",
+            Self::ChildOf(_) => "Child of application process",
+            Self::NameOrCommand => "Name or command line",
+        }
+    }
+
+    pub fn confidence(&self) -> MatchConfidence {
+        match self {
+            Self::ExecutablePath => MatchConfidence::High,
+            Self::ChildOf(_) => MatchConfidence::Medium,
+            Self::NameOrCommand => MatchConfidence::Low,
+        }
+    }
+}
 
 /// Snapshot of a running process.
 ///
@@ -61,11 +134,20 @@ use sysinfo::{ProcessesToUpdate, System};
 /// - Process identifier (PID).
 /// - Full command line.
 /// - Process name.
+/// - The reason the process matched.
+/// - Parent PID and child PIDs.
+/// - Owning user name.
+/// - Start time, in seconds since the Unix epoch.
+/// - CPU usage in percent and resident memory in bytes.
+/// - Executable path, when readable.
 ///
 /// The command line is retained because it often contains
 /// application identifiers that are not present in the
 /// process name alone.
 ///
+/// The resource details are shown before termination so users
+/// can recognize processes that may hold unsaved work.
+///
 /// Note:
 /// `ProcessData` is a lightweight snapshot of process information
 /// captured during scanning and does not maintain a live
@@ -75,12 +157,82 @@ pub struct ProcessData {
     pid: i32,
     command: String,
     name: String,
+    match_reason: MatchReason,
+    parent_pid: Option<i32>,
+    children: Vec<i32>,
+    user: String,
+    start_time: u64,
+    cpu_usage: f32,
+    memory: u64,
+    exe: Option<PathBuf>,
 }
 
 impl ProcessData {
     /// Construct a ProcessData.
     pub fn new(pid: i32, command: String, name: String) -> Self {
-        Self { pid, command, name }
+        Self {
+            pid,
+            command,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Construct a ProcessData from a process table entry.
+    pub fn from_info(info: &ProcessInfo, match_reason: MatchReason, children: Vec<i32>) -> Self {
+        Self {
+            pid: info.pid(),
+            command: info.as_command().to_string(),
+            name: info.as_name().to_string(),
+            match_reason,
+            parent_pid: info.parent_pid(),
+            children,
+            user: info.as_user().to_string(),
+            start_time: info.start_time(),
+            cpu_usage: info.cpu_usage(),
+            memory: info.memory(),
+            exe: info.as_exe().map(Path::to_path_buf),
+        }
+    }
+
+    pub fn with_match_reason(mut self, match_reason: MatchReason) -> Self {
+        self.match_reason = match_reason;
+        self
+    }
+
+    pub fn with_parent_pid(mut self, parent_pid: Option<i32>) -> Self {
+        self.parent_pid = parent_pid;
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<i32>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn with_user(mut self, user: String) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn with_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    pub fn with_cpu_usage(mut self, cpu_usage: f32) -> Self {
+        self.cpu_usage = cpu_usage;
+        self
+    }
+
+    pub fn with_memory(mut self, memory: u64) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn with_exe(mut self, exe: Option<PathBuf>) -> Self {
+        self.exe = exe;
+        self
     }
 
     /// get the copy of pid
@@ -97,6 +249,57 @@ impl ProcessData {
     pub fn as_name(&self) -> &str {
         &self.name
     }
+
+    /// get the copy of the match reason
+    pub fn match_reason(&self) -> MatchReason {
+        self.match_reason
+    }
+
+    /// Returns true when the process may only be terminated
+    /// after explicit confirmation.
+    ///
+    /// Doc:
+    /// Name and command line matches also hit unrelated
+    /// processes, such as an editor with a file of the
+    /// application open or a `grep` for its identifier.
+    pub fn requires_confirmation(&self) -> bool {
+        self.match_reason.confidence() == MatchConfidence::Low
+    }
+
+    /// get the copy of parent pid
+    pub fn parent_pid(&self) -> Option<i32> {
+        self.parent_pid
+    }
+
+    /// get the reference of child pids
+    pub fn as_children(&self) -> &[i32] {
+        &self.children
+    }
+
+    /// get the reference of owning user name
+    pub fn as_user(&self) -> &str {
+        &self.user
+    }
+
+    /// get the copy of start time in seconds since the Unix epoch
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+
+    /// get the copy of CPU usage in percent
+    pub fn cpu_usage(&self) -> f32 {
+        self.cpu_usage
+    }
+
+    /// get the copy of resident memory in bytes
+    pub fn memory(&self) -> u64 {
+        self.memory
+    }
+
+    /// get the reference of executable path
+    pub fn as_exe(&self) -> Option<&Path> {
+        self.exe.as_deref()
+    }
 }
 
 /// Collection of running application processes.
@@ -135,40 +338,107 @@ impl ProcessEntry {
         self.processes.is_empty()
     }
 
+    /// Returns the processes that are only terminated after
+    /// explicit confirmation.
+    pub fn requiring_confirmation(&self) -> Vec<&ProcessData> {
+        self.processes
+            .iter()
+            .filter(|process| process.requires_confirmation())
+            .collect()
+    }
+
+    /// Returns discovered processes in tree order.
+    ///
+    /// Doc:
+    /// Each process is paired with its depth in the tree. A
+    /// process is a root when its parent was not discovered.
+    /// Children follow their parent, ordered by PID.
+    ///
+    /// ```text
+    /// 0  Vendor            (501)
+    /// 1    Vendor Helper   (502)
+    /// 1    Vendor Helper   (503)
+    /// 0  vendor-agent      (620)
+    /// ```
+    pub fn tree(&self) -> Vec<(usize, &ProcessData)> {
+        let by_pid: HashMap<i32, &ProcessData> =
+            self.processes.iter().map(|p| (p.pid, p)).collect();
+
+        let mut roots: Vec<&ProcessData> = self
+            .processes
+            .iter()
+            .filter(|p| {
+                p.parent_pid
+                    .is_none_or(|parent| !by_pid.contains_key(&parent))
+            })
+            .collect();
+        roots.sort_by_key(|p| p.pid);
+
+        let mut tree = Vec::with_capacity(self.processes.len());
+        let mut stack: Vec<(usize, &ProcessData)> =
+            roots.into_iter().rev().map(|p| (0, p)).collect();
+
+        while let Some((depth, process)) = stack.pop() {
+            // Guards against cycles caused by PID reuse.
+            if tree.len() >= self.processes.len() {
+                break;
+            }
+
+            tree.push((depth, process));
+
+            let mut children: Vec<&ProcessData> = process
+                .children
+                .iter()
+                .filter_map(|pid| by_pid.get(pid).copied())
+                .collect();
+            children.sort_by_key(|p| std::cmp::Reverse(p.pid));
+
+            stack.extend(children.into_iter().map(|child| (depth + 1, child)));
+        }
+
+        tree
+    }
+
     /// Discovers running processes associated with an application.
     ///
     /// Doc:
     /// Scans the current process table and attempts to identify
     /// processes belonging to the provided application.
     ///
-    /// Matching is performed using:
+    /// Matching is performed in order of confidence:
+    ///
+    /// 1. Processes whose executable is inside the application
+    ///    bundle.
+    /// 2. Descendants of those processes.
+    /// 3. Processes whose name or command line matches the
+    ///    application metadata.
+    ///
+    /// The text match uses:
     ///
     /// - Executable name.
     /// - Bundle identifier.
     /// - Common helper process names.
+    /// - Nested bundle executable names and identifiers.
     ///
-    /// Both the process name and complete command line are
-    /// inspected when evaluating matches.
-    ///
-    /// Matching logic is delegated to `MatchRules`, which
-    /// provides case-insensitive and Unicode-normalized
-    /// comparisons.
+    /// Matching logic for the text match is delegated to
+    /// `MatchRules`, which provides case-insensitive and
+    /// Unicode-normalized comparisons.
     ///
     /// Design:
-    /// Process identification relies on metadata-derived
-    /// matching rules rather than executable names alone.
-    ///
-    /// Many macOS applications launch helper processes,
-    /// background services, or child processes whose names
-    /// differ from the primary application executable.
+    /// The executable path is the most reliable signal. It finds
+    /// helpers regardless of how they are named, and it is not
+    /// fooled by unrelated processes that merely mention the
+    /// application in their arguments.
     ///
-    /// A reusable `MatchRules` instance is constructed from
-    /// application metadata and applied to both process
-    /// names and command lines.
+    /// Children are included because applications launch
+    /// interpreters, shells, and system tools whose executables
+    /// live outside the bundle but which still hold application
+    /// files open.
     ///
-    /// This provides consistent matching behavior across
-    /// process discovery and filesystem scanning while
-    /// avoiding duplicated comparison logic.
+    /// The text match is kept as a fallback for processes whose
+    /// executable path cannot be read, for example processes
+    /// owned by other users, and for applications that copy
+    /// their helpers outside the bundle.
     ///
     /// For example, helper processes are often named:
     ///
@@ -180,21 +450,37 @@ impl ProcessEntry {
     /// Visual Studio Code Helper
     /// ```
     ///
-    /// Matching against executable names, bundle identifiers,
-    /// helper process names, and command-line arguments improves
-    /// detection accuracy across different application
-    /// architectures.
-    ///
     /// Note:
-    /// Process discovery is inherently heuristic-based and may
+    /// Text matches are inherently heuristic-based and may
     /// produce false positives when unrelated processes contain
-    /// similar identifiers.
+    /// similar identifiers. They are recorded with
+    /// `MatchReason::NameOrCommand` so callers can treat them
+    /// with care.
     ///
     /// Empty metadata values do not generate matching rules
     /// and are ignored during rule construction.
+    ///
+    /// The current process is never matched.
     pub fn find_app_processes(metadata: &Metadata) -> Self {
-        let mut sys = System::new();
-        sys.refresh_processes(ProcessesToUpdate::All, true);
+        Self::find_app_processes_with(metadata, &SysinfoProcessSource::new())
+    }
+
+    /// Discovers processes associated with an application using
+    /// the provided `ProcessSource`.
+    pub fn find_app_processes_with<S>(metadata: &Metadata, source: &S) -> Self
+    where
+        S: ProcessSource + ?Sized,
+    {
+        let own_pid = std::process::id() as i32;
+        let snapshot: Vec<ProcessInfo> = source
+            .processes()
+            .into_iter()
+            .filter(|process| process.pid() != own_pid)
+            .collect();
+
+        let mut children = Self::children_by_parent(&snapshot);
+        let mut matched = Self::match_by_executable(&snapshot, metadata.as_bundle_path());
+        Self::match_children(&children, &mut matched);
 
         // Design:
         // Many macOS applications spawn helper processes using a
@@ -214,12 +500,25 @@ impl ProcessEntry {
         // Rules are reused against both process names and
         // command lines to provide consistent matching
         // behavior throughout discovery.
+        //
+        // Nested bundles (login items, XPC services, extensions)
+        // run as separate processes under their own executable
+        // names and identifiers, so they are included as well.
+        let nested_executables = metadata.nested_executable_names();
+        let nested_ids = metadata.nested_bundle_ids();
+
         let rules = MatchRules::new()
             .equal(metadata.as_bundle_executable_name())
             .equal(&helper)
             .contain(metadata.as_bundle_id())
             .contain(metadata.as_alias_name());
 
+        let rules = nested_executables
+            .iter()
+            .fold(rules, |rules, name| rules.equal(name));
+
+        let rules = nested_ids.iter().fold(rules, |rules, id| rules.contain(id));
+
         debug!(
             "Process matching rules: count={}, executable='{}', bundle_id='{}', alias='{}'",
             rules.len(),
@@ -228,47 +527,112 @@ impl ProcessEntry {
             metadata.as_alias_name()
         );
 
-        let processes = sys
-            .processes()
+        let rules = rules.compile();
+
+        // Match against both the process name and the full
+        // command line.
+        //
+        // Some applications expose useful identifiers only
+        // through command-line arguments while others expose
+        // them through the process name.
+        let text_matches: Vec<i32> = snapshot
             .par_iter()
-            .filter_map(|(&pid, process)| {
-                // Join full command line for debug
-                let cmd_line = process
-                    .cmd()
-                    .iter()
-                    .map(|s: &OsString| s.to_string_lossy().into_owned())
-                    .collect::<Vec<_>>()
-                    .join(" ");
-
-                // Convert process name into a UTF-8 string so it can
-                // be evaluated by MatchRules.
-                let process_name = process.name().to_string_lossy().to_string();
-
-                // Match against both the process name and the full
-                // command line.
-                //
-                // Some applications expose useful identifiers only
-                // through command-line arguments while others expose
-                // them through the process name.
-                let is_match = rules.check_string(&process_name) || rules.check_string(&cmd_line);
-
-                if is_match {
-                    debug!(
-                        "MATCH pid={} name='{}' cmd='{}'",
-                        pid, process_name, cmd_line
-                    );
-                    // Construct the result
-                    Some(ProcessData::new(
-                        pid.as_u32() as i32,
-                        cmd_line,
-                        process_name,
-                    ))
-                } else {
-                    None
-                }
+            .filter(|process| !matched.contains_key(&process.pid()))
+            .filter(|process| {
+                rules.check_string(process.as_name()) || rules.check_string(process.as_command())
             })
+            .map(|process| process.pid())
             .collect();
 
+        matched.extend(
+            text_matches
+                .into_iter()
+                .map(|pid| (pid, MatchReason::NameOrCommand)),
+        );
+
+        let mut processes: Vec<ProcessData> = snapshot
+            .into_iter()
+            .filter_map(|process| {
+                let reason = *matched.get(&process.pid())?;
+
+                debug!(
+                    "MATCH pid={} name='{}' cmd='{}' reason='{}'",
+                    process.pid(),
+                    process.as_name(),
+                    process.as_command(),
+                    reason.as_str()
+                );
+
+                let mut process_children = children.remove(&process.pid()).unwrap_or_default();
+                process_children.sort_unstable();
+
+                Some(ProcessData::from_info(&process, reason, process_children))
+            })
+            .collect();
+
+        processes.sort_by_key(|p| (std::cmp::Reverse(p.match_reason.confidence()), p.pid));
+
         Self { processes }
     }
+
+    /// Maps every PID to the PIDs of its direct children.
+    fn children_by_parent(snapshot: &[ProcessInfo]) -> HashMap<i32, Vec<i32>> {
+        let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+
+        for process in snapshot {
+            if let Some(parent) = process.parent_pid() {
+                children.entry(parent).or_default().push(process.pid());
+            }
+        }
+
+        children
+    }
+
+    /// Matches processes whose executable is inside the bundle.
+    ///
+    /// Note:
+    /// Both the bundle path as given and its canonical form are
+    /// compared, since process executables are reported with
+    /// symlinks resolved.
+    fn match_by_executable(
+        snapshot: &[ProcessInfo],
+        bundle_path: &Path,
+    ) -> HashMap<i32, MatchReason> {
+        if bundle_path.as_os_str().is_empty() {
+            return HashMap::new();
+        }
+
+        let canonical = std::fs::canonicalize(bundle_path).ok();
+
+        snapshot
+            .iter()
+            .filter(|process| {
+                process.as_exe().is_some_and(|exe| {
+                    exe.starts_with(bundle_path)
+                        || canonical
+                            .as_deref()
+                            .is_some_and(|canonical| exe.starts_with(canonical))
+                })
+            })
+            .map(|process| (process.pid(), MatchReason::ExecutablePath))
+            .collect()
+    }
+
+    /// Adds descendants of matched processes.
+    ///
+    /// Doc:
+    /// Walks the process tree breadth-first from every matched
+    /// process and records each descendant with its parent PID.
+    fn match_children(children: &HashMap<i32, Vec<i32>>, matched: &mut HashMap<i32, MatchReason>) {
+        let mut queue: VecDeque<i32> = matched.keys().copied().collect();
+
+        while let Some(parent) = queue.pop_front() {
+            for &child in children.get(&parent).into_iter().flatten() {
+                if let Entry::Vacant(entry) = matched.entry(child) {
+                    entry.insert(MatchReason::ChildOf(parent));
+                    queue.push_back(child);
+                }
+            }
+        }
+    }
 }
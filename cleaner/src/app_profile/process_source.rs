@@ -0,0 +1,245 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Process table sources.
+//!
+//! Process discovery reads the process table through the
+//! `ProcessSource` trait instead of querying the operating
+//! system directly.
+//!
+//! Two implementations are provided:
+//!
+//! - `SysinfoProcessSource` reads the live process table.
+//! - `StaticProcessSource` returns a fixed list of processes.
+//!
+//! Design:
+//! Discovery results depend on every process running on the
+//! machine. A static source makes discovery deterministic, so
+//! matching rules can be tested against known process tables.
+//!..
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use sysinfo::{
+    MINIMUM_CPU_UPDATE_INTERVAL, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users,
+};
+
+/// Process table entry.
+///
+/// Doc:
+/// Stores what a `ProcessSource` reports about a process:
+///
+/// - PID and parent PID.
+/// - Process name and full command line.
+/// - Executable path, when readable.
+/// - Owning user name.
+/// - Start time, in seconds since the Unix epoch.
+/// - CPU usage in percent and resident memory in bytes.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pid: i32,
+    parent_pid: Option<i32>,
+    name: String,
+    command: String,
+    exe: Option<PathBuf>,
+    user: String,
+    start_time: u64,
+    cpu_usage: f32,
+    memory: u64,
+}
+
+impl ProcessInfo {
+    pub fn new(pid: i32, parent_pid: Option<i32>, name: String, command: String) -> Self {
+        Self {
+            pid,
+            parent_pid,
+            name,
+            command,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_exe(mut self, exe: Option<PathBuf>) -> Self {
+        self.exe = exe;
+        self
+    }
+
+    pub fn with_user(mut self, user: String) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn with_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    pub fn with_cpu_usage(mut self, cpu_usage: f32) -> Self {
+        self.cpu_usage = cpu_usage;
+        self
+    }
+
+    pub fn with_memory(mut self, memory: u64) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// get the copy of pid
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// get the copy of parent pid
+    pub fn parent_pid(&self) -> Option<i32> {
+        self.parent_pid
+    }
+
+    /// get the reference of process name
+    pub fn as_name(&self) -> &str {
+        &self.name
+    }
+
+    /// get the reference of command line
+    pub fn as_command(&self) -> &str {
+        &self.command
+    }
+
+    /// get the reference of executable path
+    pub fn as_exe(&self) -> Option<&Path> {
+        self.exe.as_deref()
+    }
+
+    /// get the reference of owning user name
+    pub fn as_user(&self) -> &str {
+        &self.user
+    }
+
+    /// get the copy of start time in seconds since the Unix epoch
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+
+    /// get the copy of CPU usage in percent
+    pub fn cpu_usage(&self) -> f32 {
+        self.cpu_usage
+    }
+
+    /// get the copy of resident memory in bytes
+    pub fn memory(&self) -> u64 {
+        self.memory
+    }
+}
+
+/// Process table abstraction.
+///
+/// Doc:
+/// Implementations return a snapshot of the processes visible
+/// to the current user.
+pub trait ProcessSource: Send + Sync {
+    fn processes(&self) -> Vec<ProcessInfo>;
+}
+
+/// Live process table backed by `sysinfo`.
+///
+/// Note:
+/// CPU usage is computed from the difference between two
+/// refreshes, so taking a snapshot waits for the minimum update
+/// interval of `sysinfo`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SysinfoProcessSource;
+
+impl SysinfoProcessSource {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ProcessSource for SysinfoProcessSource {
+    fn processes(&self) -> Vec<ProcessInfo> {
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_user(UpdateKind::OnlyIfNotSet)
+                .with_memory()
+                .with_cpu(),
+        );
+
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            false,
+            ProcessRefreshKind::nothing().with_cpu(),
+        );
+
+        let users = Users::new_with_refreshed_list();
+
+        sys.processes()
+            .iter()
+            .map(|(pid, process)| {
+                let command = process
+                    .cmd()
+                    .iter()
+                    .map(|s: &OsString| s.to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let user = process
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map(|user| user.name().to_string())
+                    .unwrap_or_default();
+
+                ProcessInfo::new(
+                    pid.as_u32() as i32,
+                    process.parent().map(|parent| parent.as_u32() as i32),
+                    process.name().to_string_lossy().to_string(),
+                    command,
+                )
+                .with_exe(process.exe().map(Path::to_path_buf))
+                .with_user(user)
+                .with_start_time(process.start_time())
+                .with_cpu_usage(process.cpu_usage())
+                .with_memory(process.memory())
+            })
+            .collect()
+    }
+}
+
+/// Fixed process table.
+///
+/// Doc:
+/// Returns the processes it was constructed with. Intended for
+/// tests.
+#[derive(Debug, Default, Clone)]
+pub struct StaticProcessSource {
+    processes: Vec<ProcessInfo>,
+}
+
+impl StaticProcessSource {
+    pub fn new(processes: Vec<ProcessInfo>) -> Self {
+        Self { processes }
+    }
+}
+
+impl ProcessSource for StaticProcessSource {
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.processes.clone()
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Application size breakdown.
+//!
+//! Shows where the space of an application goes before it is
+//! removed:
+//!
+//! ```text
+//! Cafe.app                 412.0 MB
+//!   Executables             48.2 MB   arm64 23.9 MB, x86_64 24.3 MB
+//!   Frameworks             210.5 MB
+//!   Resources               96.1 MB
+//!   Localizations           31.7 MB
+//!   PlugIns                 12.4 MB
+//!   Nested apps             13.1 MB
+//! Leftovers                 88.0 MB
+//!   Associated files        80.2 MB
+//!   Sandbox containers       7.8 MB
+//! ```
+//!
+//! Note:
+//! Sizes are logical file sizes. Allocated blocks, compression
+//! and clones are not taken into account.
+//!..
+
+use std::path::{Component, Path};
+
+use crate::app_profile::path_category::PathCategory;
+use crate::app_profile::path_entry::PathEntry;
+use crate::macho::SliceHeader;
+use crate::path_data::PathData;
+use crate::utility::FileSystem;
+
+/// Number of largest files kept in a breakdown.
+const LARGEST_FILES: usize = 10;
+
+/// Section of an application bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BundleSection {
+    /// `Contents/MacOS`.
+    Executables,
+    /// `Contents/Frameworks`.
+    Frameworks,
+    /// `Contents/Resources`, localizations excluded.
+    Resources,
+    /// Every `*.lproj` folder.
+    Localizations,
+    /// `Contents/PlugIns`.
+    PlugIns,
+    /// Applications nested inside the bundle.
+    NestedApps,
+    /// Everything else.
+    Other,
+}
+
+impl BundleSection {
+    /// Every section, in display order.
+    pub const ALL: [BundleSection; 7] = [
+        Self::Executables,
+        Self::Frameworks,
+        Self::Resources,
+        Self::Localizations,
+        Self::PlugIns,
+        Self::NestedApps,
+        Self::Other,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Executables => "Executables",
+            Self::Frameworks => "Frameworks",
+            Self::Resources => "Resources",
+            Self::Localizations => "Localizations",
+            Self::PlugIns => "PlugIns",
+            Self::NestedApps => "Nested apps",
+            Self::Other => "Other",
+        }
+    }
+
+    /// Returns the section of a path relative to the bundle.
+    ///
+    /// Doc:
+    /// Nested applications take precedence over localizations,
+    /// which take precedence over the `Contents` folders.
+    fn of(relative: &Path) -> Self {
+        let names: Vec<&str> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        let Some((_, folders)) = names.split_last() else {
+            return Self::Other;
+        };
+
+        if folders.iter().any(|name| name.ends_with(".app")) {
+            return Self::NestedApps;
+        }
+
+        if folders.iter().any(|name| name.ends_with(".lproj")) {
+            return Self::Localizations;
+        }
+
+        match folders {
+            ["Contents", "MacOS", ..] => Self::Executables,
+            ["Contents", "Frameworks", ..] => Self::Frameworks,
+            ["Contents", "Resources", ..] => Self::Resources,
+            ["Contents", "PlugIns", ..] => Self::PlugIns,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Size breakdown of an application and its leftovers.
+///
+/// Doc:
+/// Stores:
+///
+/// - The bundle size and the size of each `BundleSection`.
+/// - The executable bytes per CPU architecture.
+/// - The largest files of the bundle.
+/// - The size of each category moved to Trash before the
+///   bundle, in `PathCategory::REMOVAL_ORDER`.
+///
+/// Design:
+/// The breakdown is computed on demand from the file system,
+/// since it is only needed for display and reports, and is not
+/// kept inside `AppProfile`.
+///
+/// Note:
+/// Executable bytes per architecture cover the slices of the
+/// files in `Contents/MacOS`. Universal headers and padding
+/// are not attributed to an architecture.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SizeBreakdown {
+    bundle_size: u64,
+    sections: Vec<(BundleSection, u64)>,
+    architectures: Vec<(&'static str, u64)>,
+    largest_files: Vec<(PathData, u64)>,
+    leftovers: Vec<(PathCategory, u64)>,
+}
+
+impl SizeBreakdown {
+    /// Computes the breakdown of a bundle and the leftovers
+    /// discovered in `path_entry` through the provided
+    /// `FileSystem`.
+    pub fn new_with<FS>(fs: &FS, bundle_path: &Path, path_entry: &PathEntry) -> Self
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let mut breakdown = Self {
+            sections: BundleSection::ALL.iter().map(|s| (*s, 0)).collect(),
+            ..Self::default()
+        };
+
+        let mut files: Vec<(PathData, u64)> = Vec::new();
+
+        // Links, such as `Versions/Current` inside frameworks,
+        // point at files counted under their real path.
+        for entry in fs.walk(bundle_path, usize::MAX) {
+            if entry.is_dir() || entry.is_symlink() {
+                continue;
+            }
+
+            let path = entry.as_path();
+            let size = fs.symlink_metadata(path).map(|m| m.len()).unwrap_or(0);
+            let relative = path.strip_prefix(bundle_path).unwrap_or(path);
+            let section = BundleSection::of(relative);
+
+            breakdown.bundle_size += size;
+            if let Some((_, total)) = breakdown.sections.iter_mut().find(|(s, _)| *s == section) {
+                *total += size;
+            }
+
+            if section == BundleSection::Executables {
+                breakdown.add_architectures(fs, path);
+            }
+
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            files.push((PathData::new(path.to_path_buf(), name), size));
+        }
+
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_path().cmp(b.0.as_path())));
+        files.truncate(LARGEST_FILES);
+        breakdown.largest_files = files;
+
+        // The categories `Cleaner::move_to_trash()` removes with
+        // the bundle, so report-only categories are left out.
+        breakdown.leftovers = PathCategory::REMOVAL_ORDER
+            .iter()
+            .map(|category| {
+                let size = path_entry
+                    .paths_in(*category)
+                    .iter()
+                    .map(|path| fs.total_size(path.as_path()))
+                    .sum();
+                (*category, size)
+            })
+            .collect();
+
+        breakdown
+    }
+
+    /// Adds the slices of an executable to the architectures.
+    ///
+    /// Note:
+    /// Only the slice headers are read. Files that are not Mach-O,
+    /// such as scripts, are skipped.
+    fn add_architectures<FS>(&mut self, fs: &FS, path: &Path)
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let Ok(headers) = SliceHeader::read_with(fs, path) else {
+            return;
+        };
+
+        for header in &headers {
+            let Ok(slice) = header.parse() else {
+                continue;
+            };
+            let size = header.size();
+
+            match self
+                .architectures
+                .iter_mut()
+                .find(|(arch, _)| *arch == slice.arch_name())
+            {
+                Some((_, total)) => *total += size,
+                None => self.architectures.push((slice.arch_name(), size)),
+            }
+        }
+    }
+
+    /// get the copy of bundle size in bytes
+    pub fn bundle_size(&self) -> u64 {
+        self.bundle_size
+    }
+
+    /// get section sizes reference, in `BundleSection::ALL` order
+    pub fn as_sections(&self) -> &[(BundleSection, u64)] {
+        &self.sections
+    }
+
+    /// Returns the size of one bundle section.
+    pub fn section(&self, section: BundleSection) -> u64 {
+        self.sections
+            .iter()
+            .find(|(s, _)| *s == section)
+            .map(|(_, size)| *size)
+            .unwrap_or(0)
+    }
+
+    /// get executable bytes per architecture reference
+    pub fn as_architectures(&self) -> &[(&'static str, u64)] {
+        &self.architectures
+    }
+
+    /// get largest files reference, largest first
+    pub fn as_largest_files(&self) -> &[(PathData, u64)] {
+        &self.largest_files
+    }
+
+    /// get leftover sizes per category reference, in
+    /// `PathCategory::REMOVAL_ORDER`
+    pub fn as_leftovers(&self) -> &[(PathCategory, u64)] {
+        &self.leftovers
+    }
+
+    /// Returns the total size of the leftovers.
+    pub fn leftover_size(&self) -> u64 {
+        self.leftovers.iter().map(|(_, size)| size).sum()
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Component control tracking.
+//!
+//! Records the outcome of every attempt to stop a running
+//! component of the application:
+//!
+//! - Process termination.
+//! - launchd job unloading.
+//!
+//! Design:
+//! Mirrors `TrashEntry`. Successful and unsuccessful attempts
+//! are stored together so callers can report partial results,
+//! such as jobs that could only be unloaded with administrator
+//! rights.
+//!..
+
+use crate::app_profile::ProcessData;
+use crate::control::{ServiceJob, ServiceOutcome, TerminationOutcome};
+
+/// Result of component control operations.
+///
+/// Doc:
+/// Stores:
+///
+/// - Processes together with their termination outcome.
+/// - launchd jobs together with their unload outcome.
+///
+/// Note:
+/// Entries are appended in the order the operations were
+/// performed. The record is cleared by `Cleaner::reset()`.
+#[derive(Debug, Default, Clone)]
+pub struct ControlEntry {
+    processes: Vec<(ProcessData, TerminationOutcome)>,
+    services: Vec<(ServiceJob, ServiceOutcome)>,
+}
+
+impl ControlEntry {
+    pub fn new(
+        processes: Vec<(ProcessData, TerminationOutcome)>,
+        services: Vec<(ServiceJob, ServiceOutcome)>,
+    ) -> Self {
+        Self {
+            processes,
+            services,
+        }
+    }
+
+    /// get process termination outcomes reference
+    pub fn processes(&self) -> &[(ProcessData, TerminationOutcome)] {
+        &self.processes
+    }
+
+    /// get launchd job unload results reference
+    pub fn services(&self) -> &[(ServiceJob, ServiceOutcome)] {
+        &self.services
+    }
+
+    /// Returns the processes that are still running.
+    pub fn processes_not_stopped(&self) -> Vec<&ProcessData> {
+        self.processes
+            .iter()
+            .filter(|(_, outcome)| !outcome.is_stopped())
+            .map(|(process, _)| process)
+            .collect()
+    }
+
+    /// Returns the jobs that could not be unloaded without
+    /// administrator rights.
+    pub fn services_requiring_admin(&self) -> Vec<&ServiceJob> {
+        self.services
+            .iter()
+            .filter(|(_, outcome)| *outcome == ServiceOutcome::RequiresAdmin)
+            .map(|(job, _)| job)
+            .collect()
+    }
+
+    pub fn extend_processes(
+        &mut self,
+        processes: impl IntoIterator<Item = (ProcessData, TerminationOutcome)>,
+    ) {
+        self.processes.extend(processes);
+    }
+
+    pub fn extend_services(
+        &mut self,
+        services: impl IntoIterator<Item = (ServiceJob, ServiceOutcome)>,
+    ) {
+        self.services.extend(services);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty() && self.services.is_empty()
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Control of running application components.
+//!
+//! Before application files are moved to Trash, the components
+//! that keep them in use should be stopped:
+//!
+//! - Running application processes.
+//! - Loaded launchd jobs.
+//!
+//! The module is built around:
+//!
+//! - `terminate_processes`, which stops processes gracefully
+//!   and escalates when required.
+//! - `ServiceControl`, the abstraction used to unload launchd
+//!   jobs.
+//! - `ControlEntry`, the record of every stop attempt.
+//!
+//! Design:
+//! Stopping components is separated from discovering them.
+//! Scanning only reads the file system, while this module
+//! performs the state-changing operations and records their
+//! outcomes for reporting.
+//!
+//! Note:
+//! Outcomes are recorded rather than returned as errors because
+//! a component that cannot be stopped should not prevent the
+//! remaining cleanup from running.
+//!..
+
+mod control_entry;
+mod process;
+mod service;
+
+pub use control_entry::ControlEntry;
+pub use process::{
+    ProcessControl, ProcessSignal, RecordingProcessControl, SignalDelivery, SystemProcessControl,
+    TerminationOutcome, TerminationPolicy, terminate_processes,
+};
+pub use service::{
+    LaunchctlServiceControl, RecordingServiceControl, ServiceControl, ServiceDomain, ServiceJob,
+    ServiceOutcome,
+};
+
+pub(crate) use process::termination_order;
+pub(crate) use service::unload_jobs;
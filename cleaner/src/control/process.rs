@@ -0,0 +1,562 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Graceful process termination.
+//!
+//! Sending `SIGTERM` only requests termination. Applications may
+//! take a moment to save state, ignore the request entirely, or
+//! be restarted by a helper that is still running.
+//!
+//! Termination is therefore performed in steps:
+//!
+//! 1. Order processes so children are stopped before parents.
+//! 2. Send `SIGTERM`.
+//! 3. Poll the process table until the process exits or the
+//!    grace period elapses.
+//! 4. Optionally escalate to `SIGKILL`.
+//!
+//! The module is built around:
+//!
+//! - `TerminationPolicy`, which configures waiting and
+//!   escalation.
+//! - `TerminationOutcome`, the per-process result.
+//! - `ProcessControl`, the abstraction used to signal processes
+//!   and check whether they still run.
+//!
+//! Two `ProcessControl` implementations are provided:
+//!
+//! - `SystemProcessControl` signals real processes and polls the
+//!   process table through `sysinfo`.
+//! - `RecordingProcessControl` simulates processes in memory, for
+//!   tests.
+//!
+//! Design:
+//! Processes are stopped one tree level at a time. Helpers and
+//! login items frequently relaunch the main application when it
+//! disappears, so the deepest processes are stopped first and
+//! their parents only afterwards.
+//!
+//! Note:
+//! Zombie processes are treated as exited. They no longer run
+//! and only wait to be reaped by their parent.
+//!..
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use mini_logger::debug;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System};
+
+use crate::app_profile::ProcessData;
+use crate::errors::{ErrorKind, Result};
+use crate::syscom;
+
+pub use crate::syscom::{ProcessSignal, SignalDelivery};
+
+/// Time allowed for a process to disappear after `SIGKILL`.
+const KILL_WAIT: Duration = Duration::from_secs(1);
+
+/// Process termination settings.
+///
+/// Doc:
+/// - `grace_period`: how long to wait after `SIGTERM`.
+/// - `poll_interval`: how often the process table is checked.
+/// - `escalate`: whether `SIGKILL` is sent once the grace period
+///   elapsed.
+///
+/// Note:
+/// The default waits five seconds and escalates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminationPolicy {
+    grace_period: Duration,
+    poll_interval: Duration,
+    escalate: bool,
+}
+
+impl Default for TerminationPolicy {
+    fn default() -> Self {
+        Self {
+            grace_period: Duration::from_secs(5),
+            poll_interval: Duration::from_millis(100),
+            escalate: true,
+        }
+    }
+}
+
+impl TerminationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_escalation(mut self, escalate: bool) -> Self {
+        self.escalate = escalate;
+        self
+    }
+
+    /// get the copy of the grace period
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    /// get the copy of the poll interval
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// get whether `SIGKILL` escalation is enabled
+    pub fn escalate(&self) -> bool {
+        self.escalate
+    }
+}
+
+/// Result of terminating a single process.
+///
+/// Doc:
+/// - `Exited`: the process exited after `SIGTERM`.
+/// - `Escalated`: the process exited only after `SIGKILL`.
+/// - `StillRunning`: the process outlived every signal sent.
+/// - `PermissionDenied`: the process belongs to another user.
+/// - `AlreadyExited`: the process was gone before any signal.
+/// - `Unconfirmed`: the process was matched by name or command
+///   line only and its termination was not confirmed. No signal
+///   was sent.
+/// - `Failed`: the signal could not be delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminationOutcome {
+    Exited,
+    Escalated,
+    StillRunning,
+    PermissionDenied,
+    AlreadyExited,
+    Unconfirmed,
+    Failed(ErrorKind),
+}
+
+impl TerminationOutcome {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Exited => "Exited",
+            Self::Escalated => "Exited after SIGKILL",
+            Self::StillRunning => "Still running",
+            Self::PermissionDenied => "Permission denied",
+            Self::AlreadyExited => "Already exited",
+            Self::Unconfirmed => "Not confirmed",
+            Self::Failed(_) => "Failed",
+        }
+    }
+
+    /// Returns true when the process is no longer running.
+    pub fn is_stopped(&self) -> bool {
+        matches!(self, Self::Exited | Self::Escalated | Self::AlreadyExited)
+    }
+}
+
+/// Process signaling abstraction.
+///
+/// Doc:
+/// Implementations deliver signals and report whether a process
+/// is still running.
+///
+/// Design:
+/// `Cleaner` terminates processes through this trait, so the
+/// termination sequence can be tested without signaling real
+/// processes.
+pub trait ProcessControl: Send + Sync {
+    fn signal(&self, pid: i32, signal: ProcessSignal) -> Result<SignalDelivery>;
+
+    fn is_running(&self, pid: i32) -> bool;
+}
+
+/// Process control for real processes.
+///
+/// Doc:
+/// Signals are delivered with `kill(2)`. Liveness is read from
+/// the process table through `sysinfo`.
+#[derive(Debug, Default)]
+pub struct SystemProcessControl {
+    sys: Mutex<System>,
+}
+
+impl SystemProcessControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProcessControl for SystemProcessControl {
+    fn signal(&self, pid: i32, signal: ProcessSignal) -> Result<SignalDelivery> {
+        syscom::send_signal(pid, signal)
+    }
+
+    fn is_running(&self, pid: i32) -> bool {
+        let Ok(mut sys) = self.sys.lock() else {
+            return false;
+        };
+
+        let pid = Pid::from_u32(pid as u32);
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        sys.process(pid)
+            .map(|process| process.status() != ProcessStatus::Zombie)
+            .unwrap_or(false)
+    }
+}
+
+/// In-memory process control.
+///
+/// Doc:
+/// Simulates processes without signaling anything:
+///
+/// - Every PID is running until it receives a signal that
+///   stops it, unless marked as exited.
+/// - `SIGTERM` stops a process unless it ignores it.
+/// - `SIGKILL` always stops a process.
+/// - Processes marked as protected answer with
+///   `PermissionDenied`.
+///
+/// Every delivered signal is recorded in call order.
+#[derive(Debug, Default)]
+pub struct RecordingProcessControl {
+    exited: Mutex<HashSet<i32>>,
+    ignore_terminate: HashSet<i32>,
+    protected: HashSet<i32>,
+    signals: Mutex<Vec<(i32, ProcessSignal)>>,
+}
+
+impl RecordingProcessControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a process as already exited.
+    pub fn with_exited(self, pid: i32) -> Self {
+        if let Ok(mut exited) = self.exited.lock() {
+            exited.insert(pid);
+        }
+        self
+    }
+
+    /// Marks a process as ignoring `SIGTERM`.
+    pub fn with_ignored_terminate(mut self, pid: i32) -> Self {
+        self.ignore_terminate.insert(pid);
+        self
+    }
+
+    /// Marks a process as owned by another user.
+    pub fn with_protected(mut self, pid: i32) -> Self {
+        self.protected.insert(pid);
+        self
+    }
+
+    /// Returns the delivered signals in call order.
+    pub fn signals(&self) -> Vec<(i32, ProcessSignal)> {
+        self.signals
+            .lock()
+            .map(|signals| signals.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl ProcessControl for RecordingProcessControl {
+    fn signal(&self, pid: i32, signal: ProcessSignal) -> Result<SignalDelivery> {
+        if self.protected.contains(&pid) {
+            return Ok(SignalDelivery::PermissionDenied);
+        }
+
+        let mut exited = self.exited.lock().map_err(|_| {
+            ErrorKind::failed()
+                .with_summary("Failed to signal process")
+                .with_reason("Recording state is poisoned")
+        })?;
+
+        if exited.contains(&pid) {
+            return Ok(SignalDelivery::NotFound);
+        }
+
+        if let Ok(mut signals) = self.signals.lock() {
+            signals.push((pid, signal));
+        }
+
+        if signal == ProcessSignal::Kill || !self.ignore_terminate.contains(&pid) {
+            exited.insert(pid);
+        }
+
+        Ok(SignalDelivery::Sent)
+    }
+
+    fn is_running(&self, pid: i32) -> bool {
+        self.exited
+            .lock()
+            .map(|exited| !exited.contains(&pid))
+            .unwrap_or(false)
+    }
+}
+
+/// Terminates processes and waits for them to exit.
+///
+/// Doc:
+/// Processes are grouped by their depth in the process tree,
+/// restricted to the provided processes, and stopped deepest
+/// group first.
+///
+/// Processes that require confirmation are only signaled when
+/// their PID is listed in `confirmed`. The others are reported
+/// as `Unconfirmed` without being signaled.
+///
+/// The progress callback receives the number of processes with
+/// a final outcome and the total number of processes.
+///
+/// Returns every process together with its outcome, in the order
+/// the outcomes were determined.
+pub fn terminate_processes<C, F>(
+    processes: &[ProcessData],
+    confirmed: &[i32],
+    policy: &TerminationPolicy,
+    control: &C,
+    progress: Option<F>,
+) -> Vec<(ProcessData, TerminationOutcome)>
+where
+    C: ProcessControl + ?Sized,
+    F: Fn(usize, usize) + Send + Sync + Clone,
+{
+    let total = processes.len();
+    let mut results = Vec::with_capacity(total);
+
+    let (terminable, unconfirmed): (Vec<ProcessData>, Vec<ProcessData>) =
+        processes.iter().cloned().partition(|process| {
+            !process.requires_confirmation() || confirmed.contains(&process.pid())
+        });
+
+    for process in unconfirmed {
+        debug!(
+            "Skip PID {} ({}): {}",
+            process.pid(),
+            process.as_name(),
+            TerminationOutcome::Unconfirmed.as_str()
+        );
+
+        results.push((process, TerminationOutcome::Unconfirmed));
+
+        if let Some(ref progress_hook) = progress {
+            progress_hook(results.len(), total);
+        }
+    }
+
+    for group in termination_order(&terminable) {
+        let outcomes = terminate_group(control, &group, policy);
+
+        for (process, outcome) in group.into_iter().zip(outcomes) {
+            debug!(
+                "Terminate PID {} ({}): {}",
+                process.pid(),
+                process.as_name(),
+                outcome.as_str()
+            );
+
+            results.push((process, outcome));
+
+            if let Some(ref progress_hook) = progress {
+                progress_hook(results.len(), total);
+            }
+        }
+    }
+
+    results
+}
+
+/// Groups processes by tree depth, deepest group first.
+///
+/// Doc:
+/// The depth of a process is the number of its ancestors that
+/// are part of the provided processes. Unrelated processes all
+/// have depth zero and form a single group.
+pub(crate) fn termination_order(processes: &[ProcessData]) -> Vec<Vec<ProcessData>> {
+    let pids: HashSet<i32> = processes.iter().map(ProcessData::pid).collect();
+
+    let parents: HashMap<i32, i32> = processes
+        .iter()
+        .filter_map(|p| Some((p.pid(), p.parent_pid()?)))
+        .collect();
+
+    let depth = |pid: i32| {
+        let mut depth = 0;
+        let mut current = pid;
+
+        // Bounded by the number of processes to guard against
+        // cycles caused by PID reuse between snapshots.
+        while let Some(&parent) = parents.get(&current) {
+            if depth >= pids.len() || !pids.contains(&parent) {
+                break;
+            }
+
+            depth += 1;
+            current = parent;
+        }
+
+        depth
+    };
+
+    let mut groups: BTreeMap<usize, Vec<ProcessData>> = BTreeMap::new();
+
+    for process in processes {
+        groups
+            .entry(depth(process.pid()))
+            .or_default()
+            .push(process.clone());
+    }
+
+    groups.into_values().rev().collect()
+}
+
+/// Terminates a group of processes that can be stopped together.
+fn terminate_group<C>(
+    control: &C,
+    group: &[ProcessData],
+    policy: &TerminationPolicy,
+) -> Vec<TerminationOutcome>
+where
+    C: ProcessControl + ?Sized,
+{
+    let mut outcomes = vec![TerminationOutcome::StillRunning; group.len()];
+
+    let signaled = signal_all(
+        control,
+        group,
+        (0..group.len()).collect(),
+        ProcessSignal::Terminate,
+        &mut outcomes,
+        TerminationOutcome::AlreadyExited,
+    );
+
+    let (exited, remaining) = wait_for_exit(
+        control,
+        group,
+        signaled,
+        policy.grace_period,
+        policy.poll_interval,
+    );
+
+    for index in exited {
+        outcomes[index] = TerminationOutcome::Exited;
+    }
+
+    if !policy.escalate || remaining.is_empty() {
+        return outcomes;
+    }
+
+    let killed = signal_all(
+        control,
+        group,
+        remaining,
+        ProcessSignal::Kill,
+        &mut outcomes,
+        TerminationOutcome::Exited,
+    );
+
+    let (exited, _) = wait_for_exit(control, group, killed, KILL_WAIT, policy.poll_interval);
+
+    for index in exited {
+        outcomes[index] = TerminationOutcome::Escalated;
+    }
+
+    outcomes
+}
+
+/// Sends a signal to the selected processes.
+///
+/// Doc:
+/// Records the outcome of processes that could not be signaled
+/// and returns the indices of the processes that were.
+/// `not_found` is the outcome recorded for processes that no
+/// longer exist.
+fn signal_all<C>(
+    control: &C,
+    group: &[ProcessData],
+    indices: Vec<usize>,
+    signal: ProcessSignal,
+    outcomes: &mut [TerminationOutcome],
+    not_found: TerminationOutcome,
+) -> Vec<usize>
+where
+    C: ProcessControl + ?Sized,
+{
+    indices
+        .into_iter()
+        .filter(|&index| match control.signal(group[index].pid(), signal) {
+            Ok(SignalDelivery::Sent) => true,
+            Ok(SignalDelivery::NotFound) => {
+                outcomes[index] = not_found.clone();
+                false
+            }
+            Ok(SignalDelivery::PermissionDenied) => {
+                outcomes[index] = TerminationOutcome::PermissionDenied;
+                false
+            }
+            Err(err) => {
+                outcomes[index] = TerminationOutcome::Failed(err);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Polls the process table until the selected processes exit.
+///
+/// Doc:
+/// Returns the indices of exited processes and of processes
+/// still running once the timeout elapsed.
+fn wait_for_exit<C>(
+    control: &C,
+    group: &[ProcessData],
+    mut pending: Vec<usize>,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> (Vec<usize>, Vec<usize>)
+where
+    C: ProcessControl + ?Sized,
+{
+    let deadline = Instant::now() + timeout;
+    let mut exited = Vec::new();
+
+    loop {
+        let (running, stopped): (Vec<usize>, Vec<usize>) = pending
+            .into_iter()
+            .partition(|&index| control.is_running(group[index].pid()));
+
+        exited.extend(stopped);
+        pending = running;
+
+        if pending.is_empty() || Instant::now() >= deadline {
+            return (exited, pending);
+        }
+
+        std::thread::sleep(poll_interval);
+    }
+}
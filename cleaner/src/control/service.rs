@@ -0,0 +1,370 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! launchd job unloading.
+//!
+//! Moving a LaunchAgent or LaunchDaemon plist to Trash does not
+//! stop the job. launchd keeps the job loaded until logout or
+//! reboot and may restart it on demand, which keeps application
+//! files in use and recreates files that were just removed.
+//!
+//! Jobs are therefore unloaded before their definitions are
+//! trashed.
+//!
+//! The module is built around:
+//!
+//! - `ServiceJob`, a loaded job candidate derived from its plist.
+//! - `ServiceDomain`, the launchd domain the job belongs to.
+//! - `ServiceOutcome`, the result of an unload attempt.
+//! - `ServiceControl`, the unload abstraction.
+//!
+//! Two implementations are provided:
+//!
+//! - `LaunchctlServiceControl` calls the `launchctl` utility.
+//! - `RecordingServiceControl` records requests without touching
+//!   the system, for tests and dry runs.
+//!
+//! Note:
+//! Jobs in the system domain can only be unloaded by root. They
+//! are reported as requiring administrator rights instead of
+//! being attempted.
+//!..
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use mini_logger::debug;
+
+use crate::errors::ErrorKind;
+use crate::path_data::PathData;
+use crate::syscom;
+use crate::utility::LaunchdPlist;
+
+/// launchd domain of a job.
+///
+/// Doc:
+/// The domain is derived from the directory that holds the job
+/// definition:
+///
+/// - `LaunchAgents` jobs run in the per-user `gui/<uid>` domain.
+/// - `LaunchDaemons` jobs run in the `system` domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceDomain {
+    Gui,
+    System,
+}
+
+impl ServiceDomain {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Gui => "User agent",
+            Self::System => "System daemon",
+        }
+    }
+
+    /// Returns the `launchctl` domain target for the given user.
+    pub fn target(&self, uid: u32) -> String {
+        match self {
+            Self::Gui => format!("gui/{}", uid),
+            Self::System => "system".to_string(),
+        }
+    }
+}
+
+/// launchd job candidate.
+///
+/// Doc:
+/// Stores what is required to unload a job:
+///
+/// - The job definition path.
+/// - The job label.
+/// - The launchd domain.
+///
+/// Note:
+/// The label is read from the plist. When the plist cannot be
+/// parsed, the file stem is used since it follows the label by
+/// convention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceJob {
+    plist: PathData,
+    label: String,
+    domain: ServiceDomain,
+}
+
+impl ServiceJob {
+    pub fn new(plist: PathData, label: String, domain: ServiceDomain) -> Self {
+        Self {
+            plist,
+            label,
+            domain,
+        }
+    }
+
+    /// Constructs a job from a launchd plist path.
+    ///
+    /// Doc:
+    /// Returns `None` when the path is not a `.plist` file stored
+    /// directly inside a `LaunchAgents` or `LaunchDaemons`
+    /// directory. Background task files such as BTM databases are
+    /// therefore ignored.
+    pub fn from_path(plist: &PathData) -> Option<Self> {
+        let path = plist.as_path();
+
+        if path.extension().map(|ext| ext != "plist").unwrap_or(true) {
+            return None;
+        }
+
+        let domain = match path.parent()?.file_name()?.to_str()? {
+            "LaunchAgents" => ServiceDomain::Gui,
+            "LaunchDaemons" => ServiceDomain::System,
+            _ => return None,
+        };
+
+        let label = match LaunchdPlist::from_path(path) {
+            Ok(job) => job.as_label().to_string(),
+            Err(err) => {
+                debug!("Using file name as launchd label: {}", err);
+                path.file_stem()?.to_string_lossy().into_owned()
+            }
+        };
+
+        Some(Self::new(plist.clone(), label, domain))
+    }
+
+    /// get job definition path reference
+    pub fn as_plist(&self) -> &PathData {
+        &self.plist
+    }
+
+    /// get job label reference
+    pub fn as_label(&self) -> &str {
+        &self.label
+    }
+
+    /// get the copy of the job domain
+    pub fn domain(&self) -> ServiceDomain {
+        self.domain
+    }
+}
+
+/// Result of an unload attempt.
+///
+/// Doc:
+/// - `Unloaded`: the job was loaded and has been removed.
+/// - `NotLoaded`: launchd did not know the job.
+/// - `RequiresAdmin`: unloading needs administrator rights.
+/// - `Failed`: the attempt failed for another reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceOutcome {
+    Unloaded,
+    NotLoaded,
+    RequiresAdmin,
+    Failed(ErrorKind),
+}
+
+impl ServiceOutcome {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unloaded => "Unloaded",
+            Self::NotLoaded => "Not loaded",
+            Self::RequiresAdmin => "Requires administrator rights",
+            Self::Failed(_) => "Failed",
+        }
+    }
+}
+
+/// launchd job unloading abstraction.
+///
+/// Doc:
+/// Implementations stop a single job and report the outcome.
+///
+/// Design:
+/// `Cleaner` depends on this trait instead of calling
+/// `launchctl` directly, so the unload step can be exercised
+/// without modifying the launchd state of the machine running
+/// the tests.
+pub trait ServiceControl: Send + Sync {
+    fn unload(&self, job: &ServiceJob) -> ServiceOutcome;
+}
+
+/// `launchctl` based service control.
+///
+/// Doc:
+/// Unloads jobs with:
+///
+/// ```text
+/// launchctl bootout <domain>/<label>
+/// ```
+///
+/// and falls back to the legacy form when `bootout` fails for
+/// an unrecognized reason:
+///
+/// ```text
+/// launchctl unload <plist>
+/// ```
+///
+/// Note:
+/// System domain jobs are not attempted unless the process runs
+/// as root.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LaunchctlServiceControl;
+
+impl LaunchctlServiceControl {
+    /// `launchctl` exit codes reporting an unknown service.
+    const NOT_LOADED_CODES: [i32; 2] = [3, 113];
+
+    /// `launchctl` exit codes reporting a permission problem.
+    ///
+    /// Note:
+    /// Exit code 1 is a generic failure and is not listed here.
+    /// Permission problems reported with it are recognized by
+    /// the stderr text instead.
+    const PERMISSION_CODES: [i32; 1] = [150];
+
+    /// stderr fragments reporting a permission problem.
+    const PERMISSION_MESSAGES: [&str; 3] = ["not permitted", "permission denied", "requires root"];
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn is_permission_error(code: i32, stderr: &str) -> bool {
+        let stderr = stderr.to_lowercase();
+
+        Self::PERMISSION_CODES.contains(&code)
+            || Self::PERMISSION_MESSAGES
+                .iter()
+                .any(|message| stderr.contains(message))
+    }
+
+    fn legacy_unload(job: &ServiceJob, bootout_error: String) -> ServiceOutcome {
+        let plist = job.as_plist().as_path().to_string_lossy().into_owned();
+
+        match syscom::run_launchctl_command(&["unload", &plist]) {
+            Ok((Some(0), stderr)) => {
+                if !stderr.is_empty() {
+                    debug!("launchctl unload {} warned: {}", plist, stderr);
+                }
+                ServiceOutcome::Unloaded
+            }
+            Ok((_, stderr)) => ServiceOutcome::Failed(
+                ErrorKind::failed()
+                    .with_summary("Failed to unload launchd job")
+                    .with_reason(format!("{}: {}; {}", job.as_label(), bootout_error, stderr)),
+            ),
+            Err(err) => ServiceOutcome::Failed(err),
+        }
+    }
+}
+
+impl ServiceControl for LaunchctlServiceControl {
+    fn unload(&self, job: &ServiceJob) -> ServiceOutcome {
+        if job.domain() == ServiceDomain::System && !syscom::is_privileged() {
+            return ServiceOutcome::RequiresAdmin;
+        }
+
+        let service_target = format!(
+            "{}/{}",
+            job.domain().target(syscom::current_uid()),
+            job.as_label()
+        );
+
+        let (code, stderr) = match syscom::run_launchctl_command(&["bootout", &service_target]) {
+            Ok(output) => output,
+            Err(err) => return ServiceOutcome::Failed(err),
+        };
+
+        debug!(
+            "launchctl bootout {} exited with {:?}: {}",
+            service_target, code, stderr
+        );
+
+        match code {
+            Some(0) => ServiceOutcome::Unloaded,
+            Some(code) if Self::NOT_LOADED_CODES.contains(&code) => ServiceOutcome::NotLoaded,
+            Some(code) if Self::is_permission_error(code, &stderr) => ServiceOutcome::RequiresAdmin,
+            _ => Self::legacy_unload(job, stderr),
+        }
+    }
+}
+
+/// Recording service control.
+///
+/// Doc:
+/// Records every unload request and answers with a configured
+/// outcome. Jobs without a configured outcome are reported as
+/// `Unloaded`.
+///
+/// Design:
+/// Intended for tests and previews. The launchd state of the
+/// system is never modified.
+#[derive(Debug, Default)]
+pub struct RecordingServiceControl {
+    outcomes: HashMap<String, ServiceOutcome>,
+    requests: Mutex<Vec<ServiceJob>>,
+}
+
+impl RecordingServiceControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configures the outcome returned for a job label.
+    pub fn with_outcome(mut self, label: impl Into<String>, outcome: ServiceOutcome) -> Self {
+        self.outcomes.insert(label.into(), outcome);
+        self
+    }
+
+    /// Returns the recorded unload requests in call order.
+    pub fn requests(&self) -> Vec<ServiceJob> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl ServiceControl for RecordingServiceControl {
+    fn unload(&self, job: &ServiceJob) -> ServiceOutcome {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(job.clone());
+        }
+
+        self.outcomes
+            .get(job.as_label())
+            .cloned()
+            .unwrap_or(ServiceOutcome::Unloaded)
+    }
+}
+
+/// Unloads every launchd job defined by the provided paths.
+///
+/// Doc:
+/// Paths that are not launchd job definitions are skipped.
+/// Returns the jobs together with their outcomes.
+pub(crate) fn unload_jobs<C>(control: &C, paths: &[PathData]) -> Vec<(ServiceJob, ServiceOutcome)>
+where
+    C: ServiceControl + ?Sized,
+{
+    paths
+        .iter()
+        .filter_map(ServiceJob::from_path)
+        .map(|job| {
+            let outcome = control.unload(&job);
+            debug!("launchd job {}: {}", job.as_label(), outcome.as_str());
+            (job, outcome)
+        })
+        .collect()
+}
@@ -32,7 +32,8 @@
 //! 4. Locate associated files.
 //! 5. Locate BTM (Background Task Management) files.
 //! 6. Optionally terminate running processes.
-//! 7. Move discovered files to Trash.
+//! 7. Unload launchd jobs defined by discovered background task files.
+//! 8. Move discovered files to Trash.
 //!
 //! The crate separates application state, orchestration, and
 //! platform-specific operations:
@@ -40,6 +41,12 @@
 //! - `AppProfile` owns discovered application data.
 //! - `Cleaner` coordinates scanning, process handling, and cleanup.
 //! - `syscom` provides macOS system command integration.
+//! - `macho` reads executables for signing and entitlement data.
+//! - `thin` removes unused architectures from universal binaries.
+//! - `report` exports scan results as JSON, CSV, HTML and Markdown,
+//!   and the planned removal as a shell script.
+//! - `ControlEntry` represents process termination and launchd
+//!   unload results.
 //! - `TrashEntry` represents trash operation results.
 //!
 //! Application files are discovered from multiple sources:
@@ -68,25 +75,48 @@
 mod app_profile;
 mod syscom;
 
+pub mod control;
 pub mod errors;
+pub mod macho;
 pub mod path_data;
+pub mod report;
+pub mod thin;
 pub mod utility;
 
 pub use app_profile::AppProfile;
+pub use app_profile::DocumentType;
+pub use app_profile::Localization;
 pub use app_profile::Metadata;
-pub use app_profile::PathEntry;
-pub use app_profile::ProcessEntry;
+pub use app_profile::{BundleSection, SizeBreakdown};
+pub use app_profile::{MatchConfidence, MatchReason, ProcessData, ProcessEntry};
+pub use app_profile::{NestedBundle, NestedBundleKind};
+pub use app_profile::{PathCategory, PathEntry};
+pub use app_profile::{ProcessInfo, ProcessSource, StaticProcessSource, SysinfoProcessSource};
 pub use errors::{ErrorKind, Result};
+pub use syscom::{get_xattr, set_xattr};
+pub use utility::FileOrigin;
 pub use utility::IconCache;
-
+pub use utility::{FileSystem, MemoryFileSystem, RealFileSystem};
+pub use utility::{IndexedFileSystem, ScanIndex};
+pub use utility::{RuleConfig, RuleKind};
+
+use control::{
+    ControlEntry, LaunchctlServiceControl, ProcessControl, ServiceControl, SystemProcessControl,
+    TerminationOutcome, TerminationPolicy,
+};
 use path_data::PathData;
 use path_data::trash_entry::TrashEntry;
+use report::ScanReport;
+use thin::{BinaryStore, ThinReport, TrashBinaryStore};
 
 use mini_logger::debug;
 use rayon::prelude::*;
 use std::borrow::Cow;
 use std::path::Path;
 
+/// Icon size, in points, embedded into HTML reports.
+const REPORT_ICON_SIZE: f64 = 128.0;
+
 /// Application cleanup coordinator.
 ///
 /// Doc:
@@ -102,11 +132,16 @@ use std::path::Path;
 /// - Application profile creation.
 /// - Process discovery.
 /// - Process termination.
+/// - launchd job unloading.
 /// - Receipt scanning.
 /// - Associated file discovery.
 /// - BTM file discovery.
 /// - BOM log export.
+/// - JSON, CSV, HTML and Markdown report export.
+/// - Removal shell script export.
 /// - Moving discovered files to Trash.
+/// - Thinning universal binaries.
+/// - Removing unused localizations.
 ///
 /// Typical workflow:
 ///
@@ -123,6 +158,9 @@ use std::path::Path;
 /// scan_app_profile()
 ///       │
 ///       ├─ save_bom_logs()
+///       ├─ export_json() / export_csv()
+///       ├─ export_html() / export_markdown()
+///       ├─ export_shell_script()
 ///       ├─ move_to_trash()
 ///       └─ reset()
 /// ```
@@ -131,17 +169,33 @@ use std::path::Path;
 /// `Cleaner` acts as an orchestration layer. Platform-specific
 /// operations are delegated to `syscom`, while discovered state and
 /// cleanup results are stored internally.
+///
+/// launchd jobs are unloaded by `move_to_trash()` unless
+/// `set_skip_service_unload(true)` was called.
+///
+/// Processes matched by name or command line only are terminated
+/// by `kill_app_process()` only after `confirm_processes()`.
 #[derive(Debug, Default, Clone)]
 pub struct Cleaner {
     app_profile: AppProfile,
+    control_entry: ControlEntry,
     trash_entry: TrashEntry,
+    thin_entry: TrashEntry,
+    slim_entry: TrashEntry,
+    skip_service_unload: bool,
+    confirmed_processes: Vec<i32>,
 }
 
 impl Cleaner {
     pub fn new(app_profile: AppProfile) -> Self {
         Self {
             app_profile,
+            control_entry: ControlEntry::default(),
             trash_entry: TrashEntry::default(),
+            thin_entry: TrashEntry::default(),
+            slim_entry: TrashEntry::default(),
+            skip_service_unload: false,
+            confirmed_processes: Vec::new(),
         }
     }
 
@@ -149,6 +203,52 @@ impl Cleaner {
         &self.app_profile
     }
 
+    pub fn as_control_entry(&self) -> &ControlEntry {
+        &self.control_entry
+    }
+
+    pub fn skip_service_unload(&self) -> bool {
+        self.skip_service_unload
+    }
+
+    /// Enables or disables launchd job unloading during
+    /// `move_to_trash()`.
+    pub fn set_skip_service_unload(&mut self, skip: bool) {
+        self.skip_service_unload = skip;
+    }
+
+    pub fn as_confirmed_processes(&self) -> &[i32] {
+        &self.confirmed_processes
+    }
+
+    /// Confirms the termination of low confidence processes.
+    ///
+    /// Processes that require confirmation, see
+    /// `ProcessData::requires_confirmation()`, are skipped by
+    /// `kill_app_process()` unless their PID was confirmed. The
+    /// confirmation is cleared by the next process discovery.
+    pub fn confirm_processes(&mut self, pids: impl IntoIterator<Item = i32>) {
+        self.confirmed_processes.extend(pids);
+    }
+
+    pub fn match_segments(&self) -> bool {
+        self.app_profile.as_path_entry().match_segments()
+    }
+
+    /// Enables or disables path-segment matching for general
+    /// associated files, proposing owning vendor directories as
+    /// removal targets.
+    pub fn set_match_segments(&mut self, enabled: bool) {
+        self.app_profile.set_match_segments(enabled);
+    }
+
+    /// Replaces the user matching rules used by later scans.
+    ///
+    /// Returns an error if a glob or regex rule is invalid.
+    pub fn set_rule_config(&mut self, config: RuleConfig) -> Result<()> {
+        self.app_profile.set_rule_config(config)
+    }
+
     pub fn as_trash_entry(&self) -> &TrashEntry {
         &self.trash_entry
     }
@@ -157,11 +257,30 @@ impl Cleaner {
         &mut self.trash_entry
     }
 
+    /// Returns the binaries replaced by the last `thin_app()`.
+    pub fn as_thin_entry(&self) -> &TrashEntry {
+        &self.thin_entry
+    }
+
+    /// Returns the localizations moved by the last `slim_app()`.
+    pub fn as_slim_entry(&self) -> &TrashEntry {
+        &self.slim_entry
+    }
+
     pub fn new_profile<F>(path: &Path, progress: Option<F>) -> Result<Self>
     where
         F: Fn(Cow<'static, str>) + Send + Sync + Clone,
     {
-        let app_profile = AppProfile::from_path(path)?;
+        Self::new_profile_with(&RealFileSystem::new(), path, progress)
+    }
+
+    /// Build a profile reading the bundle through the provided `FileSystem`.
+    pub fn new_profile_with<FS, F>(fs: &FS, path: &Path, progress: Option<F>) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+        F: Fn(Cow<'static, str>) + Send + Sync + Clone,
+    {
+        let app_profile = AppProfile::from_path_with(fs, path)?;
 
         if let Some(ref progress_hook) = progress {
             let app_name = app_profile.as_metadata().as_name();
@@ -175,7 +294,17 @@ impl Cleaner {
     where
         F: Fn(Cow<'static, str>) + Send + Sync + Clone,
     {
-        self.app_profile.find_pid_and_command();
+        self.find_app_process_with(&SysinfoProcessSource::new(), progress)
+    }
+
+    /// Discover application processes from the provided `ProcessSource`.
+    pub fn find_app_process_with<S, F>(&mut self, source: &S, progress: Option<F>) -> Result<&Self>
+    where
+        S: ProcessSource + ?Sized,
+        F: Fn(Cow<'static, str>) + Send + Sync + Clone,
+    {
+        self.app_profile.find_pid_and_command(source);
+        self.confirmed_processes.clear();
 
         if let Some(ref progress_hook) = progress {
             let process_count = self.app_profile.as_process_entry().list().len();
@@ -185,8 +314,41 @@ impl Cleaner {
         Ok(self)
     }
 
-    pub fn kill_app_process<F>(&self, progress: Option<F>) -> Result<()>
+    /// Terminate discovered application processes.
+    ///
+    /// Uses the default `TerminationPolicy`: `SIGTERM`, a grace
+    /// period, then `SIGKILL`. Every outcome is recorded in the
+    /// `ControlEntry`.
+    ///
+    /// Low confidence processes are left running unless confirmed
+    /// with `confirm_processes()`, and recorded as
+    /// `TerminationOutcome::Unconfirmed`.
+    pub fn kill_app_process<F>(&mut self, progress: Option<F>) -> Result<()>
+    where
+        F: Fn(usize, usize) + Send + Sync + Clone,
+    {
+        self.kill_app_process_with(
+            &SystemProcessControl::new(),
+            &TerminationPolicy::default(),
+            progress,
+        )
+    }
+
+    /// Terminate discovered application processes through the
+    /// provided `ProcessControl` using the provided
+    /// `TerminationPolicy`.
+    ///
+    /// Returns an error listing the processes that are still
+    /// running, including processes owned by other users.
+    /// Unconfirmed processes are not reported as errors.
+    pub fn kill_app_process_with<C, F>(
+        &mut self,
+        control: &C,
+        policy: &TerminationPolicy,
+        progress: Option<F>,
+    ) -> Result<()>
     where
+        C: ProcessControl + ?Sized,
         F: Fn(usize, usize) + Send + Sync + Clone,
     {
         let processes = self.app_profile.as_process_entry();
@@ -195,37 +357,44 @@ impl Cleaner {
             return Ok(());
         }
 
-        let total = processes.list().len();
-        let mut errors = Vec::new();
-        let mut killed_count = 0;
-
-        for (current, p) in processes.list().iter().enumerate() {
-            match syscom::kill_pid(p.pid()) {
-                Ok(_) => {
-                    killed_count += 1;
-                }
+        let results = control::terminate_processes(
+            processes.list(),
+            &self.confirmed_processes,
+            policy,
+            control,
+            progress,
+        );
+
+        let total = results
+            .iter()
+            .filter(|(_, outcome)| *outcome != TerminationOutcome::Unconfirmed)
+            .count();
+
+        let not_stopped: Vec<String> = results
+            .iter()
+            .filter(|(_, outcome)| {
+                !outcome.is_stopped() && *outcome != TerminationOutcome::Unconfirmed
+            })
+            .map(|(process, outcome)| {
+                let reason = match outcome {
+                    TerminationOutcome::Failed(err) => err.reason().unwrap_or("Unknown"),
+                    _ => outcome.as_str(),
+                };
 
-                Err(err) => {
-                    errors.push(err);
-                }
-            }
+                format!("PID {} ({}): {}", process.pid(), process.as_name(), reason)
+            })
+            .collect();
 
-            if let Some(ref progress_hook) = progress {
-                progress_hook(current + 1, total);
-            }
-        }
+        self.control_entry.extend_processes(results);
 
-        if !errors.is_empty() {
+        if !not_stopped.is_empty() {
             return Err(ErrorKind::failed()
-                .with_summary(format!("Killed {}/{} processes", killed_count, total))
-                .with_reason(
-                    errors
-                        .iter()
-                        .filter_map(|e| e.reason())
-                        .map(str::to_owned)
-                        .collect::<Vec<_>>()
-                        .join("\n"),
-                ));
+                .with_summary(format!(
+                    "Stopped {}/{} processes",
+                    total - not_stopped.len(),
+                    total
+                ))
+                .with_reason(not_stopped.join("\n")));
         }
 
         Ok(())
@@ -236,7 +405,38 @@ impl Cleaner {
     where
         F: Fn(usize, &Path) + Send + Sync + Clone,
     {
-        self.app_profile.find_path_entry(progress);
+        self.scan_app_profile_with(&RealFileSystem::new(), progress)
+    }
+
+    /// Discover associated paths using a `ScanIndex`.
+    ///
+    /// The index is pointed at the scan roots, refreshed
+    /// incrementally, then queried instead of walking the disk.
+    /// Callers keep the index between scans, and may persist it
+    /// with `ScanIndex::save`.
+    pub fn scan_app_profile_indexed<F>(
+        &mut self,
+        index: &mut ScanIndex,
+        progress: F,
+    ) -> Result<&Self>
+    where
+        F: Fn(usize, &Path) + Send + Sync + Clone,
+    {
+        let fs = RealFileSystem::new();
+
+        index.set_roots(PathEntry::scan_roots());
+        index.refresh(&fs);
+
+        self.scan_app_profile_with(&IndexedFileSystem::new(index, &fs), progress)
+    }
+
+    /// Discover associated paths through the provided `FileSystem`.
+    pub fn scan_app_profile_with<FS, F>(&mut self, fs: &FS, progress: F) -> Result<&Self>
+    where
+        FS: FileSystem + ?Sized,
+        F: Fn(usize, &Path) + Send + Sync + Clone,
+    {
+        self.app_profile.find_path_entry_with(fs, progress);
 
         Ok(self)
     }
@@ -278,6 +478,61 @@ impl Cleaner {
         results.into_iter().collect::<Result<()>>()
     }
 
+    /// Returns a snapshot of the scan result and the last trash
+    /// operation.
+    pub fn scan_report(&self) -> ScanReport {
+        self.scan_report_with(&RealFileSystem::new())
+    }
+
+    /// Returns a snapshot of the scan result, reading path sizes
+    /// through the provided `FileSystem`.
+    pub fn scan_report_with<FS>(&self, fs: &FS) -> ScanReport
+    where
+        FS: FileSystem + ?Sized,
+    {
+        ScanReport::new_with(fs, &self.app_profile, &self.trash_entry)
+    }
+
+    /// Export the scan result as versioned JSON into the given file.
+    pub fn export_json(&self, file: &Path) -> Result<()> {
+        self.scan_report().save_json(file)
+    }
+
+    /// Export the scan result as CSV into the given file.
+    pub fn export_csv(&self, file: &Path) -> Result<()> {
+        self.scan_report().save_csv(file)
+    }
+
+    /// Export a self-contained HTML report, application icon
+    /// included, into the given file.
+    pub fn export_html(&self, file: &Path) -> Result<()> {
+        let report = self.scan_report();
+        let bundle_path = self.app_profile.as_metadata().as_bundle_path();
+
+        let report = match IconCache::new(bundle_path, REPORT_ICON_SIZE) {
+            Some(icon_cache) => report.with_icon(&icon_cache),
+            None => report,
+        };
+
+        report.save_html(file)
+    }
+
+    /// Export a Markdown report into the given file.
+    pub fn export_markdown(&self, file: &Path) -> Result<()> {
+        self.scan_report().save_markdown(file)
+    }
+
+    /// Export a shell script reproducing `move_to_trash()` into
+    /// the given file.
+    ///
+    /// The script stops the discovered processes, unloads launchd
+    /// jobs unless skipped, and moves every path to a quarantine
+    /// folder. Run it with `--dry-run` to print the commands only.
+    pub fn export_shell_script(&self, file: &Path) -> Result<()> {
+        self.scan_report()
+            .save_shell_script(file, !self.skip_service_unload)
+    }
+
     /// Returns all discovered application paths with their index.
     pub fn all_entries_enumerate(&self) -> Vec<(usize, PathData)> {
         self.app_profile
@@ -288,36 +543,57 @@ impl Cleaner {
             .collect()
     }
 
+    /// Unload launchd jobs defined by discovered background task files.
+    ///
+    /// Uses `launchctl`. Outcomes, including jobs that require
+    /// administrator rights, are recorded in the `ControlEntry`.
+    pub fn unload_background_tasks(&mut self) -> Result<&Self> {
+        self.unload_background_tasks_with(&LaunchctlServiceControl::new())
+    }
+
+    /// Unload launchd jobs using the provided `ServiceControl`.
+    pub fn unload_background_tasks_with<C>(&mut self, control: &C) -> Result<&Self>
+    where
+        C: ServiceControl + ?Sized,
+    {
+        let background_task_files = self.app_profile.as_path_entry().as_background_task_files();
+        let results = control::unload_jobs(control, background_task_files);
+
+        self.control_entry.extend_services(results);
+
+        Ok(self)
+    }
+
     /// Move discovered application paths to Trash.
     ///
-    /// Associated paths are moved first. The application bundle
-    /// is moved only when all associated paths were successfully
-    /// moved.
+    /// launchd jobs are unloaded first unless skipped. Associated
+    /// paths are moved next. The application bundle is moved only
+    /// when all associated paths were successfully moved.
     pub fn move_to_trash(&mut self) -> Result<&Self> {
+        self.move_to_trash_with(&LaunchctlServiceControl::new())
+    }
+
+    /// Move discovered application paths to Trash using the
+    /// provided `ServiceControl` for launchd jobs.
+    pub fn move_to_trash_with<C>(&mut self, control: &C) -> Result<&Self>
+    where
+        C: ServiceControl + ?Sized,
+    {
+        if !self.skip_service_unload {
+            self.unload_background_tasks_with(control)?;
+        }
+
         let path_entry = self.app_profile.as_path_entry();
 
         let mut moved = Vec::new();
         let mut failed = Vec::new();
 
         // Associated paths
-        let general_associated_trash =
-            TrashEntry::moved_path_to_trash(path_entry.as_general_associated_files())?;
-        moved.extend(general_associated_trash.moved_path().iter().cloned());
-        failed.extend(general_associated_trash.failed_path().iter().cloned());
-
-        let sandbox_container_trash =
-            TrashEntry::moved_path_to_trash(path_entry.as_sandbox_container())?;
-        moved.extend(sandbox_container_trash.moved_path().iter().cloned());
-        failed.extend(sandbox_container_trash.failed_path().iter().cloned());
-
-        let background_task_trash =
-            TrashEntry::moved_path_to_trash(path_entry.as_background_task_files())?;
-        moved.extend(background_task_trash.moved_path().iter().cloned());
-        failed.extend(background_task_trash.failed_path().iter().cloned());
-
-        let bom_trash = TrashEntry::moved_path_to_trash(path_entry.as_bom_files())?;
-        moved.extend(bom_trash.moved_path().iter().cloned());
-        failed.extend(bom_trash.failed_path().iter().cloned());
+        for category in PathCategory::REMOVAL_ORDER {
+            let category_trash = TrashEntry::moved_path_to_trash(&path_entry.paths_in(category))?;
+            moved.extend(category_trash.moved_path().iter().cloned());
+            failed.extend(category_trash.failed_path().iter().cloned());
+        }
 
         // App bundle only if associated succeeded
         match failed.is_empty() {
@@ -352,6 +628,76 @@ impl Cleaner {
         Ok(self)
     }
 
+    /// Scan the application bundle for universal binaries.
+    pub fn thin_report(&self) -> Result<ThinReport> {
+        self.thin_report_with(&RealFileSystem::new())
+    }
+
+    /// Scan the application bundle for universal binaries through
+    /// the provided `FileSystem`.
+    pub fn thin_report_with<FS>(&self, fs: &FS) -> Result<ThinReport>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        ThinReport::scan_with(fs, self.app_profile.as_metadata().as_bundle_path())
+    }
+
+    /// Remove every architecture but `arch` from the application's
+    /// universal binaries.
+    ///
+    /// Originals are moved to Trash and recorded in the thin
+    /// entry, leaving the uninstall `TrashEntry` untouched.
+    /// Signed bundles are refused unless `force` is set.
+    pub fn thin_app(&mut self, arch: &str, force: bool) -> Result<&Self> {
+        self.thin_app_with(
+            &RealFileSystem::new(),
+            &TrashBinaryStore::new(),
+            arch,
+            force,
+        )
+    }
+
+    /// Thin the application's universal binaries using the
+    /// provided `FileSystem` and `BinaryStore`.
+    pub fn thin_app_with<FS, S>(
+        &mut self,
+        fs: &FS,
+        store: &S,
+        arch: &str,
+        force: bool,
+    ) -> Result<&Self>
+    where
+        FS: FileSystem + ?Sized,
+        S: BinaryStore + ?Sized,
+    {
+        let report = self.thin_report_with(fs)?;
+        self.thin_entry = thin::thin_bundle_with(fs, store, &report, arch, force)?;
+
+        Ok(self)
+    }
+
+    /// Move the application's localizations outside the `keep`
+    /// languages to Trash.
+    ///
+    /// The result is recorded in the slim entry, leaving the
+    /// uninstall `TrashEntry` untouched. See
+    /// `AppProfile::remove_localizations()`.
+    pub fn slim_app(&mut self, keep: &[String]) -> Result<&Self> {
+        self.slim_app_with(&RealFileSystem::new(), keep)
+    }
+
+    /// Move the application's localizations outside the `keep`
+    /// languages, found through the provided `FileSystem`, to
+    /// Trash.
+    pub fn slim_app_with<FS>(&mut self, fs: &FS, keep: &[String]) -> Result<&Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        self.slim_entry = self.app_profile.remove_localizations_with(fs, keep)?;
+
+        Ok(self)
+    }
+
     // pub fn restore_moved_path(&self) -> Result<()> {
     //     Ok(println!("to do"))
     // }
@@ -362,5 +708,7 @@ impl Cleaner {
 
     pub fn reset(&mut self) {
         self.app_profile.reset();
+        self.control_entry = ControlEntry::default();
+        self.confirmed_processes.clear();
     }
 }
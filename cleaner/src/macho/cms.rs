@@ -0,0 +1,321 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Certificate subjects from CMS signatures.
+//!
+//! The signature slot of a code signature holds a CMS
+//! `SignedData` structure. Only the path to the embedded
+//! certificates is followed:
+//!
+//! ```text
+//! ContentInfo
+//!   └─ [0] SignedData
+//!        └─ [0] certificates
+//!             └─ Certificate
+//!                  └─ tbsCertificate
+//!                       ├─ issuer
+//!                       └─ subject
+//! ```
+//!
+//! Design:
+//! `codesign` writes BER with indefinite lengths around the
+//! certificates, while the certificates themselves are DER, so
+//! both length forms are accepted.
+//!
+//! Note:
+//! Signatures are not verified. The subjects describe who the
+//! signature claims to come from.
+//!..
+
+use std::fmt;
+
+use crate::errors::Result;
+use crate::macho::mach_file::invalid;
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_CONTEXT_0: u8 = 0xa0;
+const TAG_BMP_STRING: u8 = 0x1e;
+
+/// `1.2.840.113549.1.7.2`, PKCS #7 signed data.
+const OID_SIGNED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+const OID_ORGANIZATION: &[u8] = &[0x55, 0x04, 0x0a];
+const OID_ORGANIZATIONAL_UNIT: &[u8] = &[0x55, 0x04, 0x0b];
+
+/// Upper bound of nested elements, which stops malicious input
+/// from exhausting the stack.
+const MAX_DEPTH: usize = 16;
+
+/// A BER element.
+#[derive(Debug, Clone, Copy)]
+struct Element<'a> {
+    tag: u8,
+    content: &'a [u8],
+    /// Raw bytes, header included.
+    raw: &'a [u8],
+}
+
+impl<'a> Element<'a> {
+    fn parse(data: &'a [u8], depth: usize) -> Result<Self> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("signature nests too deeply"));
+        }
+
+        let tag = *data.first().ok_or_else(|| invalid("truncated signature"))?;
+        if tag & 0x1f == 0x1f {
+            return Err(invalid("unsupported signature tag"));
+        }
+
+        let first = *data.get(1).ok_or_else(|| invalid("truncated signature"))?;
+
+        // Indefinite length: the content runs until an
+        // end-of-contents marker after the last child.
+        if first == 0x80 {
+            let mut cursor = 2;
+            while data.get(cursor..cursor + 2) != Some(&[0, 0]) {
+                cursor += Self::parse(&data[cursor..], depth + 1)?.raw.len();
+            }
+
+            return Ok(Self {
+                tag,
+                content: &data[2..cursor],
+                raw: &data[..cursor + 2],
+            });
+        }
+
+        let (header, len) = if first & 0x80 == 0 {
+            (2, first as usize)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count > 4 {
+                return Err(invalid("signature element is too long"));
+            }
+
+            let bytes = data
+                .get(2..2 + count)
+                .ok_or_else(|| invalid("truncated signature"))?;
+            let len = bytes
+                .iter()
+                .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+
+            (2 + count, len)
+        };
+
+        let raw = header
+            .checked_add(len)
+            .and_then(|end| data.get(..end))
+            .ok_or_else(|| invalid("truncated signature"))?;
+
+        Ok(Self {
+            tag,
+            content: &raw[header..],
+            raw,
+        })
+    }
+
+    fn children(&self, depth: usize) -> Result<Vec<Element<'a>>> {
+        let mut children = Vec::new();
+        let mut rest = self.content;
+
+        while !rest.is_empty() {
+            let child = Self::parse(rest, depth + 1)?;
+            rest = &rest[child.raw.len()..];
+            children.push(child);
+        }
+
+        Ok(children)
+    }
+
+    fn expect(self, tag: u8) -> Result<Self> {
+        if self.tag != tag {
+            return Err(invalid(format!(
+                "expected signature tag {:#04x}, found {:#04x}",
+                tag, self.tag
+            )));
+        }
+
+        Ok(self)
+    }
+}
+
+/// Subject of a signing certificate.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CertificateSubject {
+    common_name: Option<String>,
+    organization: Option<String>,
+    organizational_unit: Option<String>,
+}
+
+impl CertificateSubject {
+    /// get common name reference
+    pub fn as_common_name(&self) -> Option<&str> {
+        self.common_name.as_deref()
+    }
+
+    /// get organization reference
+    pub fn as_organization(&self) -> Option<&str> {
+        self.organization.as_deref()
+    }
+
+    /// get organizational unit reference
+    ///
+    /// Note:
+    /// Apple developer certificates store the team identifier
+    /// here.
+    pub fn as_organizational_unit(&self) -> Option<&str> {
+        self.organizational_unit.as_deref()
+    }
+
+    fn from_name(name: Element<'_>, depth: usize) -> Result<Self> {
+        let mut subject = Self::default();
+
+        for set in name.children(depth)? {
+            for attribute in set.children(depth + 1)? {
+                let [oid, value] = attribute.children(depth + 2)?[..] else {
+                    continue;
+                };
+
+                let field = match oid.content {
+                    OID_COMMON_NAME => &mut subject.common_name,
+                    OID_ORGANIZATION => &mut subject.organization,
+                    OID_ORGANIZATIONAL_UNIT => &mut subject.organizational_unit,
+                    _ => continue,
+                };
+
+                field.get_or_insert_with(|| decode_string(value));
+            }
+        }
+
+        Ok(subject)
+    }
+}
+
+impl fmt::Display for CertificateSubject {
+    /// Prints the common name, as `codesign` lists authorities,
+    /// or the organization when there is none.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self
+            .common_name
+            .as_deref()
+            .or(self.organization.as_deref())
+            .or(self.organizational_unit.as_deref())
+            .unwrap_or("Unknown");
+
+        write!(f, "{}", name)
+    }
+}
+
+fn decode_string(value: Element<'_>) -> String {
+    if value.tag == TAG_BMP_STRING {
+        let units: Vec<u16> = value
+            .content
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+
+    String::from_utf8_lossy(value.content).into_owned()
+}
+
+/// Extracts the certificate subjects of a CMS signature.
+///
+/// Doc:
+/// Subjects are ordered from the signing certificate up to the
+/// root, following issuer links, as `codesign` lists them.
+/// Certificates outside the chain are appended in stored order.
+///
+/// Returns an empty list when the signature embeds no
+/// certificates, and an error if it is not CMS signed data.
+pub fn certificate_subjects(cms: &[u8]) -> Result<Vec<CertificateSubject>> {
+    let content_info = Element::parse(cms, 0)?.expect(TAG_SEQUENCE)?;
+    let [content_type, content, ..] = content_info.children(0)?[..] else {
+        return Err(invalid("signature is not CMS content"));
+    };
+
+    if content_type.content != OID_SIGNED_DATA {
+        return Err(invalid("signature is not CMS signed data"));
+    }
+
+    let signed_data = content
+        .expect(TAG_CONTEXT_0)?
+        .children(1)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| invalid("signature has no signed data"))?
+        .expect(TAG_SEQUENCE)?;
+
+    let Some(certificates) = signed_data
+        .children(2)?
+        .into_iter()
+        .find(|element| element.tag == TAG_CONTEXT_0)
+    else {
+        return Ok(Vec::new());
+    };
+
+    // (issuer, subject raw name, subject)
+    let mut parsed = Vec::new();
+    for certificate in certificates.children(3)? {
+        let tbs = certificate
+            .expect(TAG_SEQUENCE)?
+            .children(4)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| invalid("certificate has no body"))?
+            .children(5)?;
+
+        // The version field is optional and tagged [0].
+        let skip = usize::from(tbs.first().is_some_and(|e| e.tag == TAG_CONTEXT_0));
+        let (Some(issuer), Some(subject)) = (tbs.get(skip + 2), tbs.get(skip + 4)) else {
+            return Err(invalid("certificate body is truncated"));
+        };
+
+        parsed.push((
+            issuer.raw,
+            subject.raw,
+            CertificateSubject::from_name(*subject, 6)?,
+        ));
+    }
+
+    Ok(order_chain(parsed))
+}
+
+/// Orders certificates leaf first by following issuer links.
+fn order_chain(
+    mut certificates: Vec<(&[u8], &[u8], CertificateSubject)>,
+) -> Vec<CertificateSubject> {
+    let is_issuer = |subject: &[u8], certificates: &[(&[u8], &[u8], CertificateSubject)]| {
+        certificates
+            .iter()
+            .any(|(issuer, own, _)| *issuer == subject && *own != subject)
+    };
+
+    let mut ordered = Vec::with_capacity(certificates.len());
+    let mut next = certificates
+        .iter()
+        .position(|(_, subject, _)| !is_issuer(subject, &certificates));
+
+    while let Some(index) = next {
+        let (issuer, subject, certificate) = certificates.remove(index);
+        ordered.push(certificate);
+
+        next = (issuer != subject)
+            .then(|| certificates.iter().position(|(_, own, _)| *own == issuer))
+            .flatten();
+    }
+
+    ordered.extend(certificates.into_iter().map(|(_, _, subject)| subject));
+    ordered
+}
@@ -0,0 +1,114 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! CodeDirectory parsing.
+//!
+//! The CodeDirectory is the signed core of a code signature. It
+//! names the signed code and carries its signing flags:
+//!
+//! ```text
+//! offset  field
+//!      8  version
+//!     12  flags
+//!     20  identOffset   -> signing identifier, NUL-terminated
+//!     48  teamOffset    -> team identifier (version >= 0x20200)
+//! ```
+//!
+//! Note:
+//! Page hashes are not read or verified. The parser reports what
+//! the signature claims, it does not validate it.
+//!..
+
+use crate::errors::Result;
+use crate::macho::mach_file::{ByteReader, invalid};
+
+const CSMAGIC_CODEDIRECTORY: u32 = 0xfade_0c02;
+
+/// First CodeDirectory version carrying a team identifier.
+const CS_SUPPORTSTEAMID: u32 = 0x20200;
+
+/// Ad-hoc signature, made without a signing identity.
+pub const CS_ADHOC: u32 = 0x0000_0002;
+
+/// Hardened runtime.
+pub const CS_RUNTIME: u32 = 0x0001_0000;
+
+/// Ad-hoc signature applied automatically by the linker.
+pub const CS_LINKER_SIGNED: u32 = 0x0002_0000;
+
+/// Parsed CodeDirectory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeDirectory {
+    version: u32,
+    flags: u32,
+    identifier: String,
+    team_identifier: Option<String>,
+}
+
+impl CodeDirectory {
+    /// Parses a CodeDirectory blob, header included.
+    ///
+    /// Returns an error if the magic is wrong or a string lies
+    /// outside the blob.
+    pub fn parse(blob: &[u8]) -> Result<Self> {
+        let reader = ByteReader::new(blob, false);
+
+        if reader.u32(0)? != CSMAGIC_CODEDIRECTORY {
+            return Err(invalid("blob is not a code directory"));
+        }
+
+        let version = reader.u32(8)?;
+        let flags = reader.u32(12)?;
+        let identifier = reader.c_str(reader.u32(20)? as usize)?;
+
+        let team_offset = if version >= CS_SUPPORTSTEAMID {
+            reader.u32(48)? as usize
+        } else {
+            0
+        };
+
+        let team_identifier = match team_offset {
+            0 => None,
+            offset => Some(reader.c_str(offset)?).filter(|team| !team.is_empty()),
+        };
+
+        Ok(Self {
+            version,
+            flags,
+            identifier,
+            team_identifier,
+        })
+    }
+
+    /// get the copy of CodeDirectory version
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// get the copy of signing flags
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// get signing identifier reference
+    pub fn as_identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// get team identifier reference
+    pub fn as_team_identifier(&self) -> Option<&str> {
+        self.team_identifier.as_deref()
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Code signature superblob parsing.
+//!
+//! The data referenced by `LC_CODE_SIGNATURE` is a big-endian
+//! superblob: a magic, a length, and an index of typed slots,
+//! each pointing at a blob inside the superblob:
+//!
+//! ```text
+//! SuperBlob 0xfade0cc0
+//!   ├─ slot 0        CodeDirectory  0xfade0c02
+//!   ├─ slot 5        Entitlements   0xfade7171
+//!   └─ slot 0x10000  CMS signature  0xfade0b01
+//! ```
+//!
+//! Note:
+//! Blobs are returned without interpretation. Callers decode the
+//! slots they need.
+//!..
+
+use crate::errors::Result;
+use crate::macho::mach_file::{ByteReader, invalid};
+
+const CSMAGIC_EMBEDDED_SIGNATURE: u32 = 0xfade_0cc0;
+const CSMAGIC_EMBEDDED_ENTITLEMENTS: u32 = 0xfade_7171;
+const CSMAGIC_BLOBWRAPPER: u32 = 0xfade_0b01;
+
+/// Slot of the CodeDirectory.
+pub const CSSLOT_CODEDIRECTORY: u32 = 0;
+
+/// Slot of the XML entitlements.
+pub const CSSLOT_ENTITLEMENTS: u32 = 5;
+
+/// Slot of the CMS signature.
+pub const CSSLOT_SIGNATURESLOT: u32 = 0x10000;
+
+/// Parsed code signature superblob.
+#[derive(Debug, Clone)]
+pub struct SuperBlob<'a> {
+    slots: Vec<(u32, &'a [u8])>,
+}
+
+impl<'a> SuperBlob<'a> {
+    /// Parses a code signature superblob.
+    ///
+    /// Returns an error if the magic is wrong or a slot points
+    /// outside the superblob.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let reader = ByteReader::new(data, false);
+
+        if reader.u32(0)? != CSMAGIC_EMBEDDED_SIGNATURE {
+            return Err(invalid("code signature is not an embedded signature"));
+        }
+
+        let length = (reader.u32(4)? as usize).min(data.len());
+        let reader = ByteReader::new(&data[..length], false);
+        let count = reader.u32(8)? as usize;
+
+        let slots = (0..count)
+            .map(|index| {
+                let entry = 12 + index * 8;
+                let slot = reader.u32(entry)?;
+                let offset = reader.u32(entry + 4)? as usize;
+                let blob_length = reader.u32(offset + 4)? as usize;
+
+                Ok((slot, reader.bytes(offset, blob_length)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { slots })
+    }
+
+    /// Returns the blob stored in a slot, header included.
+    pub fn blob(&self, slot: u32) -> Option<&'a [u8]> {
+        self.slots
+            .iter()
+            .find(|(index, _)| *index == slot)
+            .map(|(_, blob)| *blob)
+    }
+
+    /// Returns the XML entitlements property list.
+    ///
+    /// Note:
+    /// Returns `None` when the slot is absent or does not hold an
+    /// entitlements blob.
+    pub fn entitlements_plist(&self) -> Option<&'a [u8]> {
+        let blob = self.blob(CSSLOT_ENTITLEMENTS)?;
+        let reader = ByteReader::new(blob, false);
+
+        (reader.u32(0).ok()? == CSMAGIC_EMBEDDED_ENTITLEMENTS && blob.len() >= 8)
+            .then(|| &blob[8..])
+    }
+
+    /// Returns the DER-encoded CMS signature.
+    ///
+    /// Note:
+    /// Returns `None` when the slot is absent or empty, as in
+    /// ad-hoc signatures.
+    pub fn cms_signature(&self) -> Option<&'a [u8]> {
+        let blob = self.blob(CSSLOT_SIGNATURESLOT)?;
+        let reader = ByteReader::new(blob, false);
+
+        (reader.u32(0).ok()? == CSMAGIC_BLOBWRAPPER && blob.len() > 8).then(|| &blob[8..])
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Linked library discovery.
+//!
+//! Executables name the libraries they load in dylib load
+//! commands, either as absolute install names or relative to a
+//! run-time location:
+//!
+//! ```text
+//! /Library/Frameworks/Vendor.framework/Versions/A/Vendor
+//! /usr/local/lib/libvendor.dylib
+//! @rpath/Vendor.framework/Vendor
+//! @executable_path/../Frameworks/libinternal.dylib
+//! ```
+//!
+//! Relative names are resolved the way `dyld` does, so that
+//! libraries inside the bundle and libraries installed elsewhere
+//! can be told apart.
+//!
+//! Note:
+//! Libraries under `/System` and `/usr/lib` belong to the
+//! operating system, and libraries under package manager
+//! prefixes such as `/usr/local` and `/opt/homebrew` belong to
+//! the package manager. Neither is ever reported.
+//!..
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::errors::{ErrorKind, Result};
+use crate::macho::mach_file::{LoadCommand, MachFile, MachSlice, SliceHeader};
+use crate::utility::FileSystem;
+
+/// Load command of a required library.
+pub const LC_LOAD_DYLIB: u32 = 0xc;
+
+/// Load command of a library loaded lazily.
+pub const LC_LAZY_LOAD_DYLIB: u32 = 0x20;
+
+/// Load command of an optional library.
+pub const LC_LOAD_WEAK_DYLIB: u32 = 0x8000_0018;
+
+/// Load command of a re-exported library.
+pub const LC_REEXPORT_DYLIB: u32 = 0x8000_001f;
+
+/// Load command of an upward library.
+pub const LC_LOAD_UPWARD_DYLIB: u32 = 0x8000_0023;
+
+/// Load command of a run-path search directory.
+pub const LC_RPATH: u32 = 0x8000_001c;
+
+const DYLIB_COMMANDS: [u32; 5] = [
+    LC_LOAD_DYLIB,
+    LC_LAZY_LOAD_DYLIB,
+    LC_LOAD_WEAK_DYLIB,
+    LC_REEXPORT_DYLIB,
+    LC_LOAD_UPWARD_DYLIB,
+];
+
+/// Locations owned by the operating system.
+const SYSTEM_PREFIXES: [&str; 3] = ["/System/", "/usr/lib/", "/Library/Apple/"];
+
+/// Locations managed by Homebrew and MacPorts.
+const PACKAGE_MANAGER_PREFIXES: [&str; 3] = ["/usr/local/", "/opt/homebrew/", "/opt/local/"];
+
+impl LoadCommand<'_> {
+    /// Returns the install name of a dylib load command.
+    ///
+    /// Note:
+    /// Returns `None` for other commands.
+    pub fn dylib_name(&self) -> Option<String> {
+        DYLIB_COMMANDS
+            .contains(&self.cmd())
+            .then(|| self.string_at(8))
+            .flatten()
+    }
+
+    /// Returns the directory of an `LC_RPATH` command.
+    ///
+    /// Note:
+    /// Returns `None` for other commands.
+    pub fn rpath(&self) -> Option<String> {
+        (self.cmd() == LC_RPATH)
+            .then(|| self.string_at(8))
+            .flatten()
+    }
+
+    /// Reads an `lc_str`: a string at an offset stored inside
+    /// the command.
+    fn string_at(&self, field: usize) -> Option<String> {
+        let reader = self.reader();
+        let offset = reader.u32(field).ok()? as usize;
+
+        reader.c_str(offset).ok().filter(|name| !name.is_empty())
+    }
+}
+
+/// Libraries linked by a Mach-O file.
+///
+/// Doc:
+/// Collects install names and run-path directories over every
+/// slice of a universal file, without duplicates.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LinkedLibraries {
+    install_names: Vec<String>,
+    rpaths: Vec<String>,
+}
+
+impl LinkedLibraries {
+    /// Reads the linked libraries of a Mach-O file.
+    ///
+    /// Returns an error if the data is not a valid Mach-O file.
+    pub fn from_mach_o(data: &[u8]) -> Result<Self> {
+        let file = MachFile::parse(data)?;
+
+        Ok(Self::from_slices(file.slices()))
+    }
+
+    /// Reads the linked libraries of a Mach-O file with the
+    /// provided `FileSystem`.
+    ///
+    /// Doc:
+    /// Same as `from_mach_o()`, reading only the slice headers
+    /// and load commands instead of the whole executable.
+    pub fn from_executable_with<FS>(fs: &FS, path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let read =
+            || -> Result<Self> { Self::from_slice_headers(&SliceHeader::read_with(fs, path)?) };
+
+        read().map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read linked libraries")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })
+    }
+
+    /// Reads the linked libraries from slice headers read with
+    /// `SliceHeader::read_with()`.
+    ///
+    /// Returns an error if a header cannot be parsed.
+    pub fn from_slice_headers(headers: &[SliceHeader]) -> Result<Self> {
+        let slices = headers
+            .iter()
+            .map(SliceHeader::parse)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from_slices(&slices))
+    }
+
+    /// Collects the install names and run paths of every slice.
+    fn from_slices(slices: &[MachSlice<'_>]) -> Self {
+        let mut libraries = Self::default();
+
+        let commands = slices.iter().flat_map(|slice| slice.load_commands().iter());
+
+        for command in commands {
+            if let Some(name) = command.dylib_name()
+                && !libraries.install_names.contains(&name)
+            {
+                libraries.install_names.push(name);
+            } else if let Some(rpath) = command.rpath()
+                && !libraries.rpaths.contains(&rpath)
+            {
+                libraries.rpaths.push(rpath);
+            }
+        }
+
+        libraries
+    }
+
+    /// get install names reference
+    pub fn as_install_names(&self) -> &[String] {
+        &self.install_names
+    }
+
+    /// get run-path directories reference
+    pub fn as_rpaths(&self) -> &[String] {
+        &self.rpaths
+    }
+
+    /// Returns the libraries loaded from outside a bundle.
+    ///
+    /// Doc:
+    /// Resolves every install name for an executable located at
+    /// `executable`:
+    ///
+    /// - `@executable_path` and `@loader_path` resolve to the
+    ///   executable's directory.
+    /// - `@rpath` is tried against each run-path directory in
+    ///   order, and the first candidate that `exists` is used.
+    ///
+    /// Libraries resolving inside `bundle`, system libraries and
+    /// package manager libraries are dropped. Libraries inside a framework are reported as
+    /// the framework bundle:
+    ///
+    /// ```text
+    /// /Library/Frameworks/Vendor.framework/Versions/A/Vendor
+    ///     -> /Library/Frameworks/Vendor.framework
+    /// ```
+    ///
+    /// Note:
+    /// `@rpath` names that resolve nowhere are dropped, since
+    /// their location is unknown.
+    pub fn external_paths<F>(&self, executable: &Path, bundle: &Path, exists: F) -> Vec<PathBuf>
+    where
+        F: Fn(&Path) -> bool,
+    {
+        let loader_dir = executable.parent().unwrap_or(Path::new("/"));
+        let rpaths: Vec<PathBuf> = self
+            .rpaths
+            .iter()
+            .map(|rpath| resolve_loader_path(rpath, loader_dir))
+            .collect();
+
+        let mut paths: Vec<PathBuf> = Vec::new();
+
+        for name in &self.install_names {
+            let resolved = match name.strip_prefix("@rpath/") {
+                Some(relative) => rpaths
+                    .iter()
+                    .map(|rpath| normalize(&rpath.join(relative)))
+                    .find(|candidate| exists(candidate)),
+                None => Some(resolve_loader_path(name, loader_dir)),
+            };
+
+            let Some(resolved) = resolved else {
+                continue;
+            };
+
+            if resolved.starts_with(bundle)
+                || is_system_path(&resolved)
+                || is_package_manager_path(&resolved)
+            {
+                continue;
+            }
+
+            let owner = owning_framework(&resolved).unwrap_or(resolved);
+            if !paths.contains(&owner) {
+                paths.push(owner);
+            }
+        }
+
+        paths
+    }
+}
+
+/// Returns whether a library belongs to the operating system.
+pub fn is_system_path(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    SYSTEM_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Returns whether a library belongs to a package manager.
+///
+/// Note:
+/// Package managers install libraries for every program on the
+/// system, so an application linking one never owns it.
+pub fn is_package_manager_path(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    PACKAGE_MANAGER_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Resolves `@executable_path` and `@loader_path` prefixes.
+fn resolve_loader_path(name: &str, loader_dir: &Path) -> PathBuf {
+    let relative = name
+        .strip_prefix("@executable_path")
+        .or_else(|| name.strip_prefix("@loader_path"));
+
+    match relative {
+        Some(rest) => normalize(&loader_dir.join(rest.trim_start_matches('/'))),
+        None => normalize(Path::new(name)),
+    }
+}
+
+/// Removes `.` and `..` components without touching the disk.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    normalized
+}
+
+/// Returns the innermost `.framework` bundle containing a path.
+fn owning_framework(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| {
+            ancestor
+                .extension()
+                .map(|ext| ext == "framework")
+                .unwrap_or(false)
+        })
+        .map(Path::to_path_buf)
+}
@@ -0,0 +1,200 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Code-signing entitlements.
+//!
+//! Entitlements are embedded in the code signature of every
+//! signed executable. The ones relevant to discovery are:
+//!
+//! - `com.apple.security.application-groups`, the exact names of
+//!   the group containers the executable may use.
+//! - `com.apple.developer.team-identifier`, the signing team.
+//! - `com.apple.application-identifier`, prefixed with the team.
+//!
+//! Note:
+//! Only the XML entitlements slot is read. Executables signed
+//! since macOS 12 also carry a DER copy holding the same values.
+//!..
+
+use plist::Value;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::macho::code_signature::SuperBlob;
+use crate::macho::mach_file::{MachFile, SliceHeader};
+use crate::utility::FileSystem;
+
+const APPLICATION_GROUPS: &str = "com.apple.security.application-groups";
+const TEAM_IDENTIFIER: &str = "com.apple.developer.team-identifier";
+const APPLICATION_IDENTIFIERS: [&str; 2] =
+    ["com.apple.application-identifier", "application-identifier"];
+
+/// Entitlements of a signed executable.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Entitlements {
+    application_groups: Vec<String>,
+    team_identifier: Option<String>,
+    application_identifier: Option<String>,
+}
+
+impl Entitlements {
+    /// Parses an entitlements property list.
+    ///
+    /// Doc:
+    /// The team identifier is read from
+    /// `com.apple.developer.team-identifier`, or else from the
+    /// prefix of the application identifier:
+    ///
+    /// ```text
+    /// ABCDE12345.com.vendor.app -> ABCDE12345
+    /// ```
+    ///
+    /// Returns an error if the data is not a dictionary property
+    /// list.
+    pub fn from_plist(data: &[u8]) -> Result<Self> {
+        let plist = Value::from_reader(Cursor::new(data)).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to parse entitlements")
+                .with_reason(e.to_string())
+        })?;
+
+        let dict = plist.as_dictionary().ok_or_else(|| {
+            ErrorKind::failed()
+                .with_summary("Failed to parse entitlements")
+                .with_reason("entitlements are not a dictionary")
+        })?;
+
+        let string = |key: &str| {
+            dict.get(key)
+                .and_then(|value| value.as_string())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let application_groups = dict
+            .get(APPLICATION_GROUPS)
+            .and_then(|value| value.as_array())
+            .map(|groups| {
+                groups
+                    .iter()
+                    .filter_map(|group| group.as_string())
+                    .map(str::trim)
+                    .filter(|group| !group.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let application_identifier = APPLICATION_IDENTIFIERS.iter().find_map(|key| string(key));
+
+        let team_identifier = string(TEAM_IDENTIFIER).or_else(|| {
+            application_identifier
+                .as_deref()
+                .and_then(|id| id.split_once('.'))
+                .map(|(team, _)| team.to_string())
+        });
+
+        Ok(Self {
+            application_groups,
+            team_identifier,
+            application_identifier,
+        })
+    }
+
+    /// Reads the entitlements of a Mach-O file.
+    ///
+    /// Doc:
+    /// Every slice of a universal file is inspected and the
+    /// first slice carrying entitlements is used.
+    ///
+    /// Returns `Ok(None)` when the file is unsigned or signed
+    /// without entitlements, and an error if it is not a valid
+    /// Mach-O file.
+    pub fn from_mach_o(data: &[u8]) -> Result<Option<Self>> {
+        let file = MachFile::parse(data)?;
+
+        for slice in file.slices() {
+            let Some(signature) = slice.code_signature()? else {
+                continue;
+            };
+
+            if let Some(entitlements) = Self::from_signature(signature)? {
+                return Ok(Some(entitlements));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Reads the entitlements of an embedded code signature, the
+    /// `LC_CODE_SIGNATURE` data of one slice.
+    ///
+    /// Returns `Ok(None)` when the signature carries no
+    /// entitlements.
+    pub fn from_signature(signature: &[u8]) -> Result<Option<Self>> {
+        SuperBlob::parse(signature)?
+            .entitlements_plist()
+            .map(Self::from_plist)
+            .transpose()
+    }
+
+    /// Reads the entitlements of an executable through the
+    /// provided `FileSystem`.
+    ///
+    /// Doc:
+    /// Same as `from_mach_o()`, reading only the slice headers
+    /// and code signatures instead of the whole executable.
+    pub fn from_executable_with<FS>(fs: &FS, path: &Path) -> Result<Option<Self>>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let read = || -> Result<Option<Self>> {
+            for header in SliceHeader::read_with(fs, path)? {
+                let Some(signature) = header.read_code_signature_with(fs, path)? else {
+                    continue;
+                };
+
+                if let Some(entitlements) = Self::from_signature(&signature)? {
+                    return Ok(Some(entitlements));
+                }
+            }
+
+            Ok(None)
+        };
+
+        read().map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read entitlements")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })
+    }
+
+    /// get application groups reference
+    pub fn as_application_groups(&self) -> &[String] {
+        &self.application_groups
+    }
+
+    /// get team identifier reference
+    pub fn as_team_identifier(&self) -> Option<&str> {
+        self.team_identifier.as_deref()
+    }
+
+    /// get application identifier reference
+    pub fn as_application_identifier(&self) -> Option<&str> {
+        self.application_identifier.as_deref()
+    }
+}
@@ -0,0 +1,556 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Mach-O container and load command parsing.
+//!
+//! A Mach-O file is either:
+//!
+//! - A thin file: one header followed by its load commands.
+//! - A universal (fat) file: a big-endian table of slices, each
+//!   of them a thin file for one architecture.
+//!
+//! Both 32-bit and 64-bit headers are accepted in either byte
+//! order.
+//!
+//! Note:
+//! Only headers and load commands are parsed. Section contents
+//! are left to the callers that need them. `SliceHeader` reads
+//! just those parts from disk.
+//!..
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::utility::FileSystem;
+
+const MH_MAGIC: u32 = 0xfeed_face;
+const MH_MAGIC_64: u32 = 0xfeed_facf;
+const FAT_MAGIC: u32 = 0xcafe_babe;
+const FAT_MAGIC_64: u32 = 0xcafe_babf;
+
+/// Upper bound of slices in a universal file.
+///
+/// Note:
+/// Java class files share `0xcafebabe` and store their version
+/// where the slice count would be, which is always far above
+/// this bound.
+const MAX_FAT_ARCHS: u32 = 32;
+
+/// Load command holding the code signature location.
+pub const LC_CODE_SIGNATURE: u32 = 0x1d;
+
+/// CPU type of 64-bit Intel slices.
+pub const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+
+/// CPU type of Apple silicon slices.
+pub const CPU_TYPE_ARM64: u32 = 0x0100_000c;
+
+pub(crate) fn invalid(reason: impl Into<Cow<'static, str>>) -> ErrorKind {
+    ErrorKind::failed()
+        .with_summary("Invalid Mach-O file")
+        .with_reason(reason)
+}
+
+/// Bounds-checked integer reads.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(data: &'a [u8], little_endian: bool) -> Self {
+        Self {
+            data,
+            little_endian,
+        }
+    }
+
+    pub(crate) fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or_else(|| {
+                invalid(format!(
+                    "read of {} bytes at offset {} exceeds {} bytes",
+                    len,
+                    offset,
+                    self.data.len()
+                ))
+            })
+    }
+
+    pub(crate) fn u32(&self, offset: usize) -> Result<u32> {
+        let bytes: [u8; 4] = self.bytes(offset, 4)?.try_into().unwrap_or_default();
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    pub(crate) fn c_str(&self, offset: usize) -> Result<String> {
+        let tail = self.data.get(offset..).unwrap_or_default();
+        let len = tail
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| invalid(format!("unterminated string at offset {}", offset)))?;
+
+        Ok(String::from_utf8_lossy(&tail[..len]).into_owned())
+    }
+
+    pub(crate) fn u64(&self, offset: usize) -> Result<u64> {
+        let bytes: [u8; 8] = self.bytes(offset, 8)?.try_into().unwrap_or_default();
+        Ok(if self.little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+}
+
+/// A single load command.
+#[derive(Debug, Clone, Copy)]
+pub struct LoadCommand<'a> {
+    cmd: u32,
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> LoadCommand<'a> {
+    /// get the copy of command type
+    pub fn cmd(&self) -> u32 {
+        self.cmd
+    }
+
+    /// get command bytes reference, header included
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub(crate) fn reader(&self) -> ByteReader<'a> {
+        ByteReader::new(self.data, self.little_endian)
+    }
+}
+
+/// A thin Mach-O image.
+///
+/// Doc:
+/// Either a whole thin file or one slice of a universal file.
+/// Offsets stored in load commands are relative to the start of
+/// the slice.
+#[derive(Debug, Clone)]
+pub struct MachSlice<'a> {
+    data: &'a [u8],
+    offset: u64,
+    cpu_type: u32,
+    cpu_subtype: u32,
+    file_type: u32,
+    is_64: bool,
+    little_endian: bool,
+    commands: Vec<LoadCommand<'a>>,
+}
+
+impl<'a> MachSlice<'a> {
+    /// Parses a thin Mach-O image.
+    ///
+    /// Returns an error if the magic is unknown or a load
+    /// command lies outside the image.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        Self::parse_at(data, 0)
+    }
+
+    fn parse_at(data: &'a [u8], offset: u64) -> Result<Self> {
+        let magic_bytes: [u8; 4] = ByteReader::new(data, true)
+            .bytes(0, 4)?
+            .try_into()
+            .unwrap_or_default();
+
+        let (is_64, little_endian) = match (
+            u32::from_le_bytes(magic_bytes),
+            u32::from_be_bytes(magic_bytes),
+        ) {
+            (MH_MAGIC_64, _) => (true, true),
+            (MH_MAGIC, _) => (false, true),
+            (_, MH_MAGIC_64) => (true, false),
+            (_, MH_MAGIC) => (false, false),
+            _ => return Err(invalid("unknown Mach-O magic")),
+        };
+
+        let reader = ByteReader::new(data, little_endian);
+        let cpu_type = reader.u32(4)?;
+        let cpu_subtype = reader.u32(8)?;
+        let file_type = reader.u32(12)?;
+        let ncmds = reader.u32(16)?;
+        let header_size = if is_64 { 32 } else { 28 };
+
+        let mut commands = Vec::new();
+        let mut cursor = header_size;
+
+        for index in 0..ncmds {
+            let cmd = reader.u32(cursor)?;
+            let size = reader.u32(cursor + 4)? as usize;
+
+            if size < 8 {
+                return Err(invalid(format!(
+                    "load command {} has invalid size {}",
+                    index, size
+                )));
+            }
+
+            commands.push(LoadCommand {
+                cmd,
+                data: reader.bytes(cursor, size)?,
+                little_endian,
+            });
+            cursor += size;
+        }
+
+        Ok(Self {
+            data,
+            offset,
+            cpu_type,
+            cpu_subtype,
+            file_type,
+            is_64,
+            little_endian,
+            commands,
+        })
+    }
+
+    /// get slice bytes reference
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// get the copy of slice offset within the file
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// get the copy of CPU type
+    pub fn cpu_type(&self) -> u32 {
+        self.cpu_type
+    }
+
+    /// get the copy of CPU subtype
+    pub fn cpu_subtype(&self) -> u32 {
+        self.cpu_subtype
+    }
+
+    /// get the copy of file type
+    pub fn file_type(&self) -> u32 {
+        self.file_type
+    }
+
+    /// get the copy of 64-bit flag
+    pub fn is_64(&self) -> bool {
+        self.is_64
+    }
+
+    /// get the copy of byte order flag
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    /// get load commands reference
+    pub fn load_commands(&self) -> &[LoadCommand<'a>] {
+        &self.commands
+    }
+
+    /// Returns the architecture name used by `lipo`.
+    ///
+    /// Note:
+    /// Unknown CPU types are reported as `unknown`.
+    pub fn arch_name(&self) -> &'static str {
+        match self.cpu_type {
+            CPU_TYPE_X86_64 => "x86_64",
+            CPU_TYPE_ARM64 => "arm64",
+            0x7 => "i386",
+            0x12 => "ppc",
+            0x0100_0012 => "ppc64",
+            _ => "unknown",
+        }
+    }
+
+    /// Returns the code signature bytes.
+    ///
+    /// Doc:
+    /// Resolves the `LC_CODE_SIGNATURE` command, whose data lies
+    /// in `__LINKEDIT`.
+    ///
+    /// Returns `Ok(None)` for unsigned images and an error if the
+    /// command points outside the slice.
+    pub fn code_signature(&self) -> Result<Option<&'a [u8]>> {
+        let Some((offset, size)) = self.code_signature_range()? else {
+            return Ok(None);
+        };
+
+        ByteReader::new(self.data, self.little_endian)
+            .bytes(offset as usize, size as usize)
+            .map(Some)
+    }
+
+    /// Returns the offset and size of the code signature,
+    /// relative to the start of the slice.
+    ///
+    /// Note:
+    /// Returns `Ok(None)` for unsigned images. The range is not
+    /// checked against the slice, so it can be resolved from
+    /// headers read with `SliceHeader::read_with()`.
+    pub fn code_signature_range(&self) -> Result<Option<(u64, u64)>> {
+        let Some(command) = self
+            .commands
+            .iter()
+            .find(|command| command.cmd == LC_CODE_SIGNATURE)
+        else {
+            return Ok(None);
+        };
+
+        let reader = command.reader();
+
+        Ok(Some((reader.u32(8)? as u64, reader.u32(12)? as u64)))
+    }
+}
+
+/// Header and load commands of one slice, read from disk.
+///
+/// Doc:
+/// Holds only the bytes needed to parse the load commands of a
+/// slice, together with the slice offset and size within the
+/// file.
+///
+/// Design:
+/// Executables are often tens of megabytes, while their load
+/// commands fit in a few kilobytes. Scanners that inspect many
+/// executables, such as the executables of every installed
+/// application, read the headers instead of whole files.
+#[derive(Debug, Clone)]
+pub struct SliceHeader {
+    offset: u64,
+    size: u64,
+    data: Vec<u8>,
+}
+
+impl SliceHeader {
+    /// Bytes read to identify a file, enough for a universal
+    /// header with `MAX_FAT_ARCHS` slices.
+    const PROBE_LEN: usize = 8 + MAX_FAT_ARCHS as usize * 32;
+
+    /// Reads the header of every slice of a Mach-O file.
+    ///
+    /// Returns an error if the file cannot be read or is not a
+    /// Mach-O file.
+    pub fn read_with<FS>(fs: &FS, path: &Path) -> Result<Vec<Self>>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let probe = fs.read_range(path, 0, Self::PROBE_LEN)?;
+        let reader = ByteReader::new(&probe, false);
+        let magic = reader.u32(0)?;
+
+        if magic != FAT_MAGIC && magic != FAT_MAGIC_64 {
+            let size = fs.metadata(path).map(|m| m.len()).unwrap_or(0);
+            return Ok(vec![Self::read_at(fs, path, 0, size)?]);
+        }
+
+        let count = reader.u32(4)?;
+        if count > MAX_FAT_ARCHS {
+            return Err(invalid(format!("{} slices is not a universal file", count)));
+        }
+
+        let is_64 = magic == FAT_MAGIC_64;
+        let entry_size = if is_64 { 32 } else { 20 };
+
+        (0..count as usize)
+            .map(|index| {
+                let entry = 8 + index * entry_size;
+                let (offset, size) = if is_64 {
+                    (reader.u64(entry + 8)?, reader.u64(entry + 16)?)
+                } else {
+                    (
+                        reader.u32(entry + 8)? as u64,
+                        reader.u32(entry + 12)? as u64,
+                    )
+                };
+
+                Self::read_at(fs, path, offset, size)
+            })
+            .collect()
+    }
+
+    /// Reads the header and load commands of the slice starting
+    /// at `offset` and `size` bytes long.
+    fn read_at<FS>(fs: &FS, path: &Path, offset: u64, size: u64) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let header = fs.read_range(path, offset, 32)?;
+        let magic_bytes: [u8; 4] = ByteReader::new(&header, true)
+            .bytes(0, 4)?
+            .try_into()
+            .unwrap_or_default();
+
+        let (header_size, little_endian) = match (
+            u32::from_le_bytes(magic_bytes),
+            u32::from_be_bytes(magic_bytes),
+        ) {
+            (MH_MAGIC_64, _) => (32, true),
+            (MH_MAGIC, _) => (28, true),
+            (_, MH_MAGIC_64) => (32, false),
+            (_, MH_MAGIC) => (28, false),
+            _ => return Err(invalid("unknown Mach-O magic")),
+        };
+
+        let commands_size = ByteReader::new(&header, little_endian).u32(20)? as usize;
+        let data = fs.read_range(path, offset, header_size + commands_size)?;
+
+        Ok(Self { offset, size, data })
+    }
+
+    /// get the copy of slice offset within the file
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// get the copy of slice size in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Parses the header and load commands.
+    ///
+    /// Note:
+    /// The parsed slice holds no section or signature data, so
+    /// `MachSlice::code_signature()` fails on it. Use
+    /// `MachSlice::code_signature_range()` instead.
+    pub fn parse(&self) -> Result<MachSlice<'_>> {
+        MachSlice::parse_at(&self.data, self.offset)
+    }
+
+    /// Reads the code signature of the slice from `path`.
+    ///
+    /// Doc:
+    /// Resolves `LC_CODE_SIGNATURE` from the load commands and
+    /// reads only the signature bytes.
+    ///
+    /// Returns `Ok(None)` for unsigned slices.
+    pub fn read_code_signature_with<FS>(&self, fs: &FS, path: &Path) -> Result<Option<Vec<u8>>>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let Some((offset, size)) = self.parse()?.code_signature_range()? else {
+            return Ok(None);
+        };
+
+        fs.read_range(path, self.offset + offset, size as usize)
+            .map(Some)
+    }
+}
+
+/// A thin or universal Mach-O file.
+#[derive(Debug, Clone)]
+pub struct MachFile<'a> {
+    slices: Vec<MachSlice<'a>>,
+    is_fat: bool,
+}
+
+impl<'a> MachFile<'a> {
+    /// Parses a thin or universal Mach-O file.
+    ///
+    /// Returns an error if the data is not a Mach-O file or a
+    /// slice lies outside it.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let reader = ByteReader::new(data, false);
+        let magic = reader.u32(0)?;
+
+        if magic != FAT_MAGIC && magic != FAT_MAGIC_64 {
+            return Ok(Self {
+                slices: vec![MachSlice::parse(data)?],
+                is_fat: false,
+            });
+        }
+
+        let count = reader.u32(4)?;
+        if count > MAX_FAT_ARCHS {
+            return Err(invalid(format!("{} slices is not a universal file", count)));
+        }
+
+        let is_64 = magic == FAT_MAGIC_64;
+        let entry_size = if is_64 { 32 } else { 20 };
+
+        let slices = (0..count as usize)
+            .map(|index| {
+                let entry = 8 + index * entry_size;
+                let (offset, size) = if is_64 {
+                    (reader.u64(entry + 8)?, reader.u64(entry + 16)?)
+                } else {
+                    (
+                        reader.u32(entry + 8)? as u64,
+                        reader.u32(entry + 12)? as u64,
+                    )
+                };
+
+                let bytes = reader.bytes(offset as usize, size as usize)?;
+                MachSlice::parse_at(bytes, offset)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            slices,
+            is_fat: true,
+        })
+    }
+
+    /// Returns whether the data starts with a Mach-O magic.
+    ///
+    /// Note:
+    /// Universal magics are accepted as well, so Java class files
+    /// can produce false positives. `parse()` rejects them.
+    pub fn is_mach_o(data: &[u8]) -> bool {
+        let Some(magic) = data.get(..4) else {
+            return false;
+        };
+        let magic: [u8; 4] = magic.try_into().unwrap_or_default();
+        let (le, be) = (u32::from_le_bytes(magic), u32::from_be_bytes(magic));
+
+        [MH_MAGIC, MH_MAGIC_64].contains(&le)
+            || [MH_MAGIC, MH_MAGIC_64, FAT_MAGIC, FAT_MAGIC_64].contains(&be)
+    }
+
+    /// Returns whether the data starts with a universal magic.
+    ///
+    /// Note:
+    /// Java class files share the magic. `parse()` rejects them.
+    pub fn is_universal(data: &[u8]) -> bool {
+        let Some(magic) = data.get(..4) else {
+            return false;
+        };
+        let magic: [u8; 4] = magic.try_into().unwrap_or_default();
+
+        [FAT_MAGIC, FAT_MAGIC_64].contains(&u32::from_be_bytes(magic))
+    }
+
+    /// get slices reference
+    pub fn slices(&self) -> &[MachSlice<'a>] {
+        &self.slices
+    }
+
+    /// get the copy of universal flag
+    pub fn is_fat(&self) -> bool {
+        self.is_fat
+    }
+}
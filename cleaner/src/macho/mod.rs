@@ -0,0 +1,59 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Mach-O executable inspection.
+//!
+//! Reads the parts of application executables that identify
+//! resources outside the bundle:
+//!
+//! - Headers and load commands of thin and universal files.
+//! - Libraries linked from outside the bundle.
+//! - The embedded code signature superblob.
+//! - The signing identity, team, and certificate chain.
+//! - Entitlements, such as application groups.
+//!
+//! Design:
+//! Parsing is pure Rust over byte slices. It does not call
+//! `codesign` or Security.framework, so it runs on any platform
+//! and is tested against small fixture binaries.
+//!
+//! Note:
+//! The parser is read-only and rejects malformed input with an
+//! error instead of guessing.
+//!..
+
+mod cms;
+mod code_directory;
+mod code_signature;
+mod dylib;
+mod entitlements;
+mod mach_file;
+mod signing_info;
+
+pub use cms::{CertificateSubject, certificate_subjects};
+pub use code_directory::{CS_ADHOC, CS_LINKER_SIGNED, CS_RUNTIME, CodeDirectory};
+pub use code_signature::{
+    CSSLOT_CODEDIRECTORY, CSSLOT_ENTITLEMENTS, CSSLOT_SIGNATURESLOT, SuperBlob,
+};
+pub use dylib::{
+    LC_LAZY_LOAD_DYLIB, LC_LOAD_DYLIB, LC_LOAD_UPWARD_DYLIB, LC_LOAD_WEAK_DYLIB, LC_REEXPORT_DYLIB,
+    LC_RPATH, LinkedLibraries, is_package_manager_path, is_system_path,
+};
+pub use entitlements::Entitlements;
+pub use mach_file::{
+    CPU_TYPE_ARM64, CPU_TYPE_X86_64, LC_CODE_SIGNATURE, LoadCommand, MachFile, MachSlice,
+    SliceHeader,
+};
+pub use signing_info::{SignatureKind, SigningInfo};
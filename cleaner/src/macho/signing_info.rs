@@ -0,0 +1,255 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Code signature summary.
+//!
+//! Combines the CodeDirectory and the CMS certificates of an
+//! executable into the facts shown to the user, similar to
+//! `codesign -dv`:
+//!
+//! - Whether the code is signed, ad-hoc signed, or unsigned.
+//! - The signing identifier and team identifier.
+//! - Whether the hardened runtime is enabled.
+//! - The certificate subject chain.
+//!
+//! Note:
+//! Signatures are summarized, not verified.
+//!..
+
+use std::fmt;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::macho::cms::{CertificateSubject, certificate_subjects};
+use crate::macho::code_directory::{CS_ADHOC, CS_LINKER_SIGNED, CS_RUNTIME, CodeDirectory};
+use crate::macho::code_signature::{CSSLOT_CODEDIRECTORY, SuperBlob};
+use crate::macho::mach_file::{MachFile, SliceHeader, invalid};
+use crate::utility::FileSystem;
+
+/// Kind of code signature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureKind {
+    /// No code signature.
+    #[default]
+    Unsigned,
+    /// Signed without a signing identity.
+    AdHoc,
+    /// Signed with a certificate.
+    Signed,
+}
+
+impl SignatureKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unsigned => "Unsigned",
+            Self::AdHoc => "Ad-hoc signed",
+            Self::Signed => "Signed",
+        }
+    }
+}
+
+/// Code signature summary of an executable.
+///
+/// Doc:
+/// Each entry stores:
+///
+/// - The signature kind.
+/// - The signing identifier.
+/// - The team identifier.
+/// - The CodeDirectory flags.
+/// - The certificate subjects, signing certificate first.
+///
+/// Note:
+/// The summary describes what the signature claims. It is not
+/// verified against the code or a trust policy.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SigningInfo {
+    kind: SignatureKind,
+    identifier: Option<String>,
+    team_identifier: Option<String>,
+    flags: u32,
+    authorities: Vec<CertificateSubject>,
+}
+
+impl SigningInfo {
+    /// Summarizes the code signature of a Mach-O file.
+    ///
+    /// Doc:
+    /// The first signed slice of a universal file is used. The
+    /// team identifier is read from the CodeDirectory, or else
+    /// from the organizational unit of the signing certificate.
+    ///
+    /// Returns an unsigned summary when no slice is signed, and
+    /// an error if the file or its signature is malformed.
+    pub fn from_mach_o(data: &[u8]) -> Result<Self> {
+        let file = MachFile::parse(data)?;
+
+        let Some(signature) = file
+            .slices()
+            .iter()
+            .map(|slice| slice.code_signature())
+            .find_map(|signature| signature.transpose())
+            .transpose()?
+        else {
+            return Ok(Self::default());
+        };
+
+        Self::from_signature(signature)
+    }
+
+    /// Summarizes an embedded code signature, the
+    /// `LC_CODE_SIGNATURE` data of one slice.
+    ///
+    /// Returns an error if the signature is malformed.
+    pub fn from_signature(signature: &[u8]) -> Result<Self> {
+        let superblob = SuperBlob::parse(signature)?;
+        let directory = superblob
+            .blob(CSSLOT_CODEDIRECTORY)
+            .ok_or_else(|| invalid("code signature has no code directory"))
+            .and_then(CodeDirectory::parse)?;
+
+        let authorities = match superblob.cms_signature() {
+            Some(cms) => certificate_subjects(cms)?,
+            None => Vec::new(),
+        };
+
+        let is_adhoc = directory.flags() & (CS_ADHOC | CS_LINKER_SIGNED) != 0;
+        let kind = if is_adhoc || authorities.is_empty() {
+            SignatureKind::AdHoc
+        } else {
+            SignatureKind::Signed
+        };
+
+        let team_identifier = directory
+            .as_team_identifier()
+            .map(str::to_string)
+            .or_else(|| {
+                authorities
+                    .first()
+                    .and_then(|leaf| leaf.as_organizational_unit())
+                    .map(str::to_string)
+            });
+
+        Ok(Self {
+            kind,
+            identifier: Some(directory.as_identifier().to_string()).filter(|id| !id.is_empty()),
+            team_identifier,
+            flags: directory.flags(),
+            authorities,
+        })
+    }
+
+    /// Summarizes the code signature of an executable through
+    /// the provided `FileSystem`.
+    ///
+    /// Doc:
+    /// Same as `from_mach_o()`, reading only the slice headers
+    /// and the first code signature instead of the whole
+    /// executable.
+    pub fn from_executable_with<FS>(fs: &FS, path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let read = || -> Result<Self> {
+            for header in SliceHeader::read_with(fs, path)? {
+                if let Some(signature) = header.read_code_signature_with(fs, path)? {
+                    return Self::from_signature(&signature);
+                }
+            }
+
+            Ok(Self::default())
+        };
+
+        read().map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read code signature")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })
+    }
+
+    /// get the copy of signature kind
+    pub fn kind(&self) -> SignatureKind {
+        self.kind
+    }
+
+    /// get signing identifier reference
+    pub fn as_identifier(&self) -> Option<&str> {
+        self.identifier.as_deref()
+    }
+
+    /// get team identifier reference
+    pub fn as_team_identifier(&self) -> Option<&str> {
+        self.team_identifier.as_deref()
+    }
+
+    /// get the copy of CodeDirectory flags
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// get certificate subjects reference, signing certificate first
+    pub fn as_authorities(&self) -> &[CertificateSubject] {
+        &self.authorities
+    }
+
+    /// Returns whether the code carries any signature.
+    pub fn is_signed(&self) -> bool {
+        self.kind != SignatureKind::Unsigned
+    }
+
+    /// Returns whether the signature is ad-hoc.
+    pub fn is_adhoc(&self) -> bool {
+        self.kind == SignatureKind::AdHoc
+    }
+
+    /// Returns whether the hardened runtime is enabled.
+    pub fn is_hardened_runtime(&self) -> bool {
+        self.flags & CS_RUNTIME != 0
+    }
+}
+
+impl fmt::Display for SigningInfo {
+    /// Prints a one-line summary:
+    ///
+    /// ```text
+    /// Developer ID Application: Example Corp (ABCDE12345) · Team ABCDE12345 · Hardened runtime
+    /// Ad-hoc signed · com.example.tool
+    /// Unsigned
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+
+        match (self.kind, self.authorities.first()) {
+            (SignatureKind::Signed, Some(leaf)) => parts.push(leaf.to_string()),
+            (kind, _) => parts.push(kind.as_str().to_string()),
+        }
+
+        if self.kind == SignatureKind::AdHoc
+            && let Some(identifier) = &self.identifier
+        {
+            parts.push(identifier.clone());
+        }
+
+        if let Some(team) = &self.team_identifier {
+            parts.push(format!("Team {}", team));
+        }
+
+        if self.is_hardened_runtime() {
+            parts.push("Hardened runtime".to_string());
+        }
+
+        write!(f, "{}", parts.join(" · "))
+    }
+}
@@ -57,6 +57,8 @@ use std::path::{Path, PathBuf};
 ///
 /// - The filesystem path.
 /// - A display name.
+/// - Why the scanner attributed the path to the application,
+///   when the scanner records it.
 ///
 /// `PathData` acts as the common path model shared across
 /// scanning, reporting, cleanup, trash operations, and
@@ -84,15 +86,27 @@ use std::path::{Path, PathBuf};
 ///
 /// Callers that require the original filesystem path should
 /// use `as_path()` instead of relying on formatted output.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PathData {
     path: PathBuf,
     name: String,
+    reason: String,
 }
 
 impl PathData {
     pub fn new(path: PathBuf, name: String) -> Self {
-        Self { path, name }
+        Self {
+            path,
+            name,
+            reason: String::new(),
+        }
+    }
+
+    /// Records why the path was attributed to the application,
+    /// such as the rule that matched it.
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = reason.into();
+        self
     }
 
     pub fn as_path(&self) -> &Path {
@@ -102,6 +116,15 @@ impl PathData {
     pub fn as_name(&self) -> &str {
         &self.name
     }
+
+    /// get match reason reference, if the scanner recorded one
+    pub fn as_reason(&self) -> Option<&str> {
+        if self.reason.is_empty() {
+            None
+        } else {
+            Some(&self.reason)
+        }
+    }
 }
 
 /// User-facing path formatter.
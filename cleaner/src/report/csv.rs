@@ -0,0 +1,157 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! CSV export of a `ScanReport`.
+//!
+//! One row per process, discovered path and trash result:
+//!
+//! ```text
+//! record,category,name,path,size,pid,match_reason,trashed_path,error
+//! process,,Cafe,/Applications/Cafe.app/Contents/MacOS/Cafe,,412,Executable inside bundle,,
+//! path,Associated files,com.example.cafe,/Users/me/Library/Caches/com.example.cafe,5120,,"File name contains ""com.example.cafe""",,
+//! moved,,Cafe.app,/Applications/Cafe.app,,,,/Users/me/.Trash/Cafe.app,
+//! failed,,Helper,/Library/PrivilegedHelperTools/Helper,,,,,[Failed: ..] - ..
+//! ```
+//!
+//! Note:
+//! Fields are quoted following RFC 4180 when they contain a
+//! comma, a quote or a line break, and prefixed with `'` when
+//! a spreadsheet would read them as a formula. Application
+//! metadata is only part of the JSON export.
+//!..
+
+use crate::report::scan_report::ScanReport;
+
+/// Columns of the CSV export.
+const CSV_HEADER: [&str; 9] = [
+    "record",
+    "category",
+    "name",
+    "path",
+    "size",
+    "pid",
+    "match_reason",
+    "trashed_path",
+    "error",
+];
+
+impl ScanReport {
+    /// Returns the report as CSV text.
+    pub fn to_csv(&self) -> String {
+        let mut rows: Vec<[String; 9]> = Vec::new();
+
+        for process in self.as_processes() {
+            let path = process
+                .as_exe()
+                .map(|exe| exe.to_string_lossy().to_string())
+                .unwrap_or_else(|| process.as_command().to_string());
+
+            rows.push([
+                "process".to_string(),
+                String::new(),
+                process.as_name().to_string(),
+                path,
+                String::new(),
+                process.pid().to_string(),
+                process.match_reason().as_str().to_string(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+
+        for path in self.as_paths() {
+            rows.push([
+                "path".to_string(),
+                path.category().as_str().to_string(),
+                path.as_path_data().as_name().to_string(),
+                path.as_path_data().as_path().to_string_lossy().to_string(),
+                path.size().to_string(),
+                String::new(),
+                path.match_reason().to_string(),
+                String::new(),
+                String::new(),
+            ]);
+        }
+
+        for item in self.as_trash_entry().moved_path() {
+            rows.push([
+                "moved".to_string(),
+                String::new(),
+                item.as_source_path().as_name().to_string(),
+                item.as_source_path()
+                    .as_path()
+                    .to_string_lossy()
+                    .to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                item.as_trashed_path().to_string_lossy().to_string(),
+                String::new(),
+            ]);
+        }
+
+        for (path, error) in self.as_trash_entry().failed_path() {
+            rows.push([
+                "failed".to_string(),
+                String::new(),
+                path.as_name().to_string(),
+                path.as_path().to_string_lossy().to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                error.to_string(),
+            ]);
+        }
+
+        let mut csv = csv_line(CSV_HEADER.iter().copied());
+
+        for row in &rows {
+            csv.push_str(&csv_line(row.iter().map(String::as_str)));
+        }
+
+        csv
+    }
+}
+
+/// Joins fields into one CSV line, ending with CRLF as RFC 4180
+/// requires.
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let mut line = fields.map(csv_field).collect::<Vec<_>>().join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Quotes a field when it contains a separator, a quote or a
+/// line break.
+///
+/// Note:
+/// Spreadsheets evaluate fields starting with `=`, `+`, `-`, `@`
+/// or a tab as formulas. File names are chosen by whoever created
+/// the file, so such fields are prefixed with `'` and shown as
+/// text.
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t']) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
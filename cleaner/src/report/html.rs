@@ -0,0 +1,242 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! HTML export of a `ScanReport`.
+//!
+//! Renders a single self-contained document:
+//!
+//! - The application icon and details.
+//! - Where the space goes: bundle sections, architectures,
+//!   largest files and leftovers outside the bundle.
+//! - Matched processes.
+//! - Discovered paths, one table per category with the match
+//!   reason and size of each path.
+//! - Paths moved to Trash and their location in the Trash.
+//! - Paths that failed, with the summary and reason of their
+//!   error.
+//!
+//! Note:
+//! Styles and the icon are inlined, so the file can be attached
+//! to a ticket or mailed on its own.
+//!..
+
+use std::fmt::Write;
+
+use crate::app_profile::PathCategory;
+use crate::report::inline_image::png_data_uri;
+use crate::report::scan_report::ScanReport;
+use crate::utility::format_size;
+
+const STYLE: &str = "
+body { font: 14px -apple-system, BlinkMacSystemFont, 'Helvetica Neue', sans-serif; color: #1d1d1f; margin: 32px auto; max-width: 960px; padding: 0 16px; }
+header { display: flex; align-items: center; gap: 16px; }
+header img { width: 64px; height: 64px; }
+h1 { margin: 0; font-size: 24px; }
+h2 { margin-top: 32px; border-bottom: 1px solid #d2d2d7; padding-bottom: 4px; }
+h3 { margin-bottom: 4px; }
+table { border-collapse: collapse; width: 100%; margin: 8px 0; }
+th, td { text-align: left; padding: 4px 8px; border-bottom: 1px solid #e5e5ea; vertical-align: top; }
+th { background: #f5f5f7; font-weight: 600; }
+td.size { text-align: right; white-space: nowrap; }
+td.path { font-family: ui-monospace, Menlo, monospace; font-size: 12px; word-break: break-all; }
+.muted { color: #6e6e73; }
+.failed { color: #c9302c; }
+";
+
+impl ScanReport {
+    /// Returns the report as a self-contained HTML document.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let name = escape_html(self.as_metadata().as_name());
+
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{name} uninstall report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n"
+        );
+
+        // Header
+        html.push_str("<header>\n");
+        if let Some(uri) = self
+            .as_icon()
+            .and_then(|(width, height, rgba)| png_data_uri(*width, *height, rgba))
+        {
+            let _ = writeln!(html, "<img src=\"{uri}\" alt=\"\">");
+        }
+        let _ = writeln!(
+            html,
+            "<div><h1>{name}</h1><div class=\"muted\">Uninstall report</div></div>\n</header>"
+        );
+
+        // Application
+        html.push_str("<h2>Application</h2>\n<table>\n");
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td>{}</td></tr>",
+                label,
+                escape_html(&value)
+            );
+        }
+        html.push_str("</table>\n");
+
+        // Size breakdown
+        let breakdown = self.as_size_breakdown();
+        let _ = writeln!(
+            html,
+            "<h2>Size breakdown</h2>\n<p class=\"muted\">Bundle {}, leftovers {}.</p>",
+            format_size(breakdown.bundle_size()),
+            format_size(breakdown.leftover_size())
+        );
+        for (title, rows) in self.size_breakdown_tables() {
+            if rows.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(html, "<h3>{title}</h3>\n<table>");
+            for (label, size) in rows {
+                let _ = writeln!(
+                    html,
+                    "<tr><td class=\"path\">{}</td><td class=\"size\">{}</td></tr>",
+                    escape_html(&label),
+                    format_size(size)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Processes
+        html.push_str("<h2>Processes</h2>\n");
+        if self.as_processes().is_empty() {
+            html.push_str("<p class=\"muted\">None.</p>\n");
+        } else {
+            html.push_str(
+                "<table>\n<tr><th>PID</th><th>Name</th><th>Match reason</th><th>Confidence</th></tr>\n",
+            );
+            for process in self.as_processes() {
+                let reason = process.match_reason();
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    process.pid(),
+                    escape_html(process.as_name()),
+                    reason.as_str(),
+                    reason.confidence().as_str()
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Discovered paths
+        let _ = writeln!(
+            html,
+            "<h2>Discovered paths</h2>\n<p class=\"muted\">{} selected for removal.</p>",
+            format_size(self.removable_size())
+        );
+        for category in PathCategory::ALL {
+            let paths = self.paths_in(category);
+            if paths.is_empty() {
+                continue;
+            }
+
+            let total: u64 = paths.iter().map(|path| path.size()).sum();
+            let kept = if category.is_removed() { "" } else { ", kept" };
+
+            let _ = writeln!(
+                html,
+                "<h3>{} ({}, {}{})</h3>\n<div class=\"muted\">{}</div>",
+                category.as_str(),
+                paths.len(),
+                format_size(total),
+                kept,
+                category.match_reason()
+            );
+            html.push_str(
+                "<table>\n<tr><th>Name</th><th>Path</th><th>Match reason</th><th>Size</th></tr>\n",
+            );
+            for path in paths {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td class=\"path\">{}</td><td>{}</td><td class=\"size\">{}</td></tr>",
+                    escape_html(path.as_path_data().as_name()),
+                    escape_html(&path.as_path_data().to_string()),
+                    escape_html(path.match_reason()),
+                    format_size(path.size())
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Moved to Trash
+        let trash_entry = self.as_trash_entry();
+        html.push_str("<h2>Moved to Trash</h2>\n");
+        if trash_entry.moved_path().is_empty() {
+            html.push_str("<p class=\"muted\">None.</p>\n");
+        } else {
+            html.push_str("<table>\n<tr><th>Path</th><th>Trash location</th></tr>\n");
+            for item in trash_entry.moved_path() {
+                let _ = writeln!(
+                    html,
+                    "<tr><td class=\"path\">{}</td><td class=\"path\">{}</td></tr>",
+                    escape_html(&item.as_source_path().to_string()),
+                    escape_html(&item.as_trashed_path().display().to_string())
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Failures
+        html.push_str("<h2>Failures</h2>\n");
+        if trash_entry.failed_path().is_empty() {
+            html.push_str("<p class=\"muted\">None.</p>\n");
+        } else {
+            html.push_str(
+                "<table>\n<tr><th>Path</th><th>Result</th><th>Summary</th><th>Reason</th></tr>\n",
+            );
+            for (path, error) in trash_entry.failed_path() {
+                let _ = writeln!(
+                    html,
+                    "<tr><td class=\"path\">{}</td><td class=\"failed\">{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&path.to_string()),
+                    error.kind().as_str(),
+                    escape_html(error.summary().unwrap_or_default()),
+                    escape_html(error.reason().unwrap_or_default())
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Escapes text for HTML element content and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,160 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Inline images for self-contained reports.
+//!
+//! RGBA icons, as stored by `IconCache`, are encoded as PNG and
+//! embedded as `data:` URIs, so a report needs no files next to
+//! it.
+//!
+//! Design:
+//! Pixels are written into stored (uncompressed) deflate blocks.
+//! Application icons are small, so a compressor is not worth a
+//! dependency.
+//!..
+
+/// PNG file signature.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Largest payload of a stored deflate block.
+const STORED_BLOCK_LEN: usize = 0xffff;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns a `data:image/png;base64,..` URI for RGBA pixels.
+///
+/// Note:
+/// Returns `None` when the buffer does not hold
+/// `width * height` pixels.
+pub(crate) fn png_data_uri(width: usize, height: usize, rgba: &[u8]) -> Option<String> {
+    let png = encode_png(width, height, rgba)?;
+
+    Some(format!("data:image/png;base64,{}", encode_base64(&png)))
+}
+
+/// Encodes RGBA pixels as an 8-bit truecolor PNG with alpha.
+pub(crate) fn encode_png(width: usize, height: usize, rgba: &[u8]) -> Option<Vec<u8>> {
+    if width == 0 || height == 0 || rgba.len() != width * height * 4 {
+        return None;
+    }
+
+    let width_be = u32::try_from(width).ok()?.to_be_bytes();
+    let height_be = u32::try_from(height).ok()?.to_be_bytes();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width_be);
+    header.extend_from_slice(&height_be);
+    // Bit depth 8, color type 6 (RGBA), default compression,
+    // filter and interlace methods.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    // Every scanline starts with filter type 0 (None).
+    let mut scanlines = Vec::with_capacity(rgba.len() + height);
+    for row in rgba.chunks_exact(width * 4) {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+    write_chunk(&mut png, b"IEND", &[]);
+
+    Some(png)
+}
+
+/// Encodes bytes as standard padded base64.
+pub(crate) fn encode_base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data into a zlib stream of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(STORED_BLOCK_LEN).collect();
+
+    for (index, block) in blocks.iter().enumerate() {
+        let is_final = index + 1 == blocks.len();
+        let len = block.len() as u16;
+
+        stream.push(u8::from(is_final));
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
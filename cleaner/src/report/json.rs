@@ -0,0 +1,208 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! JSON export of a `ScanReport`.
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "generated_at": 1760000000,
+//!   "application": { "name": "Cafe", "bundle_id": "com.example.cafe", .. },
+//!   "processes": [ { "pid": 412, "match_reason": "Executable inside bundle", .. } ],
+//!   "paths": [ { "category": "Associated files", "path": "..", "size": 5120, .. } ],
+//!   "size_breakdown": { "bundle_size": 52428800, "sections": [ .. ], "leftovers": [ .. ], .. },
+//!   "trash": { "moved": [ .. ], "failed": [ .. ] }
+//! }
+//! ```
+//!
+//! Note:
+//! Paths are written as absolute paths, never with a `~`
+//! prefix. Bytes that are not valid UTF-8 are replaced.
+//!..
+
+use serde_json::{Value, json};
+
+use crate::app_profile::{Metadata, ProcessData, SizeBreakdown};
+use crate::errors::ErrorKind;
+use crate::path_data::trash_entry::TrashEntry;
+use crate::report::scan_report::{REPORT_VERSION, ReportPath, ScanReport};
+
+impl ScanReport {
+    /// Returns the report as a JSON document.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "version": REPORT_VERSION,
+            "generated_at": self.generated_at(),
+            "application": metadata_json(self.as_metadata()),
+            "processes": self.as_processes().iter().map(process_json).collect::<Vec<_>>(),
+            "paths": self.as_paths().iter().map(path_json).collect::<Vec<_>>(),
+            "size_breakdown": size_breakdown_json(self.as_size_breakdown()),
+            "trash": trash_json(self.as_trash_entry()),
+        })
+    }
+
+    /// Returns the report as pretty-printed JSON text.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json()).unwrap_or_default()
+    }
+}
+
+fn metadata_json(metadata: &Metadata) -> Value {
+    let signing = metadata.as_signing_info().map(|info| {
+        json!({
+            "summary": info.to_string(),
+            "kind": info.kind().as_str(),
+            "identifier": info.as_identifier(),
+            "team_identifier": info.as_team_identifier(),
+            "hardened_runtime": info.is_hardened_runtime(),
+        })
+    });
+
+    let nested_bundles: Vec<Value> = metadata
+        .as_nested_bundles()
+        .iter()
+        .map(|nested| {
+            json!({
+                "path": nested.as_path().to_string_lossy(),
+                "bundle_id": nested.as_bundle_id(),
+                "kind": nested.kind().as_str(),
+            })
+        })
+        .collect();
+
+    let external_libraries: Vec<String> = metadata
+        .as_external_libraries()
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+    json!({
+        "name": metadata.as_name(),
+        "bundle_path": metadata.as_bundle_path().to_string_lossy(),
+        "bundle_id": metadata.as_bundle_id(),
+        "executable": metadata.as_bundle_executable_name(),
+        "version": metadata.as_short_version(),
+        "build": metadata.as_bundle_version(),
+        "minimum_system_version": metadata.as_minimum_system_version(),
+        "team_identifier": metadata.as_team_identifier(),
+        "signing": signing,
+        "application_groups": metadata.as_application_groups(),
+        "nested_bundles": nested_bundles,
+        "external_libraries": external_libraries,
+    })
+}
+
+fn process_json(process: &ProcessData) -> Value {
+    let reason = process.match_reason();
+
+    json!({
+        "pid": process.pid(),
+        "parent_pid": process.parent_pid(),
+        "name": process.as_name(),
+        "command": process.as_command(),
+        "executable": process.as_exe().map(|exe| exe.to_string_lossy()),
+        "user": process.as_user(),
+        "match_reason": reason.as_str(),
+        "confidence": reason.confidence().as_str(),
+    })
+}
+
+fn path_json(path: &ReportPath) -> Value {
+    json!({
+        "category": path.category().as_str(),
+        "path": path.as_path_data().as_path().to_string_lossy(),
+        "name": path.as_path_data().as_name(),
+        "size": path.size(),
+        "match_reason": path.match_reason(),
+        "removed": path.category().is_removed(),
+    })
+}
+
+fn size_breakdown_json(breakdown: &SizeBreakdown) -> Value {
+    let sections: Vec<Value> = breakdown
+        .as_sections()
+        .iter()
+        .map(|(section, size)| json!({ "section": section.as_str(), "size": size }))
+        .collect();
+
+    let architectures: Vec<Value> = breakdown
+        .as_architectures()
+        .iter()
+        .map(|(arch, size)| json!({ "architecture": arch, "size": size }))
+        .collect();
+
+    let largest_files: Vec<Value> = breakdown
+        .as_largest_files()
+        .iter()
+        .map(|(path, size)| {
+            json!({
+                "path": path.as_path().to_string_lossy(),
+                "size": size,
+            })
+        })
+        .collect();
+
+    let leftovers: Vec<Value> = breakdown
+        .as_leftovers()
+        .iter()
+        .map(|(category, size)| json!({ "category": category.as_str(), "size": size }))
+        .collect();
+
+    json!({
+        "bundle_size": breakdown.bundle_size(),
+        "sections": sections,
+        "architectures": architectures,
+        "largest_files": largest_files,
+        "leftovers": leftovers,
+        "leftover_size": breakdown.leftover_size(),
+    })
+}
+
+fn trash_json(trash_entry: &TrashEntry) -> Value {
+    let moved: Vec<Value> = trash_entry
+        .moved_path()
+        .iter()
+        .map(|item| {
+            json!({
+                "path": item.as_source_path().as_path().to_string_lossy(),
+                "trashed_path": item.as_trashed_path().to_string_lossy(),
+            })
+        })
+        .collect();
+
+    let failed: Vec<Value> = trash_entry
+        .failed_path()
+        .iter()
+        .map(|(path, error)| {
+            json!({
+                "path": path.as_path().to_string_lossy(),
+                "error": error_json(error),
+            })
+        })
+        .collect();
+
+    json!({
+        "moved": moved,
+        "failed": failed,
+    })
+}
+
+fn error_json(error: &ErrorKind) -> Value {
+    json!({
+        "kind": error.kind().as_str(),
+        "summary": error.summary(),
+        "reason": error.reason(),
+    })
+}
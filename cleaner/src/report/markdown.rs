@@ -0,0 +1,209 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Markdown export of a `ScanReport`.
+//!
+//! Renders the same sections as `to_html()` using GitHub
+//! flavored Markdown tables.
+//!
+//! Note:
+//! The application icon is not part of the Markdown document,
+//! since many renderers refuse `data:` images.
+//!..
+
+use std::fmt::Write;
+
+use crate::app_profile::PathCategory;
+use crate::report::scan_report::ScanReport;
+use crate::utility::format_size;
+
+impl ScanReport {
+    /// Returns the report as a Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        let _ = writeln!(
+            md,
+            "# {} uninstall report\n",
+            escape_markdown(self.as_metadata().as_name())
+        );
+
+        // Application
+        md.push_str("## Application\n\n| | |\n|---|---|\n");
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(md, "| {} | {} |", label, escape_markdown(&value));
+        }
+
+        // Size breakdown
+        let breakdown = self.as_size_breakdown();
+        let _ = writeln!(
+            md,
+            "\n## Size breakdown\n\nBundle {}, leftovers {}.",
+            format_size(breakdown.bundle_size()),
+            format_size(breakdown.leftover_size())
+        );
+        for (title, rows) in self.size_breakdown_tables() {
+            if rows.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(md, "\n### {title}\n\n| | Size |\n|---|---:|");
+            for (label, size) in rows {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} |",
+                    escape_markdown(&label),
+                    format_size(size)
+                );
+            }
+        }
+
+        // Processes
+        md.push_str("\n## Processes\n\n");
+        if self.as_processes().is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str("| PID | Name | Match reason | Confidence |\n|---:|---|---|---|\n");
+            for process in self.as_processes() {
+                let reason = process.match_reason();
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    process.pid(),
+                    escape_markdown(process.as_name()),
+                    reason.as_str(),
+                    reason.confidence().as_str()
+                );
+            }
+        }
+
+        // Discovered paths
+        let _ = writeln!(
+            md,
+            "\n## Discovered paths\n\n{} selected for removal.",
+            format_size(self.removable_size())
+        );
+        for category in PathCategory::ALL {
+            let paths = self.paths_in(category);
+            if paths.is_empty() {
+                continue;
+            }
+
+            let total: u64 = paths.iter().map(|path| path.size()).sum();
+            let kept = if category.is_removed() { "" } else { ", kept" };
+
+            let _ = writeln!(
+                md,
+                "\n### {} ({}, {}{})\n\n{}\n\n| Name | Path | Match reason | Size |\n|---|---|---|---:|",
+                category.as_str(),
+                paths.len(),
+                format_size(total),
+                kept,
+                category.match_reason()
+            );
+            for path in paths {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    escape_markdown(path.as_path_data().as_name()),
+                    code_span(&path.as_path_data().to_string()),
+                    escape_markdown(path.match_reason()),
+                    format_size(path.size())
+                );
+            }
+        }
+
+        // Moved to Trash
+        let trash_entry = self.as_trash_entry();
+        md.push_str("\n## Moved to Trash\n\n");
+        if trash_entry.moved_path().is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str("| Path | Trash location |\n|---|---|\n");
+            for item in trash_entry.moved_path() {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} |",
+                    code_span(&item.as_source_path().to_string()),
+                    code_span(&item.as_trashed_path().display().to_string())
+                );
+            }
+        }
+
+        // Failures
+        md.push_str("\n## Failures\n\n");
+        if trash_entry.failed_path().is_empty() {
+            md.push_str("None.\n");
+        } else {
+            md.push_str("| Path | Result | Summary | Reason |\n|---|---|---|---|\n");
+            for (path, error) in trash_entry.failed_path() {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    code_span(&path.to_string()),
+                    error.kind().as_str(),
+                    escape_markdown(error.summary().unwrap_or_default()),
+                    escape_markdown(error.reason().unwrap_or_default())
+                );
+            }
+        }
+
+        md
+    }
+}
+
+/// Escapes text for a Markdown table cell.
+///
+/// Note:
+/// Line breaks would end the table row and are replaced with
+/// spaces.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Wraps text into a code span for a table cell.
+///
+/// Doc:
+/// The span is delimited by one more backtick than the longest
+/// run of backticks inside the text. Pipes are still escaped,
+/// since tables are split before code spans are parsed.
+fn code_span(text: &str) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run + 1);
+    let text = text.replace('|', "\\|").replace(['\r', '\n'], " ");
+
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Scan result reports.
+//!
+//! A `ScanReport` is a snapshot of everything `Cleaner` knows
+//! about an application:
+//!
+//! - Application metadata and code signature.
+//! - Matched processes and why they were matched.
+//! - Every discovered path with its category, size and match
+//!   reason.
+//! - The last trash operation, moved and failed paths included.
+//!
+//! Reports are exported as:
+//!
+//! - Versioned JSON, for audit tooling.
+//! - Flat CSV, one row per process, path and trash result, for
+//!   spreadsheets.
+//! - A self-contained HTML document, or Markdown, to attach to a
+//!   ticket.
+//! - A POSIX shell script reproducing the removal, to review and
+//!   run by hand.
+//!
+//! Design:
+//! Exporters render a `ScanReport` instead of reading `Cleaner`
+//! directly, so every format describes the same snapshot and
+//! sizes are only computed once.
+//!
+//! Note:
+//! `REPORT_VERSION` is raised whenever a field of the JSON
+//! document or a CSV column changes meaning or is removed.
+//!..
+
+mod csv;
+mod html;
+mod inline_image;
+mod json;
+mod markdown;
+mod scan_report;
+mod shell_script;
+
+pub use scan_report::{REPORT_VERSION, ReportPath, ScanReport};
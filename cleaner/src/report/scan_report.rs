@@ -0,0 +1,369 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app_profile::{AppProfile, Metadata, PathCategory, ProcessData, SizeBreakdown};
+use crate::errors::{ErrorKind, Result};
+use crate::path_data::PathData;
+use crate::path_data::trash_entry::TrashEntry;
+use crate::utility::{FileSystem, IconCache};
+
+/// Version of the report format.
+pub const REPORT_VERSION: u32 = 1;
+
+/// Discovered path of a report.
+///
+/// Doc:
+/// Stores the path, its category and its size in bytes. The
+/// match reason is the rule or origin recorded by the scanner,
+/// or the one of its category, see `PathCategory::match_reason()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportPath {
+    category: PathCategory,
+    path: PathData,
+    size: u64,
+}
+
+impl ReportPath {
+    pub fn new(category: PathCategory, path: PathData, size: u64) -> Self {
+        Self {
+            category,
+            path,
+            size,
+        }
+    }
+
+    /// get the copy of path category
+    pub fn category(&self) -> PathCategory {
+        self.category
+    }
+
+    /// get path reference
+    pub fn as_path_data(&self) -> &PathData {
+        &self.path
+    }
+
+    /// get the copy of size in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns why the path was attributed to the application.
+    ///
+    /// Note:
+    /// Falls back to the category reason when the scanner did not
+    /// record the rule or origin that matched the path.
+    pub fn match_reason(&self) -> &str {
+        self.path
+            .as_reason()
+            .unwrap_or_else(|| self.category.match_reason())
+    }
+}
+
+/// Snapshot of a scan result.
+///
+/// Doc:
+/// Built from an `AppProfile` and the last `TrashEntry`:
+///
+/// ```text
+/// AppProfile ──┐              ┌─ to_json()
+///              ├─ ScanReport ─┼─ to_csv()
+/// TrashEntry ──┘              ├─ to_html()
+///                             ├─ to_markdown()
+///                             └─ to_shell_script()
+/// ```
+///
+/// The application icon is optional and only rendered by
+/// `to_html()`. The `SizeBreakdown` is rendered by `to_json()`,
+/// `to_html()` and `to_markdown()`.
+///
+/// Note:
+/// Paths moved to Trash are no longer stored in `PathEntry`, so
+/// after `Cleaner::move_to_trash()` they only appear in the
+/// trash section of the report.
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    generated_at: u64,
+    metadata: Metadata,
+    processes: Vec<ProcessData>,
+    paths: Vec<ReportPath>,
+    size_breakdown: SizeBreakdown,
+    trash_entry: TrashEntry,
+    icon: Option<(usize, usize, Vec<u8>)>,
+}
+
+impl ScanReport {
+    /// Builds a report, reading path sizes through the provided
+    /// `FileSystem`.
+    pub fn new_with<FS>(fs: &FS, app_profile: &AppProfile, trash_entry: &TrashEntry) -> Self
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let paths = app_profile
+            .as_path_entry()
+            .categorized_paths()
+            .into_iter()
+            .map(|(category, path)| {
+                let size = fs.total_size(path.as_path());
+                ReportPath::new(category, path, size)
+            })
+            .collect();
+
+        Self {
+            generated_at,
+            metadata: app_profile.as_metadata().clone(),
+            processes: app_profile.as_process_entry().list().to_vec(),
+            paths,
+            size_breakdown: app_profile.size_breakdown_with(fs),
+            trash_entry: trash_entry.clone(),
+            icon: None,
+        }
+    }
+
+    /// Attaches the application icon from an `IconCache`.
+    ///
+    /// Note:
+    /// The report is left without an icon when the cache holds
+    /// none for the application bundle.
+    pub fn with_icon(mut self, icon_cache: &IconCache) -> Self {
+        let bundle_path = self.metadata.as_bundle_path();
+
+        self.icon = match (
+            icon_cache.width(bundle_path),
+            icon_cache.height(bundle_path),
+            icon_cache.rgba_bytes(bundle_path),
+        ) {
+            (Some(width), Some(height), Some(rgba)) => Some((width, height, rgba.to_vec())),
+            _ => None,
+        };
+
+        self
+    }
+
+    /// get the copy of generation time in seconds since the Unix epoch
+    pub fn generated_at(&self) -> u64 {
+        self.generated_at
+    }
+
+    /// Returns the generation time as a UTC date:
+    ///
+    /// ```text
+    /// 2026-10-19 14:05 UTC
+    /// ```
+    pub fn generated_at_utc(&self) -> String {
+        let days = (self.generated_at / 86_400) as i64;
+        let minutes = (self.generated_at % 86_400) / 60;
+
+        // Civil date from days since 1970-01-01, proleptic
+        // Gregorian calendar.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+
+    /// get metadata reference
+    pub fn as_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// get processes reference
+    pub fn as_processes(&self) -> &[ProcessData] {
+        &self.processes
+    }
+
+    /// get paths reference
+    pub fn as_paths(&self) -> &[ReportPath] {
+        &self.paths
+    }
+
+    /// get size breakdown reference
+    pub fn as_size_breakdown(&self) -> &SizeBreakdown {
+        &self.size_breakdown
+    }
+
+    /// get trash entry reference
+    pub fn as_trash_entry(&self) -> &TrashEntry {
+        &self.trash_entry
+    }
+
+    /// get icon width, height and RGBA pixels reference
+    pub fn as_icon(&self) -> Option<&(usize, usize, Vec<u8>)> {
+        self.icon.as_ref()
+    }
+
+    /// Returns the paths of a category.
+    pub fn paths_in(&self, category: PathCategory) -> Vec<&ReportPath> {
+        self.paths
+            .iter()
+            .filter(|path| path.category == category)
+            .collect()
+    }
+
+    /// Returns the total size of the paths that would be moved
+    /// to Trash.
+    ///
+    /// Note:
+    /// Report-only categories are not counted.
+    pub fn removable_size(&self) -> u64 {
+        self.paths
+            .iter()
+            .filter(|path| path.category.is_removed())
+            .map(|path| path.size)
+            .sum()
+    }
+
+    /// Returns the size breakdown as titled tables of label and
+    /// size rows for rendered reports:
+    ///
+    /// ```text
+    /// Bundle sections  Frameworks       31.2 MB
+    /// Architectures    arm64            12.4 MB
+    /// Largest files    /Applications/.. 20.1 MB
+    /// Leftovers        Associated files  5.0 KB
+    /// ```
+    pub(crate) fn size_breakdown_tables(&self) -> Vec<(&'static str, Vec<(String, u64)>)> {
+        let breakdown = &self.size_breakdown;
+
+        vec![
+            (
+                "Bundle sections",
+                breakdown
+                    .as_sections()
+                    .iter()
+                    .map(|(section, size)| (section.as_str().to_string(), *size))
+                    .collect(),
+            ),
+            (
+                "Architectures",
+                breakdown
+                    .as_architectures()
+                    .iter()
+                    .map(|(arch, size)| (arch.to_string(), *size))
+                    .collect(),
+            ),
+            (
+                "Largest files",
+                breakdown
+                    .as_largest_files()
+                    .iter()
+                    .map(|(path, size)| (path.to_string(), *size))
+                    .collect(),
+            ),
+            (
+                "Leftovers",
+                breakdown
+                    .as_leftovers()
+                    .iter()
+                    .map(|(category, size)| (category.as_str().to_string(), *size))
+                    .collect(),
+            ),
+        ]
+    }
+
+    /// Returns the application details shown at the top of
+    /// rendered reports, empty values omitted.
+    pub(crate) fn summary_rows(&self) -> Vec<(&'static str, String)> {
+        let metadata = &self.metadata;
+        let signature = metadata
+            .as_signing_info()
+            .map(|info| info.to_string())
+            .unwrap_or_default();
+
+        [
+            (
+                "Bundle path",
+                metadata.as_bundle_path().display().to_string(),
+            ),
+            ("Bundle identifier", metadata.as_bundle_id().to_string()),
+            ("Version", metadata.as_version_string()),
+            (
+                "Minimum macOS",
+                metadata.as_minimum_system_version().to_string(),
+            ),
+            ("Team identifier", metadata.as_team_identifier().to_string()),
+            ("Signature", signature),
+            ("Generated", self.generated_at_utc()),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
+
+    /// Writes the JSON document into a file.
+    pub fn save_json(&self, file: &Path) -> Result<()> {
+        Self::write_report(file, &self.to_json_string())
+    }
+
+    /// Writes the CSV table into a file.
+    pub fn save_csv(&self, file: &Path) -> Result<()> {
+        Self::write_report(file, &self.to_csv())
+    }
+
+    /// Writes the HTML document into a file.
+    pub fn save_html(&self, file: &Path) -> Result<()> {
+        Self::write_report(file, &self.to_html())
+    }
+
+    /// Writes the Markdown document into a file.
+    pub fn save_markdown(&self, file: &Path) -> Result<()> {
+        Self::write_report(file, &self.to_markdown())
+    }
+
+    /// Writes the removal script into a file and makes it
+    /// executable.
+    pub fn save_shell_script(&self, file: &Path, unload_jobs: bool) -> Result<()> {
+        Self::write_report(file, &self.to_shell_script(unload_jobs))?;
+
+        std::fs::set_permissions(file, std::fs::Permissions::from_mode(0o755)).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to save report")
+                .with_reason(format!(
+                    "Failed to make {} executable: {}",
+                    file.display(),
+                    e
+                ))
+        })
+    }
+
+    pub(crate) fn write_report(file: &Path, contents: &str) -> Result<()> {
+        std::fs::write(file, contents).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to save report")
+                .with_reason(format!("Failed to write {}: {}", file.display(), e))
+        })
+    }
+}
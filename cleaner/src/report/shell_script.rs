@@ -0,0 +1,376 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Shell script export of a `ScanReport`.
+//!
+//! Produces a POSIX `sh` script that reproduces the removal
+//! performed by `Cleaner::move_to_trash()`, for administrators
+//! who prefer to review and run the commands themselves:
+//!
+//! 1. Stop matched processes, deepest in the process tree first.
+//! 2. Unload launchd jobs defined by background task files.
+//!    Loaded system jobs fail the run without sudo.
+//! 3. Move every path to a quarantine folder with `mv`, in
+//!    `PathCategory::REMOVAL_ORDER`. Receipts are forgotten with
+//!    `pkgutil --forget` instead. A path found in several
+//!    categories is moved once.
+//! 4. Move the application bundle, only when every other path
+//!    was moved.
+//!
+//! ```text
+//! sh remove-cafe.sh --dry-run
+//! sudo sh remove-cafe.sh
+//! ```
+//!
+//! Design:
+//! Items are moved rather than deleted. The quarantine folder
+//! mirrors their absolute paths, so each one can be put back by
+//! hand. The folder defaults to a location in the Trash and can
+//! be overridden with the `QUARANTINE` environment variable.
+//!
+//! Process IDs may be reused by the time the script runs, so a
+//! process is only signaled while its command still matches the
+//! recorded process name.
+//!
+//! Note:
+//! Paths are single-quoted. Bytes that are not valid UTF-8 are
+//! replaced and such paths will not be found by the script.
+//!..
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::app_profile::PathCategory;
+use crate::control::{ServiceDomain, ServiceJob, termination_order};
+use crate::report::scan_report::{ReportPath, ScanReport};
+use crate::utility::format_size;
+
+/// Helper functions shared by every generated script.
+const PRELUDE: &str = r#"set -u
+
+DRY_RUN=0
+case "${1:-}" in
+    --dry-run | -n) DRY_RUN=1 ;;
+    "") ;;
+    *)
+        echo "usage: $0 [--dry-run]" >&2
+        exit 2
+        ;;
+esac
+
+FAILED=0
+
+# Prints the command instead of running it with --dry-run.
+run() {
+    if [ "$DRY_RUN" -eq 1 ]; then
+        printf '[dry-run] %s' "$1"
+        shift
+        [ "$#" -gt 0 ] && printf " '%s'" "$@"
+        printf '\n'
+        return 0
+    fi
+
+    "$@"
+}
+
+# stop_process PID NAME
+# Sends SIGTERM, then SIGKILL after five seconds. Skipped when the
+# PID now belongs to another command.
+stop_process() {
+    current=$(ps -p "$1" -o comm= 2>/dev/null) || {
+        echo "not running: PID $1 ($2)"
+        return 0
+    }
+
+    case "$current" in
+        *"$2"*) ;;
+        *)
+            echo "skipped: PID $1 is now $current, expected $2"
+            return 0
+            ;;
+    esac
+
+    run kill -TERM "$1" || return 0
+    [ "$DRY_RUN" -eq 1 ] && return 0
+
+    waited=0
+    while kill -0 "$1" 2>/dev/null && [ "$waited" -lt 5 ]; do
+        sleep 1
+        waited=$((waited + 1))
+    done
+
+    if kill -0 "$1" 2>/dev/null; then
+        kill -KILL "$1" || echo "failed to stop PID $1 ($2)" >&2
+    fi
+}
+
+# unload_job gui|system LABEL PLIST
+# A loaded system job needs root to be unloaded, and is reported
+# as a failure when the script runs without sudo. Agents belong to
+# the user who ran sudo, not to root.
+unload_job() {
+    if [ "$1" = system ]; then
+        target="system/$2"
+        if [ "$(id -u)" -ne 0 ] && launchctl print "$target" >/dev/null 2>&1; then
+            echo "failed to unload: $2 (run with sudo)" >&2
+            FAILED=1
+            return 0
+        fi
+    else
+        target="gui/${SUDO_UID:-$(id -u)}/$2"
+    fi
+
+    run launchctl bootout "$target" 2>/dev/null ||
+        run launchctl unload "$3" 2>/dev/null ||
+        echo "not loaded: $2"
+}
+
+# quarantine PATH
+# Moves PATH below $QUARANTINE, keeping its absolute path.
+quarantine() {
+    if [ ! -e "$1" ] && [ ! -L "$1" ]; then
+        echo "missing: $1"
+        return 0
+    fi
+
+    if run mkdir -p "$QUARANTINE$(dirname "$1")" && run mv "$1" "$QUARANTINE$1"; then
+        [ "$DRY_RUN" -eq 1 ] || echo "moved: $1"
+    else
+        echo "failed to move: $1" >&2
+        FAILED=1
+    fi
+}
+
+# forget_receipt PACKAGE_ID BOM
+forget_receipt() {
+    run pkgutil --forget "$1" || quarantine "$2"
+}
+"#;
+
+impl ScanReport {
+    /// Returns a POSIX shell script reproducing the removal.
+    ///
+    /// Doc:
+    /// launchd jobs are only unloaded when `unload_jobs` is set,
+    /// matching `Cleaner::set_skip_service_unload()`.
+    pub fn to_shell_script(&self, unload_jobs: bool) -> String {
+        let metadata = self.as_metadata();
+        let mut script = String::new();
+
+        let _ = writeln!(
+            script,
+            "#!/bin/sh\n#\n# Removal script for {} ({}).\n# Generated on {} from {}.\n#",
+            comment(metadata.as_name()),
+            comment(metadata.as_bundle_id()),
+            self.generated_at_utc(),
+            comment(&metadata.as_bundle_path().display().to_string())
+        );
+        script.push_str(
+            "# Review every command before running it. Nothing is deleted: items are\n\
+             # moved below $QUARANTINE, keeping their absolute paths.\n\
+             #\n\
+             #   sh script.sh --dry-run    print the commands only\n\
+             #   sudo sh script.sh         system items need administrator rights\n\n",
+        );
+        script.push_str(PRELUDE);
+
+        let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+        let folder = format!(
+            "{} removal {}",
+            metadata.as_name().replace('/', "-"),
+            self.generated_at_utc().replace(':', "")
+        );
+        let _ = writeln!(
+            script,
+            "\nif [ -z \"${{QUARANTINE:-}}\" ]; then\n    QUARANTINE={}\nfi",
+            quote(&format!("{}/.Trash/{}", home, folder))
+        );
+
+        // Processes
+        script.push_str("\n# ==================== Processes ====================\n");
+        for process in termination_order(self.as_processes()).into_iter().flatten() {
+            let reason = process.match_reason();
+            // Low confidence matches are left for the reviewer to
+            // enable, as `Cleaner::kill_app_process()` does.
+            let disabled = match process.requires_confirmation() {
+                true => "# Matched by name only, uncomment to stop it.\n# ",
+                false => "",
+            };
+            let _ = writeln!(
+                script,
+                "# Process {} · {} ({} confidence)\n{}stop_process {} {}",
+                comment(process.as_name()),
+                reason.as_str(),
+                reason.confidence().as_str(),
+                disabled,
+                process.pid(),
+                quote(process.as_name())
+            );
+        }
+
+        // launchd jobs
+        script.push_str("\n# ==================== launchd jobs ====================\n");
+        if unload_jobs {
+            for job in self
+                .paths_in(PathCategory::BackgroundTasks)
+                .into_iter()
+                .filter_map(|path| ServiceJob::from_path(path.as_path_data()))
+            {
+                let domain = match job.domain() {
+                    ServiceDomain::Gui => "gui",
+                    ServiceDomain::System => "system",
+                };
+
+                let _ = writeln!(
+                    script,
+                    "# {} · {}\nunload_job {} {} {}",
+                    job.domain().as_str(),
+                    comment(job.as_label()),
+                    domain,
+                    quote(job.as_label()),
+                    quote(&job.as_plist().as_path().to_string_lossy())
+                );
+            }
+        } else {
+            script.push_str("# Skipped.\n");
+        }
+
+        // Paths found by several scanners are removed once, in
+        // their first category.
+        let mut seen: HashSet<&Path> = HashSet::new();
+
+        for category in PathCategory::REMOVAL_ORDER {
+            let paths: Vec<&ReportPath> = self
+                .paths_in(category)
+                .into_iter()
+                .filter(|path| seen.insert(path.as_path_data().as_path()))
+                .collect();
+            if paths.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(
+                script,
+                "\n# ==================== {} ====================",
+                category.as_str()
+            );
+            for path in paths {
+                let _ = writeln!(script, "{}\n{}", path_comment(path), path_command(path));
+            }
+        }
+
+        // Application bundle
+        script.push_str("\n# ==================== Application ====================\n");
+        script.push_str("# Moved only when every item above was moved.\n");
+        for path in self.paths_in(PathCategory::Application) {
+            let skipped = format!(
+                "skipped: {} because some associated files failed to move",
+                path.as_path_data().as_path().to_string_lossy()
+            );
+
+            let _ = writeln!(
+                script,
+                "{}\nif [ \"$FAILED\" -eq 0 ]; then\n    {}\nelse\n    echo {}\nfi",
+                path_comment(path),
+                path_command(path),
+                quote(&skipped)
+            );
+        }
+
+        // Shared libraries
+        let shared = self.paths_in(PathCategory::SharedLibraries);
+        if !shared.is_empty() {
+            script.push_str("\n# Kept, possibly used by other software:\n");
+            for path in shared {
+                let _ = writeln!(
+                    script,
+                    "#   {}",
+                    comment(&path.as_path_data().as_path().to_string_lossy())
+                );
+            }
+        }
+
+        // Origin files
+        let origin = self.paths_in(PathCategory::OriginFiles);
+        if !origin.is_empty() {
+            script.push_str("\n# Kept, only their origin attributes name the application:\n");
+            for path in origin {
+                let _ = writeln!(
+                    script,
+                    "#   {}",
+                    comment(&path.as_path_data().as_path().to_string_lossy())
+                );
+            }
+        }
+
+        script.push_str("\nexit \"$FAILED\"\n");
+        script
+    }
+}
+
+/// Returns the comment lines explaining a path.
+///
+/// ```text
+/// # com.example.cafe · Associated files · 2.0 KB
+/// # Name or identifier matches the application
+/// ```
+fn path_comment(path: &ReportPath) -> String {
+    format!(
+        "# {} · {} · {}\n# {}",
+        comment(path.as_path_data().as_name()),
+        path.category().as_str(),
+        format_size(path.size()),
+        comment(path.match_reason())
+    )
+}
+
+/// Returns the command removing a path.
+///
+/// Doc:
+/// Receipts are named after their package identifier, such as
+/// `com.example.cafe.pkg.bom`, and are forgotten through
+/// `pkgutil`. Every other path is quarantined.
+fn path_command(path: &ReportPath) -> String {
+    let file = path.as_path_data().as_path();
+    let quoted = quote(&file.to_string_lossy());
+
+    match (path.category(), file.file_stem()) {
+        (PathCategory::Receipts, Some(package_id)) => format!(
+            "forget_receipt {} {}",
+            quote(&package_id.to_string_lossy()),
+            quoted
+        ),
+        _ => format!("quarantine {}", quoted),
+    }
+}
+
+/// Quotes a word for `sh`.
+///
+/// Doc:
+/// The word is wrapped in single quotes, inside which nothing is
+/// expanded. Single quotes are closed, escaped and reopened:
+///
+/// ```text
+/// Bob's App -> 'Bob'\''s App'
+/// ```
+fn quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+/// Keeps text on a single comment line.
+fn comment(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
@@ -24,10 +24,12 @@
 //! Responsibilities include:
 //!
 //! - Process management.
+//! - launchd job management.
 //! - Finder integration.
 //! - Trash operations.
 //! - BOM inspection.
 //! - System path resolution.
+//! - Extended attribute access.
 //! - Native icon retrieval.
 //! - Image conversion utilities.
 //!
@@ -63,6 +65,7 @@
 mod sys_asset;
 mod sys_component;
 mod sys_utility;
+mod sys_xattr;
 
 // pub use sys_access::run_cmd_as_root;
 pub use sys_asset::{
@@ -70,7 +73,8 @@ pub use sys_asset::{
     ns_image_to_rgba_bytes,
 };
 pub use sys_component::{
-    DARWIN_USER_CACHE_DIR, DARWIN_USER_TEMP_DIR, kill_pid, show_in_finder, sysconf_path,
-    trash_files_nsfilemanager,
+    DARWIN_USER_CACHE_DIR, DARWIN_USER_TEMP_DIR, ProcessSignal, SignalDelivery, current_uid,
+    is_privileged, send_signal, show_in_finder, sysconf_path, trash_files_nsfilemanager,
 };
-pub use sys_utility::run_lsbom_command;
+pub use sys_utility::{run_launchctl_command, run_lsbom_command};
+pub use sys_xattr::{get_xattr, set_xattr};
@@ -21,6 +21,7 @@
 //! Responsibilities include:
 //!
 //! - Process termination.
+//! - User identity queries.
 //! - Trash operations.
 //! - Finder integration.
 //! - User cache discovery.
@@ -43,6 +44,7 @@ use objc2_app_kit::NSWorkspace;
 use objc2_foundation::{NSArray, NSAutoreleasePool, NSFileManager, NSString, NSURL};
 
 // ============
+use libc::SIGKILL;
 use libc::SIGTERM;
 use libc::confstr;
 use libc::kill;
@@ -102,33 +104,77 @@ pub fn sysconf_path(name: i32) -> Option<PathBuf> {
     Some(PathBuf::from(OsStr::from_bytes(&trimmed_bytes[..end])))
 }
 
-/// Terminates one or more processes.
+/// Returns the real user identifier of the current process.
 ///
 /// Doc:
-/// Sends `SIGTERM` to each provided process identifier.
+/// Used to address per-user launchd domains such as
+/// `gui/<uid>`.
+pub fn current_uid() -> u32 {
+    unsafe { libc::getuid() }
+}
+
+/// Returns whether the current process runs with root privileges.
 ///
-/// Design:
-/// `SIGTERM` is used instead of stronger termination signals
-/// because it allows applications an opportunity to perform
-/// graceful shutdown and cleanup operations.
+/// Note:
+/// The effective user identifier is checked, so a process
+/// started through `sudo` is considered privileged.
+pub fn is_privileged() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Signal sent to a process.
+///
+/// Doc:
+/// - `Terminate` sends `SIGTERM`, allowing a graceful shutdown.
+/// - `Kill` sends `SIGKILL`, which cannot be caught or ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSignal {
+    Terminate,
+    Kill,
+}
+
+/// Delivery result of a process signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalDelivery {
+    Sent,
+    NotFound,
+    PermissionDenied,
+}
+
+/// Sends a signal to a process.
 ///
-/// Failures are accumulated and returned as a single error.
+/// Doc:
+/// Delivers `SIGTERM` or `SIGKILL` to the process. Waiting for
+/// the process to exit is left to the caller.
+///
+/// Design:
+/// `ESRCH` and `EPERM` are expected results when terminating
+/// processes discovered in an earlier snapshot, and are
+/// therefore reported as `SignalDelivery` values rather than
+/// errors.
 ///
 /// Note:
-/// Processes that have already exited are ignored.
-pub fn kill_pid(pid: i32) -> Result<()> {
-    let ret = unsafe { kill(pid, SIGTERM) };
+/// Any other failure is returned as an error.
+pub fn send_signal(pid: i32, signal: ProcessSignal) -> Result<SignalDelivery> {
+    let signal_number = match signal {
+        ProcessSignal::Terminate => SIGTERM,
+        ProcessSignal::Kill => SIGKILL,
+    };
+
+    let ret = unsafe { kill(pid, signal_number) };
 
     if ret == 0 {
-        return Ok(());
+        return Ok(SignalDelivery::Sent);
     }
 
     let err = std::io::Error::last_os_error();
 
-    if err.raw_os_error() == Some(libc::ESRCH) {
-        Err(ErrorKind::skipped().with_reason("Process already exited"))
-    } else {
-        Err(ErrorKind::failed().with_reason(err.to_string()))
+    match err.raw_os_error() {
+        Some(libc::ESRCH) => Ok(SignalDelivery::NotFound),
+        Some(libc::EPERM) => Ok(SignalDelivery::PermissionDenied),
+        _ => Err(ErrorKind::failed()
+            .with_summary("Failed to signal process")
+            .with_reason(format!("PID {}: {}", pid, err))),
     }
 }
 
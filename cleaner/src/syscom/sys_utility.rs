@@ -18,6 +18,11 @@
 //! Provides utilities for interacting with macOS-specific
 //! system tools and metadata formats.
 //!
+//! Supported utilities include:
+//!
+//! - `lsbom` for BOM inspection.
+//! - `launchctl` for launchd job management.
+//!
 //! Note:
 //! These helpers depend on system utilities like native macOS command-line tools.
 //!..
@@ -98,3 +103,30 @@ pub fn run_lsbom_command(bom_file: &Path, output_file: &Path) -> Result<()> {
             )))
     }
 }
+
+/// Runs `launchctl` with the provided arguments.
+///
+/// Doc:
+/// Executes the macOS `launchctl` utility and returns its exit
+/// code together with the trimmed standard error output.
+///
+/// Design:
+/// Interpreting exit codes is left to the caller because the
+/// same code means different things for different subcommands.
+/// Only a failure to execute the utility itself is reported as
+/// an error.
+///
+/// Note:
+/// The exit code is `None` when `launchctl` was terminated by a
+/// signal.
+pub fn run_launchctl_command(args: &[&str]) -> Result<(Option<i32>, String)> {
+    let output = Command::new("launchctl").args(args).output().map_err(|e| {
+        ErrorKind::failed()
+            .with_summary("Utility execution failed")
+            .with_reason(format!("Failed to run launchctl {}: {}", args.join(" "), e))
+    })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Ok((output.status.code(), stderr))
+}
@@ -0,0 +1,209 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Extended attribute access.
+//!
+//! Provides portable wrappers around `getxattr` and `setxattr`
+//! for reading metadata such as:
+//!
+//! - `com.apple.quarantine`
+//! - `com.apple.metadata:kMDItemWhereFroms`
+//! - `com.apple.LaunchServices.OpenWith`
+//!
+//! Design:
+//! macOS and Linux expose the same calls with different
+//! signatures, and Linux only lets unprivileged users store
+//! attributes in the `user.` namespace. Names without a Linux
+//! namespace are therefore stored as `user.<name>` on Linux, so
+//! fixtures written there read back under their macOS names.
+//!
+//! Symbolic links are not followed.
+//!
+//! Note:
+//! A missing attribute, or a filesystem without extended
+//! attribute support, is reported as `None` rather than as an
+//! error.
+//!..
+
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+
+/// Namespaces understood by Linux.
+#[cfg(not(target_os = "macos"))]
+const LINUX_NAMESPACES: [&str; 4] = ["user.", "trusted.", "security.", "system."];
+
+/// Converts an attribute name into its platform spelling.
+fn platform_name(name: &str) -> String {
+    #[cfg(not(target_os = "macos"))]
+    {
+        if !LINUX_NAMESPACES.iter().any(|ns| name.starts_with(ns)) {
+            return format!("user.{}", name);
+        }
+    }
+
+    name.to_string()
+}
+
+fn c_strings(path: &Path, name: &str) -> Result<(CString, CString)> {
+    let invalid = |e: std::ffi::NulError| {
+        ErrorKind::failed()
+            .with_summary("Invalid extended attribute request")
+            .with_reason(format!("{} [{}]: {}", path.display(), name, e))
+    };
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(invalid)?;
+    let c_name = CString::new(platform_name(name)).map_err(invalid)?;
+
+    Ok((c_path, c_name))
+}
+
+/// Returns whether an error means "no such attribute here".
+fn is_missing(err: &std::io::Error) -> bool {
+    #[cfg(target_os = "macos")]
+    let missing = libc::ENOATTR;
+    #[cfg(not(target_os = "macos"))]
+    let missing = libc::ENODATA;
+
+    matches!(err.raw_os_error(), Some(code) if code == missing || code == libc::ENOTSUP)
+}
+
+/// Calls `getxattr` without following symbolic links.
+///
+/// # Safety
+///
+/// `value` must be null with `size` 0, or point to `size`
+/// writable bytes.
+unsafe fn raw_getxattr(
+    path: &CString,
+    name: &CString,
+    value: *mut libc::c_void,
+    size: usize,
+) -> isize {
+    #[cfg(target_os = "macos")]
+    unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value,
+            size,
+            0,
+            libc::XATTR_NOFOLLOW,
+        )
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    unsafe {
+        libc::lgetxattr(path.as_ptr(), name.as_ptr(), value, size)
+    }
+}
+
+/// Reads an extended attribute.
+///
+/// Doc:
+/// Returns the raw attribute value, or `None` when the entry has
+/// no such attribute or its filesystem does not support
+/// extended attributes.
+///
+/// Returns an error if the entry cannot be accessed, for
+/// example because it does not exist.
+pub fn get_xattr(path: &Path, name: &str) -> Result<Option<Vec<u8>>> {
+    let (c_path, c_name) = c_strings(path, name)?;
+    let failed = |err: std::io::Error| {
+        ErrorKind::failed()
+            .with_summary("Failed to read extended attribute")
+            .with_reason(format!("{} [{}]: {}", path.display(), name, err))
+    };
+
+    // The value can grow between the size query and the read, so
+    // retry when the buffer turns out to be too small.
+    loop {
+        let size = unsafe { raw_getxattr(&c_path, &c_name, std::ptr::null_mut(), 0) };
+        if size < 0 {
+            let err = std::io::Error::last_os_error();
+            return if is_missing(&err) {
+                Ok(None)
+            } else {
+                Err(failed(err))
+            };
+        }
+
+        let mut buffer = vec![0u8; size as usize];
+        let read =
+            unsafe { raw_getxattr(&c_path, &c_name, buffer.as_mut_ptr().cast(), buffer.len()) };
+
+        if read >= 0 {
+            buffer.truncate(read as usize);
+            return Ok(Some(buffer));
+        }
+
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ERANGE) => continue,
+            _ if is_missing(&err) => return Ok(None),
+            _ => return Err(failed(err)),
+        }
+    }
+}
+
+/// Writes an extended attribute.
+///
+/// Doc:
+/// Creates or replaces the attribute without following symbolic
+/// links.
+///
+/// Returns an error if the entry cannot be accessed or its
+/// filesystem does not support extended attributes.
+pub fn set_xattr(path: &Path, name: &str, value: &[u8]) -> Result<()> {
+    let (c_path, c_name) = c_strings(path, name)?;
+
+    #[cfg(target_os = "macos")]
+    let ret = unsafe {
+        libc::setxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    let ret = unsafe {
+        libc::lsetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+        )
+    };
+
+    if ret == 0 {
+        return Ok(());
+    }
+
+    Err(ErrorKind::failed()
+        .with_summary("Failed to write extended attribute")
+        .with_reason(format!(
+            "{} [{}]: {}",
+            path.display(),
+            name,
+            std::io::Error::last_os_error()
+        )))
+}
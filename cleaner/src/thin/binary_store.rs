@@ -0,0 +1,191 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Binary replacement.
+//!
+//! Thinned binaries replace their originals in place. The
+//! originals stay recoverable by going through the same trash
+//! flow as uninstalling:
+//!
+//! 1. The thinned file is written next to the original.
+//! 2. The original is moved to Trash.
+//! 3. The thinned file is renamed to the original name.
+//!
+//! Note:
+//! File permissions of the original are kept, so executables
+//! remain executable.
+//!..
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use mini_logger::debug;
+
+use crate::errors::{ErrorKind, Result};
+use crate::path_data::PathData;
+use crate::path_data::trash_entry::{TrashEntry, TrashItem};
+
+/// Binary replacement abstraction.
+///
+/// Doc:
+/// Replaces the contents of `binary` with `data` and returns
+/// where the original was kept.
+pub trait BinaryStore: Send + Sync {
+    fn replace(&self, binary: &PathData, data: &[u8]) -> Result<TrashItem>;
+}
+
+/// Trash based binary store.
+///
+/// Doc:
+/// Moves originals to the system Trash before the thinned file
+/// takes their place.
+///
+/// Design:
+/// The thinned file is written first, so a failure to write it
+/// leaves the original untouched, and a failure to trash the
+/// original discards the thinned file.
+///
+/// Note:
+/// Symbolic links are refused and left untouched.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TrashBinaryStore;
+
+impl TrashBinaryStore {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the hidden sibling the thinned file is written to.
+    fn staging_path(path: &Path) -> PathBuf {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        path.with_file_name(format!(".{}.thin", name))
+    }
+
+    fn io_error(summary: &'static str, path: &Path, err: std::io::Error) -> ErrorKind {
+        ErrorKind::failed()
+            .with_summary(summary)
+            .with_reason(format!("{}: {}", path.display(), err))
+    }
+}
+
+impl BinaryStore for TrashBinaryStore {
+    fn replace(&self, binary: &PathData, data: &[u8]) -> Result<TrashItem> {
+        let path = binary.as_path();
+        let staging = Self::staging_path(path);
+
+        let metadata = fs::symlink_metadata(path)
+            .map_err(|e| Self::io_error("Failed to read binary", path, e))?;
+
+        // Replacing a link with a regular file would break the
+        // framework layout and its signature.
+        if metadata.file_type().is_symlink() {
+            return Err(ErrorKind::failed()
+                .with_summary("Refusing to replace a symbolic link")
+                .with_reason(path.display().to_string()));
+        }
+
+        let permissions = metadata.permissions();
+
+        fs::write(&staging, data)
+            .and_then(|_| fs::set_permissions(&staging, permissions))
+            .map_err(|e| Self::io_error("Failed to write thinned binary", &staging, e))?;
+
+        let trash = TrashEntry::moved_path_to_trash(std::slice::from_ref(binary));
+
+        let item = match trash {
+            Ok(trash) if trash.failed_path().is_empty() && !trash.moved_path().is_empty() => {
+                trash.moved_path()[0].clone()
+            }
+            result => {
+                let _ = fs::remove_file(&staging);
+
+                return Err(match result {
+                    Ok(trash) => trash
+                        .failed_path()
+                        .first()
+                        .map(|(_, reason)| reason.clone())
+                        .unwrap_or_else(|| {
+                            ErrorKind::failed()
+                                .with_summary("Failed to move binary to Trash")
+                                .with_reason(path.display().to_string())
+                        }),
+                    Err(err) => err,
+                });
+            }
+        };
+
+        fs::rename(&staging, path).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to replace binary")
+                .with_reason(format!(
+                    "{}: {}, original kept at {}",
+                    path.display(),
+                    e,
+                    item.as_trashed_path().display()
+                ))
+        })?;
+
+        debug!(
+            "Thinned {}, original moved to {}",
+            path.display(),
+            item.as_trashed_path().display()
+        );
+
+        Ok(item)
+    }
+}
+
+/// Recording binary store.
+///
+/// Doc:
+/// Records every replacement together with the thinned data.
+/// Returned trash items have an empty trashed path.
+///
+/// Design:
+/// Intended for tests and previews. Files on disk are never
+/// modified.
+#[derive(Debug, Default)]
+pub struct RecordingBinaryStore {
+    replacements: Mutex<Vec<(PathBuf, Vec<u8>)>>,
+}
+
+impl RecordingBinaryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded replacements in call order.
+    pub fn replacements(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        self.replacements
+            .lock()
+            .map(|replacements| replacements.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl BinaryStore for RecordingBinaryStore {
+    fn replace(&self, binary: &PathData, data: &[u8]) -> Result<TrashItem> {
+        if let Ok(mut replacements) = self.replacements.lock() {
+            replacements.push((binary.as_path().to_path_buf(), data.to_vec()));
+        }
+
+        Ok(TrashItem::new(binary.clone(), PathBuf::new()))
+    }
+}
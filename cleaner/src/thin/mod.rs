@@ -0,0 +1,56 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Universal binary thinning.
+//!
+//! Universal (fat) Mach-O files carry one slice per CPU
+//! architecture, typically `x86_64` and `arm64`. A machine only
+//! ever runs one of them, so the other slices can be removed to
+//! reclaim space without uninstalling the application.
+//!
+//! The module is built around:
+//!
+//! - `ThinReport`, the universal binaries of a bundle and the
+//!   bytes reclaimable per architecture.
+//! - `thin_mach_o`, which extracts one slice as a thin file.
+//! - `BinaryStore`, the abstraction used to replace binaries.
+//! - `thin_bundle_with`, which rewrites every universal binary of
+//!   a report.
+//!
+//! Two `BinaryStore` implementations are provided:
+//!
+//! - `TrashBinaryStore` moves the originals to Trash before the
+//!   thinned files take their place.
+//! - `RecordingBinaryStore` records replacements without touching
+//!   the disk, for tests and previews.
+//!
+//! Design:
+//! Thinning builds on the `macho` parser. A slice of a universal
+//! file is a complete thin image, since offsets inside a slice
+//! are relative to its start, so `lipo -thin` is not required.
+//!
+//! Note:
+//! Modifying a binary invalidates the code signature sealing the
+//! bundle. Bundles signed with a certificate are refused unless
+//! thinning is forced.
+//!..
+
+mod binary_store;
+mod thin_report;
+mod thinning;
+
+pub use binary_store::{BinaryStore, RecordingBinaryStore, TrashBinaryStore};
+pub use thin_report::{FatBinary, ThinReport, ThinSlice};
+pub use thinning::{thin_bundle, thin_bundle_with, thin_mach_o};
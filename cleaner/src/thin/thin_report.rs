@@ -0,0 +1,332 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Universal binary discovery.
+//!
+//! Scans an application bundle for universal Mach-O files and
+//! summarizes their slices:
+//!
+//! ```text
+//! Cafe.app/Contents/MacOS/Cafe
+//!   x86_64   4.1 MB
+//!   arm64    3.8 MB
+//! ```
+//!
+//! Keeping `arm64` reclaims the `x86_64` slices, together with
+//! the alignment padding between slices.
+//!
+//! Note:
+//! Scanning only reads the file system.
+//!..
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use mini_logger::debug;
+use rayon::prelude::*;
+
+use crate::errors::{ErrorKind, Result};
+use crate::macho::{CS_LINKER_SIGNED, MachFile, SigningInfo};
+use crate::path_data::PathData;
+use crate::utility::{FileSystem, RealFileSystem};
+
+/// Directory holding the signature seal of a bundle.
+const SEAL_DIR: &str = "_CodeSignature";
+
+/// Extensions of files that can hold Mach-O code.
+///
+/// Files without an extension, such as executables and framework
+/// binaries, are inspected as well.
+const CODE_EXTENSIONS: [&str; 4] = ["dylib", "so", "node", "bundle"];
+
+/// One architecture slice of a universal binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThinSlice {
+    arch: &'static str,
+    cpu_type: u32,
+    size: u64,
+}
+
+impl ThinSlice {
+    /// get architecture name reference
+    pub fn as_arch(&self) -> &'static str {
+        self.arch
+    }
+
+    /// get the copy of CPU type
+    pub fn cpu_type(&self) -> u32 {
+        self.cpu_type
+    }
+
+    /// get the copy of slice size in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Universal binary found in a bundle.
+///
+/// Doc:
+/// Each entry stores:
+///
+/// - The binary path.
+/// - The file size.
+/// - Its architecture slices.
+/// - Whether it carries a certificate signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FatBinary {
+    path: PathData,
+    file_size: u64,
+    slices: Vec<ThinSlice>,
+    is_signed: bool,
+}
+
+impl FatBinary {
+    /// Inspects a file, returning `None` unless it is a universal
+    /// Mach-O file.
+    pub fn from_mach_o(path: &Path, data: &[u8]) -> Option<Self> {
+        let file = MachFile::parse(data).ok()?;
+        if !file.is_fat() {
+            return None;
+        }
+
+        let slices = file
+            .slices()
+            .iter()
+            .map(|slice| ThinSlice {
+                arch: slice.arch_name(),
+                cpu_type: slice.cpu_type(),
+                size: slice.as_bytes().len() as u64,
+            })
+            .collect();
+
+        // Linker signatures are ad-hoc and regenerated by every
+        // link, so they do not seal the bundle.
+        let is_signed = SigningInfo::from_mach_o(data)
+            .map(|info| info.is_signed() && info.flags() & CS_LINKER_SIGNED == 0)
+            .unwrap_or(false);
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Some(Self {
+            path: PathData::new(path.to_path_buf(), name),
+            file_size: data.len() as u64,
+            slices,
+            is_signed,
+        })
+    }
+
+    /// get binary path reference
+    pub fn as_path_data(&self) -> &PathData {
+        &self.path
+    }
+
+    /// get the copy of file size in bytes
+    pub fn file_size(&self) -> u64 {
+        self.file_size
+    }
+
+    /// get slices reference
+    pub fn as_slices(&self) -> &[ThinSlice] {
+        &self.slices
+    }
+
+    /// Returns whether the binary carries a signature made with
+    /// a signing identity.
+    pub fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// Returns the slice for an architecture name.
+    pub fn slice(&self, arch: &str) -> Option<&ThinSlice> {
+        self.slices.iter().find(|slice| slice.arch == arch)
+    }
+
+    /// Returns the bytes reclaimed by keeping only `arch`.
+    ///
+    /// Note:
+    /// Returns `0` when the binary has no slice for `arch`, since
+    /// such a binary is left untouched.
+    pub fn reclaimable_bytes(&self, arch: &str) -> u64 {
+        self.slice(arch)
+            .map(|slice| self.file_size.saturating_sub(slice.size))
+            .unwrap_or(0)
+    }
+}
+
+/// Universal binaries of an application bundle.
+///
+/// Doc:
+/// Produced by `scan()` and consumed by `thin_bundle()`.
+///
+/// Reports, for every architecture present in the bundle, the
+/// bytes reclaimable by keeping only that architecture.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ThinReport {
+    bundle_path: PathBuf,
+    binaries: Vec<FatBinary>,
+    is_sealed: bool,
+}
+
+impl ThinReport {
+    /// Scans a bundle for universal binaries.
+    pub fn scan(bundle_path: &Path) -> Result<Self> {
+        Self::scan_with(&RealFileSystem::new(), bundle_path)
+    }
+
+    /// Scans a bundle for universal binaries through the provided
+    /// `FileSystem`.
+    ///
+    /// Doc:
+    /// Every file without an extension, and every file with a
+    /// code extension such as `dylib`, is probed for a universal
+    /// magic. Only universal files are read and parsed.
+    ///
+    /// Symbolic links, such as the `Versions/Current` links of a
+    /// framework, are skipped and files reached through several
+    /// paths are reported once, so every binary is replaced in
+    /// place and the framework layout is kept.
+    ///
+    /// A `_CodeSignature` directory anywhere in the bundle marks
+    /// it as sealed.
+    ///
+    /// Design:
+    /// Resources make up most of a bundle and never hold code,
+    /// so they are not read.
+    ///
+    /// Returns an error if the bundle does not exist.
+    pub fn scan_with<FS>(fs: &FS, bundle_path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        if !fs.is_dir(bundle_path) {
+            return Err(ErrorKind::failed()
+                .with_summary("Failed to scan universal binaries")
+                .with_reason(format!("{} is not a bundle", bundle_path.display())));
+        }
+
+        let mut is_sealed = false;
+        let mut candidates: Vec<PathBuf> = Vec::new();
+        let mut canonical_paths: HashSet<PathBuf> = HashSet::new();
+
+        for entry in fs.walk(bundle_path, usize::MAX) {
+            if entry.is_symlink() {
+                continue;
+            }
+
+            let path = entry.as_path();
+
+            if entry.is_dir() {
+                is_sealed |= path.file_name().map(|n| n == SEAL_DIR).unwrap_or(false);
+                continue;
+            }
+
+            let is_code = match path.extension() {
+                None => true,
+                Some(ext) => CODE_EXTENSIONS.iter().any(|code| ext == *code),
+            };
+            if !is_code {
+                continue;
+            }
+
+            let canonical = fs.canonicalize(path).unwrap_or_else(|| path.to_path_buf());
+            if !canonical_paths.insert(canonical) {
+                continue;
+            }
+
+            candidates.push(entry.into_path());
+        }
+
+        let mut binaries: Vec<FatBinary> = candidates
+            .par_iter()
+            .filter_map(|path| {
+                let magic = fs.read_range(path, 0, 4).ok()?;
+                if !MachFile::is_universal(&magic) {
+                    return None;
+                }
+
+                let data = fs.read(path).ok()?;
+                FatBinary::from_mach_o(path, &data)
+            })
+            .collect();
+
+        binaries.sort_by(|a, b| a.path.as_path().cmp(b.path.as_path()));
+        debug!(
+            "{} universal binaries in {}",
+            binaries.len(),
+            bundle_path.display()
+        );
+
+        Ok(Self {
+            bundle_path: bundle_path.to_path_buf(),
+            binaries,
+            is_sealed,
+        })
+    }
+
+    /// get bundle path reference
+    pub fn as_bundle_path(&self) -> &Path {
+        &self.bundle_path
+    }
+
+    /// get universal binaries reference
+    pub fn as_binaries(&self) -> &[FatBinary] {
+        &self.binaries
+    }
+
+    /// get the copy of bundle seal flag
+    pub fn is_sealed(&self) -> bool {
+        self.is_sealed
+    }
+
+    /// Returns whether the bundle is sealed, or any universal
+    /// binary carries a signature made with a signing identity.
+    pub fn is_signed(&self) -> bool {
+        self.is_sealed || self.binaries.iter().any(FatBinary::is_signed)
+    }
+
+    /// Returns the architectures present, in first-seen order.
+    pub fn architectures(&self) -> Vec<&'static str> {
+        let mut architectures = Vec::new();
+
+        for slice in self.binaries.iter().flat_map(|binary| &binary.slices) {
+            if !architectures.contains(&slice.arch) {
+                architectures.push(slice.arch);
+            }
+        }
+
+        architectures
+    }
+
+    /// Returns the bytes reclaimed by keeping only `arch`.
+    pub fn reclaimable_bytes(&self, arch: &str) -> u64 {
+        self.binaries
+            .iter()
+            .map(|binary| binary.reclaimable_bytes(arch))
+            .sum()
+    }
+
+    /// Returns the reclaimable bytes for every architecture that
+    /// can be kept.
+    pub fn reclaimable_by_architecture(&self) -> Vec<(&'static str, u64)> {
+        self.architectures()
+            .into_iter()
+            .map(|arch| (arch, self.reclaimable_bytes(arch)))
+            .collect()
+    }
+}
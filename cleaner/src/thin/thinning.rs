@@ -0,0 +1,135 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Universal binary rewriting.
+//!
+//! Rewrites the universal binaries of a `ThinReport` so that
+//! only one architecture remains:
+//!
+//! ```text
+//! fat: [x86_64][arm64]  --keep arm64-->  thin: [arm64]
+//! ```
+//!
+//! Note:
+//! Binaries without a slice for the kept architecture are
+//! skipped rather than left without runnable code.
+//!..
+
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::macho::MachFile;
+use crate::path_data::trash_entry::TrashEntry;
+use crate::thin::binary_store::{BinaryStore, TrashBinaryStore};
+use crate::thin::thin_report::ThinReport;
+use crate::utility::{FileSystem, RealFileSystem};
+
+/// Extracts one architecture of a universal file.
+///
+/// Doc:
+/// The slice is returned as a complete thin Mach-O file, the
+/// same result as `lipo -thin <arch>`.
+///
+/// Returns an error if the data is not a universal file or has
+/// no slice for `arch`.
+pub fn thin_mach_o(data: &[u8], arch: &str) -> Result<Vec<u8>> {
+    let file = MachFile::parse(data)?;
+
+    if !file.is_fat() {
+        return Err(ErrorKind::skipped().with_reason("binary is already thin"));
+    }
+
+    file.slices()
+        .iter()
+        .find(|slice| slice.arch_name() == arch)
+        .map(|slice| slice.as_bytes().to_vec())
+        .ok_or_else(|| ErrorKind::skipped().with_reason(format!("binary has no {} slice", arch)))
+}
+
+/// Thins every universal binary of a report on disk.
+///
+/// Originals are moved to Trash. See `thin_bundle_with`.
+pub fn thin_bundle(report: &ThinReport, arch: &str, force: bool) -> Result<TrashEntry> {
+    thin_bundle_with(
+        &RealFileSystem::new(),
+        &TrashBinaryStore::new(),
+        report,
+        arch,
+        force,
+    )
+}
+
+/// Thins every universal binary of a report using the provided
+/// `FileSystem` and `BinaryStore`.
+///
+/// Doc:
+/// Keeps only `arch` in each binary. Replaced binaries are
+/// recorded as moved items of the returned `TrashEntry`, while
+/// binaries that were skipped or could not be replaced are
+/// recorded as failed items with their reason.
+///
+/// Design:
+/// Partial success is supported, as with uninstalling. A binary
+/// that cannot be thinned does not stop the others.
+///
+/// Note:
+/// Returns an error without modifying anything when the bundle
+/// carries a certificate signature and `force` is not set, since
+/// thinning breaks the signature seal and Gatekeeper may then
+/// refuse to launch the application.
+pub fn thin_bundle_with<FS, S>(
+    fs: &FS,
+    store: &S,
+    report: &ThinReport,
+    arch: &str,
+    force: bool,
+) -> Result<TrashEntry>
+where
+    FS: FileSystem + ?Sized,
+    S: BinaryStore + ?Sized,
+{
+    if report.is_signed() && !force {
+        return Err(ErrorKind::failed()
+            .with_summary("Refusing to thin a signed bundle")
+            .with_reason(format!(
+                "{} carries a code signature that thinning would invalidate",
+                bundle_name(report.as_bundle_path())
+            )));
+    }
+
+    let mut result = TrashEntry::default();
+
+    for binary in report.as_binaries() {
+        let path_data = binary.as_path_data();
+
+        let replaced = fs
+            .read(path_data.as_path())
+            .and_then(|data| thin_mach_o(&data, arch))
+            .and_then(|thinned| store.replace(path_data, &thinned));
+
+        match replaced {
+            Ok(item) => result.moved_path_mut().push(item),
+            Err(err) => result.failed_path_mut().push((path_data.clone(), err)),
+        }
+    }
+
+    Ok(result)
+}
+
+fn bundle_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,49 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Byte size formatting.
+//!
+//! Sizes are shown to users in the GUI and in reports with
+//! binary units, as Finder did before macOS 10.6:
+//!
+//! ```text
+//! 512       -> 512 B
+//! 1536      -> 1.5 KB
+//! 5242880   -> 5.0 MB
+//! ```
+//!..
+
+const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+/// Formats a byte count for display.
+///
+/// Note:
+/// Values below 1 KB are shown exactly, larger values with one
+/// decimal.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
@@ -0,0 +1,186 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! File origin metadata stored in extended attributes.
+//!
+//! macOS records where a file came from in extended attributes:
+//!
+//! - `com.apple.quarantine` names the agent that downloaded or
+//!   created the file, such as `Safari` or `zoom.us`.
+//! - `com.apple.LaunchServices.OpenWith` stores the bundle
+//!   identifier of the application chosen to open the file.
+//! - `com.apple.metadata:kMDItemWhereFroms` lists the URLs the
+//!   file was downloaded from.
+//!
+//! Design:
+//! Document-type caches, `*.savedState` folders, and thumbnails
+//! are often named after neither the application nor its
+//! bundle identifier. The quarantine agent and the LaunchServices
+//! bundle identifier still link them to the application. The
+//! general scanner reports such entries without proposing them
+//! for removal, because the quarantine agent of a browser or a
+//! mail client names every file it ever downloaded.
+//!
+//! Note:
+//! Download URLs are parsed for display but are not used for
+//! matching, because a site rarely identifies an application.
+//!..
+
+use plist::Value;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::utility::file_system::{FileSystem, RealFileSystem};
+
+/// Quarantine attribute written by Launch Services.
+pub const QUARANTINE_XATTR: &str = "com.apple.quarantine";
+
+/// Spotlight attribute listing download origins.
+pub const WHERE_FROMS_XATTR: &str = "com.apple.metadata:kMDItemWhereFroms";
+
+/// Per-file "Open With" application chosen in Finder.
+pub const OPEN_WITH_XATTR: &str = "com.apple.LaunchServices.OpenWith";
+
+/// Origin signals of a filesystem entry.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileOrigin {
+    quarantine_agent: Option<String>,
+    origin_bundle_id: Option<String>,
+    where_froms: Vec<String>,
+}
+
+impl FileOrigin {
+    /// Reads the origin attributes of an entry on disk.
+    pub fn from_path(path: &Path) -> Self {
+        Self::from_path_with(&RealFileSystem::new(), path)
+    }
+
+    /// Reads the origin attributes through the provided
+    /// `FileSystem`.
+    ///
+    /// Note:
+    /// Missing or malformed attributes are left empty.
+    pub fn from_path_with<FS>(fs: &FS, path: &Path) -> Self
+    where
+        FS: FileSystem + ?Sized,
+    {
+        Self {
+            quarantine_agent: Self::quarantine_agent_with(fs, path),
+            origin_bundle_id: Self::origin_bundle_id_with(fs, path),
+            where_froms: fs
+                .xattr(path, WHERE_FROMS_XATTR)
+                .map(|value| Self::parse_where_froms(&value))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Reads only the quarantine agent of an entry.
+    ///
+    /// Note:
+    /// Scanners use it instead of `from_path_with()` to avoid
+    /// reading attributes they do not match on.
+    pub fn quarantine_agent_with<FS>(fs: &FS, path: &Path) -> Option<String>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        fs.xattr(path, QUARANTINE_XATTR)
+            .and_then(|value| Self::parse_quarantine_agent(&value))
+    }
+
+    /// Reads only the LaunchServices bundle identifier of an
+    /// entry.
+    pub fn origin_bundle_id_with<FS>(fs: &FS, path: &Path) -> Option<String>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        fs.xattr(path, OPEN_WITH_XATTR)
+            .and_then(|value| Self::parse_open_with(&value))
+    }
+
+    /// get quarantine agent name reference
+    pub fn as_quarantine_agent(&self) -> Option<&str> {
+        self.quarantine_agent.as_deref()
+    }
+
+    /// get origin bundle identifier reference
+    pub fn as_origin_bundle_id(&self) -> Option<&str> {
+        self.origin_bundle_id.as_deref()
+    }
+
+    /// get download origins reference
+    pub fn as_where_froms(&self) -> &[String] {
+        &self.where_froms
+    }
+
+    /// Returns whether no origin attribute was found.
+    pub fn is_empty(&self) -> bool {
+        self.quarantine_agent.is_none()
+            && self.origin_bundle_id.is_none()
+            && self.where_froms.is_empty()
+    }
+
+    /// Extracts the agent name from a quarantine value.
+    ///
+    /// Doc:
+    /// The value is a `;` separated record:
+    ///
+    /// ```text
+    /// 0083;65a1b2c3;Safari;8F2A1C3D-...
+    /// flags;timestamp;agent;event
+    /// ```
+    ///
+    /// Returns `None` when the agent field is missing or empty.
+    pub fn parse_quarantine_agent(value: &[u8]) -> Option<String> {
+        let text = String::from_utf8_lossy(value);
+        let agent = text.trim_end_matches('\0').split(';').nth(2)?.trim();
+
+        (!agent.is_empty()).then(|| agent.to_string())
+    }
+
+    /// Extracts the bundle identifier from an `OpenWith` value.
+    ///
+    /// Doc:
+    /// The value is a property list dictionary holding
+    /// `bundleidentifier`, `path`, and `version`.
+    pub fn parse_open_with(value: &[u8]) -> Option<String> {
+        let plist = Value::from_reader(Cursor::new(value)).ok()?;
+        let bundle_id = plist
+            .as_dictionary()?
+            .get("bundleidentifier")?
+            .as_string()?
+            .trim();
+
+        (!bundle_id.is_empty()).then(|| bundle_id.to_string())
+    }
+
+    /// Extracts the URLs from a `kMDItemWhereFroms` value.
+    ///
+    /// Doc:
+    /// The value is a property list array of strings. Entries
+    /// that are not strings are skipped.
+    pub fn parse_where_froms(value: &[u8]) -> Vec<String> {
+        Value::from_reader(Cursor::new(value))
+            .ok()
+            .and_then(|plist| plist.into_array())
+            .map(|items| {
+                items
+                    .into_iter()
+                    .filter_map(|item| item.into_string())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
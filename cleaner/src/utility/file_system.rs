@@ -0,0 +1,691 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Filesystem access used by the scanners.
+//!
+//! Scanners and metadata parsing read the filesystem through the
+//! `FileSystem` trait instead of calling `WalkDir` and `std::fs`
+//! directly.
+//!
+//! Two implementations are provided:
+//!
+//! - `RealFileSystem` reads the disk.
+//! - `MemoryFileSystem` serves a tree held in memory, optionally
+//!   loaded from a YAML fixture.
+//!
+//! Design:
+//! Discovery results depend on the content of `~/Library` and
+//! other system locations. An in-memory tree makes the matching
+//! and deduplication logic testable against a known layout,
+//! including names that differ only by case or Unicode
+//! normalization, without creating files on disk.
+//!
+//! Note:
+//! The in-memory tree does not model symbolic links,
+//! permissions, or extended attributes.
+//!
+//! Symbolic links are never followed below a walk root, as
+//! with `WalkDir`. `symlink_metadata` describes a link itself,
+//! so callers that descend or sum sizes do not visit the same
+//! content twice.
+//!..
+
+use plist::Value;
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+use crate::errors::{ErrorKind, Result};
+use crate::syscom::get_xattr;
+
+/// Metadata of a filesystem entry.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FsMetadata {
+    is_dir: bool,
+    is_symlink: bool,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FsMetadata {
+    pub fn new(is_dir: bool, len: u64) -> Self {
+        Self {
+            is_dir,
+            is_symlink: false,
+            len,
+            modified: None,
+        }
+    }
+
+    pub fn with_modified(mut self, modified: Option<SystemTime>) -> Self {
+        self.modified = modified;
+        self
+    }
+
+    pub fn with_symlink(mut self, is_symlink: bool) -> Self {
+        self.is_symlink = is_symlink;
+        self
+    }
+
+    /// get the copy of directory flag
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// get the copy of file flag
+    pub fn is_file(&self) -> bool {
+        !self.is_dir && !self.is_symlink
+    }
+
+    /// get the copy of symbolic link flag
+    ///
+    /// Only `FileSystem::symlink_metadata` reports links.
+    pub fn is_symlink(&self) -> bool {
+        self.is_symlink
+    }
+
+    /// get the copy of file size in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the entry is a file without content.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// get the copy of modification time, when available
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+}
+
+/// Entries produced by `FileSystem::walk`, in traversal order.
+///
+/// Doc:
+/// Entries are produced lazily, so walking a large tree does not
+/// hold the whole tree in memory.
+pub type FsWalk<'a> = Box<dyn Iterator<Item = FsEntry> + 'a>;
+
+/// Entry produced by `FileSystem::walk`.
+///
+/// Doc:
+/// Stores the entry path, its depth relative to the walk root,
+/// and whether it is a directory or a symbolic link.
+///
+/// Note:
+/// Links are not followed, so a link to a directory is neither
+/// a directory nor descended into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsEntry {
+    path: PathBuf,
+    depth: usize,
+    is_dir: bool,
+    is_symlink: bool,
+}
+
+impl FsEntry {
+    pub fn new(path: PathBuf, depth: usize, is_dir: bool) -> Self {
+        Self {
+            path,
+            depth,
+            is_dir,
+            is_symlink: false,
+        }
+    }
+
+    pub fn with_symlink(mut self, is_symlink: bool) -> Self {
+        self.is_symlink = is_symlink;
+        self
+    }
+
+    /// get entry path reference
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// get the owned entry path
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// get the copy of depth relative to the walk root
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// get the copy of directory flag
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// get the copy of symbolic link flag
+    pub fn is_symlink(&self) -> bool {
+        self.is_symlink
+    }
+}
+
+/// Filesystem abstraction.
+///
+/// Doc:
+/// Provides the operations required by discovery:
+///
+/// - `read_dir` lists the direct children of a directory.
+/// - `metadata` describes a single entry, following links.
+/// - `symlink_metadata` describes a single entry without
+///   following links.
+/// - `walk` lists a tree up to a maximum depth.
+/// - `read` returns the content of a file.
+/// - `read_range` returns part of the content of a file.
+/// - `canonicalize` resolves the links of a path.
+/// - `total_size` sums the files below an entry.
+/// - `xattr` returns an extended attribute of an entry.
+///
+/// `walk` follows the `WalkDir` conventions: the root itself is
+/// reported at depth `0` and unreadable entries are skipped.
+/// Entries are produced lazily; callers that need a list collect
+/// only the entries they keep.
+///
+/// Note:
+/// Missing paths are not errors for `read_dir` and `walk`; they
+/// produce empty results like an empty directory would.
+pub trait FileSystem: Send + Sync {
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf>;
+
+    fn metadata(&self, path: &Path) -> Option<FsMetadata>;
+
+    /// Describes an entry without following symbolic links.
+    ///
+    /// Doc:
+    /// Implementations without symbolic links keep this default.
+    fn symlink_metadata(&self, path: &Path) -> Option<FsMetadata> {
+        self.metadata(path)
+    }
+
+    fn walk(&self, root: &Path, max_depth: usize) -> FsWalk<'_>;
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>>;
+
+    /// Reads up to `len` bytes of a file starting at `offset`.
+    ///
+    /// Doc:
+    /// Fewer bytes are returned when the file ends first, and
+    /// none when `offset` lies past its end. Implementations
+    /// reading from memory keep this default.
+    fn read_range(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let data = self.read(path)?;
+        let start = usize::try_from(offset)
+            .unwrap_or(usize::MAX)
+            .min(data.len());
+        let end = start.saturating_add(len).min(data.len());
+
+        Ok(data[start..end].to_vec())
+    }
+
+    /// Resolves symbolic links and relative components of a
+    /// path.
+    ///
+    /// Doc:
+    /// Returns `None` when the entry does not exist.
+    /// Implementations without symbolic links keep this default.
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        self.exists(path).then(|| path.to_path_buf())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_some()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).map(|m| m.is_dir()).unwrap_or(false)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).map(|m| m.is_file()).unwrap_or(false)
+    }
+
+    /// Returns the size of an entry in bytes.
+    ///
+    /// Doc:
+    /// Files report their length. Directories report the sum of
+    /// every file below them.
+    ///
+    /// Symbolic links are not followed, so a file reached through
+    /// a link is counted once, under its real path.
+    ///
+    /// Note:
+    /// Missing paths and links report `0`.
+    fn total_size(&self, path: &Path) -> u64 {
+        match self.symlink_metadata(path) {
+            Some(metadata) if metadata.is_symlink() => 0,
+            Some(metadata) if metadata.is_dir() => self
+                .walk(path, usize::MAX)
+                .filter(|entry| !entry.is_dir() && !entry.is_symlink())
+                .filter_map(|entry| self.symlink_metadata(entry.as_path()))
+                .map(|metadata| metadata.len())
+                .sum(),
+            Some(metadata) => metadata.len(),
+            None => 0,
+        }
+    }
+
+    /// Reads an extended attribute.
+    ///
+    /// Doc:
+    /// Returns `None` when the entry has no such attribute or
+    /// cannot be read. Implementations without extended
+    /// attributes keep this default.
+    fn xattr(&self, _path: &Path, _name: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Reads and parses a property list.
+    ///
+    /// Doc:
+    /// Accepts both XML and binary property lists.
+    fn read_plist(&self, path: &Path) -> Result<Value> {
+        let data = self.read(path)?;
+
+        Value::from_reader(Cursor::new(data)).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to parse plist")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })
+    }
+}
+
+/// Disk backed filesystem.
+///
+/// Doc:
+/// Uses `std::fs` for single entries and `WalkDir` for trees.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+impl RealFileSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FileSystem for RealFileSystem {
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|entry| entry.path())
+            .collect()
+    }
+
+    fn metadata(&self, path: &Path) -> Option<FsMetadata> {
+        std::fs::metadata(path)
+            .ok()
+            .map(|m| FsMetadata::new(m.is_dir(), m.len()).with_modified(m.modified().ok()))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> Option<FsMetadata> {
+        std::fs::symlink_metadata(path).ok().map(|m| {
+            FsMetadata::new(m.is_dir(), m.len())
+                .with_modified(m.modified().ok())
+                .with_symlink(m.file_type().is_symlink())
+        })
+    }
+
+    fn walk(&self, root: &Path, max_depth: usize) -> FsWalk<'_> {
+        Box::new(
+            WalkDir::new(root)
+                .max_depth(max_depth)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .map(|entry| {
+                    let depth = entry.depth();
+                    let is_dir = entry.file_type().is_dir();
+                    let is_symlink = entry.file_type().is_symlink();
+                    FsEntry::new(entry.into_path(), depth, is_dir).with_symlink(is_symlink)
+                }),
+        )
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        std::fs::read(path).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read file")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })
+    }
+
+    fn read_range(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let failed = |e: std::io::Error| {
+            ErrorKind::failed()
+                .with_summary("Failed to read file")
+                .with_reason(format!("{}: {}", path.display(), e))
+        };
+
+        let mut file = std::fs::File::open(path).map_err(failed)?;
+        file.seek(SeekFrom::Start(offset)).map_err(failed)?;
+
+        let mut data = Vec::with_capacity(len);
+        file.take(len as u64)
+            .read_to_end(&mut data)
+            .map_err(failed)?;
+
+        Ok(data)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        std::fs::canonicalize(path).ok()
+    }
+
+    fn xattr(&self, path: &Path, name: &str) -> Option<Vec<u8>> {
+        get_xattr(path, name).ok().flatten()
+    }
+}
+
+/// In-memory filesystem.
+///
+/// Doc:
+/// Stores directories and files in a sorted map keyed by
+/// absolute path. Parent directories are created implicitly when
+/// an entry is added.
+///
+/// A tree can be declared as YAML, where mappings are
+/// directories, strings are file contents, and `~` is an empty
+/// file:
+///
+/// ```text
+/// /Users/tester/Library:
+///   Preferences:
+///     com.example.App.plist: ~
+///   Application Support:
+///     Example App:
+///       settings.json: "{}"
+/// ```
+///
+/// Design:
+/// Paths are compared byte for byte. Names that differ only by
+/// case or Unicode normalization are therefore distinct entries,
+/// which lets tests reproduce layouts produced by copying files
+/// between filesystems with different conventions.
+///
+/// Modification times come from a logical clock that advances
+/// on every change. Adding or removing an entry also updates the
+/// modification time of its parent directory, as on disk.
+///
+/// Note:
+/// Traversal order is the sorted path order, so results are
+/// deterministic.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    entries: BTreeMap<PathBuf, MemoryNode>,
+    clock: u64,
+}
+
+/// Directory or file content, with its modification time.
+#[derive(Debug, Clone)]
+struct MemoryNode {
+    content: Option<Vec<u8>>,
+    modified: SystemTime,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory and its missing parents.
+    pub fn with_dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.create_dir(path);
+        self
+    }
+
+    /// Adds a file and its missing parent directories.
+    pub fn with_file(mut self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) -> Self {
+        self.create_file(path, content);
+        self
+    }
+
+    /// Creates a directory and its missing parents.
+    pub fn create_dir(&mut self, path: impl AsRef<Path>) {
+        self.insert_dir(path.as_ref());
+    }
+
+    /// Creates or replaces a file, creating missing parent
+    /// directories.
+    pub fn create_file(&mut self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) {
+        self.insert_file(path.as_ref(), content.into());
+    }
+
+    /// Removes an entry together with everything below it.
+    pub fn remove(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+
+        let removed: Vec<PathBuf> = self
+            .entries
+            .range(path.to_path_buf()..)
+            .map(|(entry, _)| entry)
+            .take_while(|entry| entry.starts_with(path))
+            .cloned()
+            .collect();
+
+        if removed.is_empty() {
+            return;
+        }
+
+        for entry in removed {
+            self.entries.remove(&entry);
+        }
+
+        let now = self.tick();
+        self.touch_parent(path, now);
+    }
+
+    /// Builds a filesystem from a YAML tree.
+    ///
+    /// Doc:
+    /// Top-level keys are absolute root paths and may contain
+    /// several components. Nested keys are single entry names.
+    ///
+    /// Returns an error if:
+    ///
+    /// - The document is not valid YAML.
+    /// - A key is not a string.
+    /// - A top-level key is not absolute.
+    /// - A value is neither a mapping, a string, nor `~`.
+    pub fn from_yaml(source: &str) -> Result<Self> {
+        let document: serde_yaml::Value = serde_yaml::from_str(source).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to parse filesystem fixture")
+                .with_reason(e.to_string())
+        })?;
+
+        let mut fs = Self::new();
+
+        match document {
+            serde_yaml::Value::Null => {}
+            serde_yaml::Value::Mapping(roots) => {
+                for (key, value) in roots {
+                    let root = Self::fixture_key(&key)?;
+
+                    if !Path::new(&root).is_absolute() {
+                        return Err(ErrorKind::failed()
+                            .with_summary("Invalid filesystem fixture")
+                            .with_reason(format!("root '{}' is not an absolute path", root)));
+                    }
+
+                    fs.insert_fixture(PathBuf::from(root), value)?;
+                }
+            }
+            _ => {
+                return Err(ErrorKind::failed()
+                    .with_summary("Invalid filesystem fixture")
+                    .with_reason("The fixture root is not a mapping"));
+            }
+        }
+
+        Ok(fs)
+    }
+
+    fn fixture_key(key: &serde_yaml::Value) -> Result<String> {
+        key.as_str().map(ToOwned::to_owned).ok_or_else(|| {
+            ErrorKind::failed()
+                .with_summary("Invalid filesystem fixture")
+                .with_reason(format!("entry name {:?} is not a string", key))
+        })
+    }
+
+    fn insert_fixture(&mut self, path: PathBuf, value: serde_yaml::Value) -> Result<()> {
+        match value {
+            serde_yaml::Value::Mapping(children) => {
+                self.insert_dir(&path);
+
+                for (key, child) in children {
+                    let name = Self::fixture_key(&key)?;
+                    self.insert_fixture(path.join(name), child)?;
+                }
+            }
+            serde_yaml::Value::String(content) => self.insert_file(&path, content.into_bytes()),
+            serde_yaml::Value::Null => self.insert_file(&path, Vec::new()),
+            other => {
+                return Err(ErrorKind::failed()
+                    .with_summary("Invalid filesystem fixture")
+                    .with_reason(format!("{}: unsupported value {:?}", path.display(), other)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insert_dir(&mut self, path: &Path) {
+        if path.as_os_str().is_empty() || self.entries.contains_key(path) {
+            return;
+        }
+
+        if let Some(parent) = path.parent() {
+            self.insert_dir(parent);
+        }
+
+        let now = self.tick();
+        self.entries.insert(
+            path.to_path_buf(),
+            MemoryNode {
+                content: None,
+                modified: now,
+            },
+        );
+        self.touch_parent(path, now);
+    }
+
+    fn insert_file(&mut self, path: &Path, content: Vec<u8>) {
+        if let Some(parent) = path.parent() {
+            self.insert_dir(parent);
+        }
+
+        let now = self.tick();
+        let replaced = self.entries.insert(
+            path.to_path_buf(),
+            MemoryNode {
+                content: Some(content),
+                modified: now,
+            },
+        );
+
+        if replaced.is_none() {
+            self.touch_parent(path, now);
+        }
+    }
+
+    /// Advances the logical clock and returns the new time.
+    fn tick(&mut self) -> SystemTime {
+        self.clock += 1;
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.clock)
+    }
+
+    fn touch_parent(&mut self, path: &Path, now: SystemTime) {
+        if let Some(parent) = path
+            .parent()
+            .and_then(|parent| self.entries.get_mut(parent))
+        {
+            parent.modified = now;
+        }
+    }
+
+    /// Number of path components, used to compute walk depths.
+    fn component_count(path: &Path) -> usize {
+        path.components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .count()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        if !self.is_dir(path) {
+            return Vec::new();
+        }
+
+        self.entries
+            .range(path.to_path_buf()..)
+            .skip(1)
+            .map(|(entry, _)| entry)
+            .take_while(|entry| entry.starts_with(path))
+            .filter(|entry| entry.parent() == Some(path))
+            .cloned()
+            .collect()
+    }
+
+    fn metadata(&self, path: &Path) -> Option<FsMetadata> {
+        self.entries.get(path).map(|node| {
+            let metadata = match &node.content {
+                None => FsMetadata::new(true, 0),
+                Some(content) => FsMetadata::new(false, content.len() as u64),
+            };
+            metadata.with_modified(Some(node.modified))
+        })
+    }
+
+    fn walk(&self, root: &Path, max_depth: usize) -> FsWalk<'_> {
+        if !self.exists(root) {
+            return Box::new(std::iter::empty());
+        }
+
+        let root = root.to_path_buf();
+        let root_depth = Self::component_count(&root);
+
+        Box::new(
+            self.entries
+                .range(root.clone()..)
+                .take_while(move |(entry, _)| entry.starts_with(&root))
+                .filter_map(move |(entry, node)| {
+                    let depth = Self::component_count(entry) - root_depth;
+                    (depth <= max_depth)
+                        .then(|| FsEntry::new(entry.clone(), depth, node.content.is_none()))
+                }),
+        )
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        match self.entries.get(path).map(|node| &node.content) {
+            Some(Some(content)) => Ok(content.clone()),
+            Some(None) => Err(ErrorKind::failed()
+                .with_summary("Failed to read file")
+                .with_reason(format!("{}: is a directory", path.display()))),
+            None => Err(ErrorKind::failed()
+                .with_summary("Failed to read file")
+                .with_reason(format!("{}: not found", path.display()))),
+        }
+    }
+}
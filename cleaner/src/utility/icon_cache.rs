@@ -132,6 +132,15 @@ impl IconCache {
         Some(Self { icon_cache: map })
     }
 
+    /// Builds a cache holding already decoded RGBA pixels for a
+    /// path, without calling platform icon APIs.
+    pub fn from_rgba(path: &Path, width: usize, height: usize, rgba_bytes: Vec<u8>) -> Self {
+        let mut map = HashMap::new();
+        map.insert(Self::get_cache_key(path), (width, height, rgba_bytes));
+
+        Self { icon_cache: map }
+    }
+
     pub fn icon_cache_owned(self) -> HashMap<String, (usize, usize, Vec<u8>)> {
         self.icon_cache
     }
@@ -0,0 +1,238 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! launchd job definition parsing.
+//!
+//! LaunchAgents and LaunchDaemons are described by property
+//! lists stored in:
+//!
+//! - `~/Library/LaunchAgents`
+//! - `/Library/LaunchAgents`
+//! - `/Library/LaunchDaemons`
+//!
+//! The file name of a job definition is only a convention. The
+//! identity of the job is its `Label`, and the code it runs is
+//! described by `Program`, `ProgramArguments`, or `BundleProgram`.
+//!
+//! Design:
+//! Parsing the job definition allows background items to be
+//! attributed to an application by what they execute rather than
+//! by what they are called. This catches jobs whose names follow
+//! a vendor naming scheme unrelated to the application, and
+//! rejects jobs that merely share part of a name.
+//!
+//! Note:
+//! Only the keys relevant to ownership are parsed.
+//!..
+
+use plist::Value;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::utility::file_system::{FileSystem, RealFileSystem};
+
+/// Parsed launchd job definition.
+///
+/// Doc:
+/// Stores the ownership-related keys of a launchd property list:
+///
+/// - `Label`
+/// - `Program`
+/// - `ProgramArguments`
+/// - `AssociatedBundleIdentifiers`
+/// - `BundleProgram`
+///
+/// Note:
+/// `BundleProgram` is relative to the bundle that registered the
+/// job through `SMAppService` and is therefore interpreted
+/// relative to the application bundle.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LaunchdPlist {
+    label: String,
+    program: String,
+    program_arguments: Vec<String>,
+    associated_bundle_ids: Vec<String>,
+    bundle_program: String,
+}
+
+impl LaunchdPlist {
+    pub fn new(
+        label: String,
+        program: String,
+        program_arguments: Vec<String>,
+        associated_bundle_ids: Vec<String>,
+        bundle_program: String,
+    ) -> Self {
+        Self {
+            label,
+            program,
+            program_arguments,
+            associated_bundle_ids,
+            bundle_program,
+        }
+    }
+
+    /// Parses a launchd job definition.
+    ///
+    /// Doc:
+    /// Reads the property list at the provided path and extracts
+    /// the job label, executable references, and associated
+    /// bundle identifiers.
+    ///
+    /// `AssociatedBundleIdentifiers` may be stored either as a
+    /// single string or as an array of strings. Both forms are
+    /// accepted.
+    ///
+    /// Returns an error if:
+    ///
+    /// - The file cannot be read or parsed.
+    /// - The plist root is not a dictionary.
+    /// - The job does not declare a `Label`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        Self::from_path_with(&RealFileSystem::new(), path)
+    }
+
+    /// Parses a launchd job definition read through the provided
+    /// filesystem.
+    ///
+    /// Doc:
+    /// Same as `from_path()`.
+    pub fn from_path_with<FS>(fs: &FS, path: &Path) -> Result<Self>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let plist = fs.read_plist(path).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read launchd plist")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })?;
+
+        let dict = plist.as_dictionary().ok_or_else(|| {
+            ErrorKind::failed()
+                .with_summary("Invalid launchd plist structure")
+                .with_reason(format!("{}: root is not a dictionary", path.display()))
+        })?;
+
+        let string = |key: &str| {
+            dict.get(key)
+                .and_then(|v| v.as_string())
+                .map(str::trim)
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let strings = |key: &str| match dict.get(key) {
+            Some(Value::String(s)) => vec![s.trim().to_string()],
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| item.as_string())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let label = string("Label");
+
+        if label.is_empty() {
+            return Err(ErrorKind::failed()
+                .with_summary("Invalid launchd plist")
+                .with_reason(format!("{}: missing Label", path.display())));
+        }
+
+        Ok(Self {
+            label,
+            program: string("Program"),
+            program_arguments: strings("ProgramArguments"),
+            associated_bundle_ids: strings("AssociatedBundleIdentifiers")
+                .into_iter()
+                .filter(|id| !id.is_empty())
+                .collect(),
+            bundle_program: string("BundleProgram"),
+        })
+    }
+
+    /// get job label reference
+    pub fn as_label(&self) -> &str {
+        &self.label
+    }
+
+    /// get `Program` reference
+    pub fn as_program(&self) -> &str {
+        &self.program
+    }
+
+    /// get `ProgramArguments` reference
+    pub fn as_program_arguments(&self) -> &[String] {
+        &self.program_arguments
+    }
+
+    /// get `AssociatedBundleIdentifiers` reference
+    pub fn as_associated_bundle_ids(&self) -> &[String] {
+        &self.associated_bundle_ids
+    }
+
+    /// get `BundleProgram` reference
+    pub fn as_bundle_program(&self) -> &str {
+        &self.bundle_program
+    }
+
+    /// Returns the executable launched by the job.
+    ///
+    /// Doc:
+    /// launchd uses `Program` when present and otherwise the
+    /// first element of `ProgramArguments`.
+    ///
+    /// Note:
+    /// `BundleProgram` is not included because it is relative
+    /// to the registering bundle.
+    pub fn executable(&self) -> Option<&str> {
+        Some(self.program.as_str())
+            .filter(|program| !program.is_empty())
+            .or_else(|| self.program_arguments.first().map(String::as_str))
+            .filter(|program| !program.is_empty())
+    }
+
+    /// Returns whether the job executes code from a bundle.
+    ///
+    /// Doc:
+    /// A job references the bundle when:
+    ///
+    /// - Its executable is located inside the bundle path.
+    /// - It declares a `BundleProgram`, which always resolves
+    ///   inside the registering bundle, and lists the bundle
+    ///   identifier in `AssociatedBundleIdentifiers`.
+    ///
+    /// Note:
+    /// An empty bundle path never matches.
+    pub fn references_bundle(&self, bundle_path: &Path, bundle_id: &str) -> bool {
+        if bundle_path.as_os_str().is_empty() {
+            return false;
+        }
+
+        let executable_inside = self
+            .executable()
+            .map(|program| Path::new(program).starts_with(bundle_path))
+            .unwrap_or(false);
+
+        let bundle_program_inside = !self.bundle_program.is_empty()
+            && !bundle_id.is_empty()
+            && self
+                .associated_bundle_ids
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(bundle_id));
+
+        executable_inside || bundle_program_inside
+    }
+}
@@ -22,6 +22,7 @@
 //! - Installer receipts.
 //! - Sandboxed applications.
 //! - Background task management.
+//! - Installed applications.
 //!
 //! The location groups are used by scanner components to reduce
 //! search scope and improve performance.
@@ -41,6 +42,7 @@
 //! - `ReceiptsLocations` for installer receipts.
 //! - `SandboxLocations` for application containers.
 //! - `BtmLocations` for persistence-related components.
+//! - `ApplicationLocations` for installed applications.
 //!
 //! The module intentionally centralizes all filesystem search
 //! roots used by the application.
@@ -310,6 +312,53 @@ impl ReceiptsLocations {
     }
 }
 
+/// Installed application locations.
+///
+/// Doc:
+/// Stores directories where applications are installed.
+///
+/// These locations are used to find the other applications on
+/// the system, for example to decide whether a library is
+/// shared with them.
+///
+/// Design:
+/// Applications may be grouped in subfolders such as
+/// `/Applications/Utilities`, so callers walk two levels deep.
+///
+/// Note:
+/// Application locations consist of scan roots only and do not
+/// define additional traversal patterns.
+#[derive(Debug, Default, Clone)]
+pub struct ApplicationLocations {
+    locations: Vec<Location>,
+}
+
+impl ApplicationLocations {
+    pub fn new() -> Self {
+        let home = env::var("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"));
+
+        Self {
+            locations: vec![
+                Location::new("/Applications"),
+                Location::new(home.join("Applications")),
+            ],
+        }
+    }
+
+    pub fn as_locations(&self) -> &[Location] {
+        &self.locations
+    }
+
+    pub fn location_roots(&self) -> Vec<PathBuf> {
+        self.locations
+            .iter()
+            .map(|location| location.as_root().to_path_buf())
+            .collect()
+    }
+}
+
 /// Sandboxed application container locations.
 ///
 /// Doc:
@@ -357,26 +406,6 @@ impl SandboxLocations {
         &self.locations
     }
 
-    /// Returns the expected relative directory patterns.
-    ///
-    /// Doc:
-    /// Returns the validation patterns associated with the
-    /// sandbox scan location.
-    ///
-    /// Design:
-    /// These patterns identify directories expected to exist
-    /// within a valid application container and help reduce
-    /// false positives during discovery.
-    ///
-    /// Note:
-    /// Returned paths are relative to the sandbox location root.
-    pub fn as_pattern(&self) -> &[PathBuf] {
-        self.locations
-            .first()
-            .map(|location| location.as_patterns())
-            .unwrap_or(&[])
-    }
-
     pub fn location_roots(&self) -> Vec<PathBuf> {
         self.locations
             .iter()
@@ -496,4 +525,20 @@ impl BackgroundTaskLocations {
             .map(|location| location.as_root().to_path_buf())
             .collect()
     }
+
+    /// Returns the launchd job definition directories.
+    ///
+    /// Doc:
+    /// Returns the `LaunchAgents` and `LaunchDaemons` roots whose
+    /// property lists can be parsed as launchd job definitions.
+    ///
+    /// Note:
+    /// Preference and privileged helper locations are excluded
+    /// because they do not contain job definitions.
+    pub fn launchd_location_roots(&self) -> Vec<PathBuf> {
+        self.legacy_dir
+            .iter()
+            .map(|location| location.as_root().to_path_buf())
+            .collect()
+    }
 }
@@ -21,10 +21,16 @@
 //! The module exposes components for:
 //!
 //! - Building scan location collections.
+//! - Parsing launchd job definitions.
 //! - Defining filename and string matching rules.
+//! - Loading matching rules from configuration files.
 //! - Performing generic filesystem scans.
 //! - Performing sandbox container scans.
+//! - Abstracting filesystem access for scanners.
+//! - Reading file origin metadata from extended attributes.
+//! - Indexing scan locations for fast rescans.
 //! - Caching application icons.
+//! - Formatting byte sizes for display.
 //!
 //! Design:
 //! Common scanning functionality is centralized here to avoid
@@ -41,14 +47,31 @@
 //! scanner or cleanup workflow.
 //!...
 
+mod byte_size;
+mod file_origin;
+mod file_system;
 mod icon_cache;
+mod launchd;
 mod locations;
+mod rule_config;
 mod rules;
+mod scan_index;
 mod scanner;
 
+pub use byte_size::format_size;
+pub use file_origin::{FileOrigin, OPEN_WITH_XATTR, QUARANTINE_XATTR, WHERE_FROMS_XATTR};
+pub use file_system::{FileSystem, FsEntry, FsMetadata, FsWalk, MemoryFileSystem, RealFileSystem};
 pub use icon_cache::IconCache;
+pub use launchd::LaunchdPlist;
 pub use locations::{
-    BackgroundTaskLocations, GeneralLocations, ReceiptsLocations, SandboxLocations,
+    ApplicationLocations, BackgroundTaskLocations, GeneralLocations, Location, ReceiptsLocations,
+    SandboxLocations,
+};
+pub use rule_config::{RuleConfig, RuleKind};
+pub use rules::{CompiledRules, MatchRules, SHORT_NEEDLE_LEN};
+pub use scan_index::{IndexEntry, IndexedFileSystem, RefreshStats, ScanIndex};
+pub use scanner::{
+    CONTAINER_METADATA_FILE, construct_and_deduplicate_paths, container_identifier_with,
+    scan_container, scan_container_with, scan_general, scan_general_find_with,
+    scan_general_segments_with, scan_general_split_with, scan_general_with,
 };
-pub use rules::MatchRules;
-pub use scanner::{construct_and_deduplicate_paths, scan_container, scan_general};
@@ -0,0 +1,215 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Matching rules declared in configuration files.
+//!
+//! A configuration lists include rules, evaluated with OR
+//! semantics, and exclude rules, evaluated afterwards:
+//!
+//! ```text
+//! include:
+//!   - contain: zoom
+//!   - glob: "us.zoom.*.plist"
+//! exclude:
+//!   - contain: zoomit
+//! ```
+//!
+//! Every rule is a mapping with a single key naming its kind:
+//! `equal`, `contain`, `token`, `prefix`, `suffix`, `glob`, or
+//! `regex`.
+//!
+//! Design:
+//! The configuration only stores the declared values. It is
+//! turned into `MatchRules` with `to_match_rules`, or merged
+//! into scanner rules with `MatchRules::with_config`, so glob and
+//! regex errors are reported when the rules are built.
+//!..
+
+use serde_yaml::Value;
+use std::path::Path;
+
+use crate::errors::{ErrorKind, Result};
+use crate::utility::MatchRules;
+
+/// Kind of a configured matching rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    Equal,
+    Contain,
+    Token,
+    Prefix,
+    Suffix,
+    Glob,
+    Regex,
+}
+
+impl RuleKind {
+    /// get the configuration key of the kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Equal => "equal",
+            RuleKind::Contain => "contain",
+            RuleKind::Token => "token",
+            RuleKind::Prefix => "prefix",
+            RuleKind::Suffix => "suffix",
+            RuleKind::Glob => "glob",
+            RuleKind::Regex => "regex",
+        }
+    }
+
+    /// Resolves a configuration key.
+    ///
+    /// Returns `None` for unknown keys.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "equal" => Some(RuleKind::Equal),
+            "contain" => Some(RuleKind::Contain),
+            "token" => Some(RuleKind::Token),
+            "prefix" => Some(RuleKind::Prefix),
+            "suffix" => Some(RuleKind::Suffix),
+            "glob" => Some(RuleKind::Glob),
+            "regex" => Some(RuleKind::Regex),
+            _ => None,
+        }
+    }
+}
+
+/// Matching rules declared by configuration.
+///
+/// Doc:
+/// Holds owned include and exclude rules so they can outlive
+/// the parsed document and be borrowed by `MatchRules`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleConfig {
+    include: Vec<(RuleKind, String)>,
+    exclude: Vec<(RuleKind, String)>,
+}
+
+impl RuleConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an include rule.
+    pub fn with_include(mut self, kind: RuleKind, value: impl Into<String>) -> Self {
+        self.include.push((kind, value.into()));
+        self
+    }
+
+    /// Adds an exclude rule.
+    pub fn with_exclude(mut self, kind: RuleKind, value: impl Into<String>) -> Self {
+        self.exclude.push((kind, value.into()));
+        self
+    }
+
+    /// get include rules reference
+    pub fn as_include(&self) -> &[(RuleKind, String)] {
+        &self.include
+    }
+
+    /// get exclude rules reference
+    pub fn as_exclude(&self) -> &[(RuleKind, String)] {
+        &self.exclude
+    }
+
+    /// Returns whether no include rules are declared.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty()
+    }
+
+    /// Parses a YAML rule configuration.
+    ///
+    /// Doc:
+    /// Both `include` and `exclude` are optional lists. An empty
+    /// document yields an empty configuration.
+    ///
+    /// Returns an error if the document is not valid YAML, a
+    /// top-level key is unknown, or a rule is not a single-key
+    /// mapping of a known kind to a string.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let invalid = |reason: String| {
+            ErrorKind::failed()
+                .with_summary("Invalid rule configuration")
+                .with_reason(reason)
+        };
+
+        let document: Value = serde_yaml::from_str(yaml).map_err(|e| invalid(e.to_string()))?;
+
+        let mut config = Self::new();
+
+        let mapping = match document {
+            Value::Null => return Ok(config),
+            Value::Mapping(mapping) => mapping,
+            _ => return Err(invalid("expected a mapping at the top level".into())),
+        };
+
+        for (key, value) in mapping {
+            let section = match key.as_str() {
+                Some("include") => &mut config.include,
+                Some("exclude") => &mut config.exclude,
+                _ => return Err(invalid(format!("unknown key {:?}", key))),
+            };
+
+            let rules = match value {
+                Value::Null => continue,
+                Value::Sequence(rules) => rules,
+                _ => return Err(invalid("rule sections must be lists".into())),
+            };
+
+            for rule in rules {
+                section.push(Self::parse_rule(&rule).ok_or_else(|| {
+                    invalid(format!(
+                        "expected a single `kind: value` rule, found {:?}",
+                        rule
+                    ))
+                })?);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Loads a YAML rule configuration from disk.
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Failed to read rule configuration")
+                .with_reason(format!("{}: {}", path.display(), e))
+        })?;
+
+        Self::from_yaml(&yaml)
+    }
+
+    /// Builds matching rules borrowing this configuration.
+    ///
+    /// Returns an error if a glob or regex rule is invalid.
+    pub fn to_match_rules(&self) -> Result<MatchRules<'_>> {
+        MatchRules::new().with_config(self)
+    }
+
+    fn parse_rule(rule: &Value) -> Option<(RuleKind, String)> {
+        let mapping = rule.as_mapping()?;
+        if mapping.len() != 1 {
+            return None;
+        }
+
+        let (key, value) = mapping.iter().next()?;
+        let kind = RuleKind::from_key(key.as_str()?)?;
+
+        Some((kind, value.as_str()?.to_string()))
+    }
+}
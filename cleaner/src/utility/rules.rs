@@ -23,6 +23,13 @@
 //!
 //! - Exact matching.
 //! - Partial matching.
+//! - Whole-token matching.
+//! - Prefix and suffix matching.
+//! - Glob patterns, such as `com.vendor.*.plist`.
+//! - Regular expressions.
+//!
+//! Exclude rules can be added to reject values accepted by the
+//! other rules, for example "contains `zoom` but not `zoomit`".
 //!
 //! Supported targets include:
 //!
@@ -52,7 +59,8 @@
 //! matches caused by unrelated parent directories.
 //!
 //! Matching behavior is implemented through composable
-//! rules collected by `MatchRules`.
+//! rules collected by `MatchRules`. Scanners that evaluate the
+//! same rules many times compile them into `CompiledRules`.
 //!
 //! Note:
 //! Matching is case-insensitive and Unicode-normalized
@@ -60,12 +68,205 @@
 //! behavior and process metadata comparisons.
 //!..
 
+use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
 use std::path::Path;
 use unicode_normalization::UnicodeNormalization;
 
+use crate::errors::{ErrorKind, Result};
+use crate::utility::rule_config::{RuleConfig, RuleKind};
+
+/// Normalizes a name the way matching rules compare it.
+///
+/// Doc:
+/// Applies NFD normalization followed by lowercasing.
+pub(crate) fn normalize_name(s: &str) -> String {
+    s.nfd() // normalize to NFD (decomposed)
+        .collect::<String>()
+        .to_lowercase() // lowercase for case-insensitive comparison
+}
+
+/// Needles up to this many characters are matched as whole
+/// tokens by `MatchRules::contain_or_token`.
+pub const SHORT_NEEDLE_LEN: usize = 6;
+
+/// Splits a name into normalized tokens.
+///
+/// Doc:
+/// Tokens are separated by `.`, `-`, `_`, whitespace, and
+/// camelCase boundaries. Letters and digits form separate
+/// tokens, and an uppercase run followed by a capitalized word
+/// is split before the word:
+///
+/// ```text
+/// com.culturedcode.ThingsMac -> com, culturedcode, things, mac
+/// Things3                    -> things, 3
+/// HTMLViewer                 -> html, viewer
+/// ```
+///
+/// Each token is normalized with `normalize_name`.
+///
+/// Note:
+/// camelCase boundaries can only be seen before lowercasing, so
+/// already normalized input is split on separators only.
+pub(crate) fn tokenize_name(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if matches!(c, '.' | '-' | '_') || c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(normalize_name(&std::mem::take(&mut current)));
+            }
+            continue;
+        }
+
+        let boundary = match (current.chars().last(), chars.get(i + 1)) {
+            (Some(prev), next) => {
+                (prev.is_lowercase() && c.is_uppercase())
+                    || (prev.is_numeric() && c.is_alphabetic())
+                    || (prev.is_alphabetic() && c.is_numeric())
+                    || (prev.is_uppercase()
+                        && c.is_uppercase()
+                        && next.is_some_and(|n| n.is_lowercase()))
+            }
+            (None, _) => false,
+        };
+
+        if boundary {
+            tokens.push(normalize_name(&std::mem::take(&mut current)));
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        tokens.push(normalize_name(&current));
+    }
+
+    tokens
+}
+
+/// Returns whether `needle` appears as a contiguous token run
+/// in `tokens`.
+fn contains_tokens(tokens: &[String], needle: &[String]) -> bool {
+    !needle.is_empty() && tokens.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Translates a glob pattern into an anchored regular expression.
+///
+/// Doc:
+/// Supports:
+///
+/// - `*` for any sequence of characters.
+/// - `?` for a single character.
+/// - `[...]` and `[!...]` character classes.
+///
+/// The pattern is normalized like candidate names before
+/// translation.
+fn glob_to_regex(glob: &str) -> Result<Regex> {
+    let invalid = |reason: &str| {
+        ErrorKind::failed()
+            .with_summary("Invalid glob rule")
+            .with_reason(format!("'{}': {}", glob, reason))
+    };
+
+    let normalized = normalize_name(glob);
+    let mut pattern = String::from("^");
+    let mut chars = normalized.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '[' => {
+                let rest = chars.as_str();
+                let Some(end) = rest.find(']') else {
+                    return Err(invalid("unclosed character class"));
+                };
+                let class = &rest[..end];
+                chars = rest[end + 1..].chars();
+
+                if class.is_empty() || class == "!" {
+                    return Err(invalid("empty character class"));
+                }
+                let (negated, body) = match class.strip_prefix('!') {
+                    Some(body) => (true, body),
+                    None => (false, class),
+                };
+                pattern.push('[');
+                if negated {
+                    pattern.push('^');
+                }
+                pattern.push_str(&escape_class(body));
+                pattern.push(']');
+            }
+            other => pattern.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+
+    pattern.push('$');
+
+    Regex::new(&pattern).map_err(|e| invalid(&e.to_string()))
+}
+
+/// Escapes the body of a glob character class for the regex crate.
+///
+/// Doc:
+/// Backslashes and `[` are escaped so they stay literal. The
+/// regex crate also reads `&&`, `--` and `~~` as class set
+/// operations, so `&` and `~` are always escaped and a `-`
+/// following another `-` is escaped.
+///
+/// Note:
+/// A single `-` is kept, so ranges such as `a-z` still work.
+fn escape_class(body: &str) -> String {
+    let mut escaped = String::with_capacity(body.len());
+    let mut previous = None;
+
+    for c in body.chars() {
+        match c {
+            '\\' | '[' | '&' | '~' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '-' if previous == Some('-') => escaped.push_str("\\-"),
+            other => escaped.push(other),
+        }
+        previous = Some(c);
+    }
+
+    escaped
+}
+
+/// Compiles a user regular expression for normalized matching.
+///
+/// Note:
+/// Candidates are normalized to NFD lowercase, so the pattern is
+/// converted to NFD and matched case-insensitively. The pattern
+/// itself is not lowercased because that would change escapes
+/// such as `\D` or `\W`.
+fn compile_regex(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(&pattern.nfd().collect::<String>())
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| {
+            ErrorKind::failed()
+                .with_summary("Invalid regex rule")
+                .with_reason(format!("'{}': {}", pattern, e))
+        })
+}
+
+#[derive(Clone)]
 enum Rules {
     Equal,
     Contain,
+    Token,
+    Prefix,
+    Suffix,
+    Glob(Regex),
+    Regex(Regex),
 }
 
 impl Rules {
@@ -97,9 +298,7 @@ impl Rules {
     /// NFD normalization is used to align with common macOS
     /// filesystem behavior.
     fn normalize_lowercase(&self, s: &str) -> String {
-        s.nfd() // normalize to NFD (decomposed)
-            .collect::<String>()
-            .to_lowercase() // lowercase for case-insensitive comparison
+        normalize_name(s)
     }
 
     /// Evaluates a path against a matching rule.
@@ -115,6 +314,16 @@ impl Rules {
         match self {
             Rules::Equal => self.path_equals_ignore_case(path, value),
             Rules::Contain => self.path_contains_ignore_case(path, value),
+            Rules::Token => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|name| self.match_tokens(name, value))
+                .unwrap_or(false),
+            _ => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|name| self.match_string(name, value))
+                .unwrap_or(false),
         }
     }
 
@@ -135,9 +344,32 @@ impl Rules {
         match self {
             Rules::Equal => self.string_equals_ignore_case(text, value),
             Rules::Contain => self.string_contains_ignore_case(text, value),
+            Rules::Token => self.match_tokens(text, value),
+            Rules::Prefix => self
+                .normalize_lowercase(text)
+                .starts_with(&self.normalize_lowercase(value)),
+            Rules::Suffix => self
+                .normalize_lowercase(text)
+                .ends_with(&self.normalize_lowercase(value)),
+            Rules::Glob(pattern) | Rules::Regex(pattern) => {
+                pattern.is_match(&self.normalize_lowercase(text))
+            }
         }
     }
 
+    /// Performs whole-token matching.
+    ///
+    /// Doc:
+    /// Returns true when the tokens of `value` appear as a
+    /// contiguous run in the tokens of `text`, so `arc` matches
+    /// `company.thebrowser.Arc` but not `search`.
+    ///
+    /// Note:
+    /// Empty values never match.
+    fn match_tokens(&self, text: &str, value: &str) -> bool {
+        contains_tokens(&tokenize_name(text), &tokenize_name(value))
+    }
+
     /// Performs case-insensitive substring matching on a path name.
     ///
     /// Doc:
@@ -275,14 +507,21 @@ impl Rules {
 /// evaluation to this type.
 ///
 /// Note:
-/// Rules are evaluated using logical OR semantics.
+/// Rules are evaluated using logical OR semantics. Exclude rules
+/// are evaluated afterwards: a value accepted by the OR set is
+/// rejected when any exclude rule matches it.
+#[derive(Clone)]
 pub struct MatchRules<'a> {
     rules: Vec<(Rules, &'a str)>,
+    excludes: Vec<(Rules, &'a str)>,
 }
 
 impl<'a> MatchRules<'a> {
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self {
+            rules: Vec::new(),
+            excludes: Vec::new(),
+        }
     }
 
     /// Returns the number of registered matching rules.
@@ -374,6 +613,165 @@ impl<'a> MatchRules<'a> {
         self
     }
 
+    /// Adds a whole-token matching rule.
+    ///
+    /// Doc:
+    /// Registers a rule that succeeds when the tokens of the
+    /// provided text appear as a contiguous run in the tokens of
+    /// the target value. See `tokenize_name` for how names are
+    /// split.
+    ///
+    /// Example:
+    ///
+    /// ```text
+    /// token("Bear")
+    ///
+    /// net.shinyfrog.bear   -> match
+    /// Bear Notes           -> match
+    /// unbearable.log       -> no match
+    /// ```
+    ///
+    /// Note:
+    /// Matching is case-insensitive and Unicode-normalized.
+    pub fn token(mut self, value: &'a str) -> Self {
+        if !value.trim().is_empty() {
+            self.rules.push((Rules::Token, value));
+        }
+
+        self
+    }
+
+    /// Adds a substring rule, or a token rule for short needles.
+    ///
+    /// Doc:
+    /// Needles of at most `SHORT_NEEDLE_LEN` characters are
+    /// registered with `token()`, longer ones with `contain()`.
+    ///
+    /// Design:
+    /// Short application names such as "Arc", "Bear", or
+    /// "Things" occur inside unrelated words. Substring matching
+    /// stays useful for long needles like bundle identifiers,
+    /// which also appear glued to other text.
+    pub fn contain_or_token(self, value: &'a str) -> Self {
+        if value.trim().chars().count() <= SHORT_NEEDLE_LEN {
+            self.token(value)
+        } else {
+            self.contain(value)
+        }
+    }
+
+    /// Adds a prefix matching rule.
+    ///
+    /// Note:
+    /// Matching is case-insensitive and Unicode-normalized.
+    pub fn prefix(mut self, value: &'a str) -> Self {
+        if !value.trim().is_empty() {
+            self.rules.push((Rules::Prefix, value));
+        }
+
+        self
+    }
+
+    /// Adds a suffix matching rule.
+    ///
+    /// Note:
+    /// Matching is case-insensitive and Unicode-normalized.
+    pub fn suffix(mut self, value: &'a str) -> Self {
+        if !value.trim().is_empty() {
+            self.rules.push((Rules::Suffix, value));
+        }
+
+        self
+    }
+
+    /// Adds a glob matching rule.
+    ///
+    /// Doc:
+    /// The glob must match the complete value, for example
+    /// `com.vendor.*.plist`.
+    ///
+    /// Returns an error if the pattern contains an unclosed
+    /// character class.
+    ///
+    /// Note:
+    /// Matching is case-insensitive and Unicode-normalized.
+    pub fn glob(mut self, pattern: &'a str) -> Result<Self> {
+        if !pattern.trim().is_empty() {
+            self.rules
+                .push((Rules::Glob(glob_to_regex(pattern)?), pattern));
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a regular expression matching rule.
+    ///
+    /// Doc:
+    /// The expression matches anywhere in the value unless it
+    /// is anchored with `^` and `$`.
+    ///
+    /// Returns an error if the expression is invalid.
+    ///
+    /// Note:
+    /// Matching is case-insensitive and Unicode-normalized.
+    pub fn regex(mut self, pattern: &'a str) -> Result<Self> {
+        if !pattern.trim().is_empty() {
+            self.rules
+                .push((Rules::Regex(compile_regex(pattern)?), pattern));
+        }
+
+        Ok(self)
+    }
+
+    /// Adds exclude rules.
+    ///
+    /// Doc:
+    /// Every rule of `rules` becomes an exclude rule. A value
+    /// accepted by this collection is rejected when any exclude
+    /// rule matches it.
+    ///
+    /// Example:
+    ///
+    /// ```text
+    /// MatchRules::new()
+    ///     .contain("zoom")
+    ///     .exclude(MatchRules::new().contain("zoomit"))
+    /// ```
+    ///
+    /// Note:
+    /// Exclude rules of `rules` itself are ignored.
+    pub fn exclude(mut self, rules: MatchRules<'a>) -> Self {
+        self.excludes.extend(rules.rules);
+        self
+    }
+
+    /// Adds the rules declared by a configuration.
+    ///
+    /// Doc:
+    /// Include rules of the configuration join the OR set and
+    /// exclude rules join the exclude set.
+    ///
+    /// Returns an error if a glob or regex rule is invalid.
+    pub fn with_config(self, config: &'a RuleConfig) -> Result<Self> {
+        let add = |rules: MatchRules<'a>, (kind, value): &'a (RuleKind, String)| match kind {
+            RuleKind::Equal => Ok(rules.equal(value)),
+            RuleKind::Contain => Ok(rules.contain(value)),
+            RuleKind::Token => Ok(rules.token(value)),
+            RuleKind::Prefix => Ok(rules.prefix(value)),
+            RuleKind::Suffix => Ok(rules.suffix(value)),
+            RuleKind::Glob => rules.glob(value),
+            RuleKind::Regex => rules.regex(value),
+        };
+
+        let rules = config.as_include().iter().try_fold(self, add)?;
+        let excludes = config
+            .as_exclude()
+            .iter()
+            .try_fold(MatchRules::new(), add)?;
+
+        Ok(rules.exclude(excludes))
+    }
+
     /// Evaluates all registered rules.
     ///
     /// Doc:
@@ -415,6 +813,10 @@ impl<'a> MatchRules<'a> {
         self.rules
             .iter()
             .any(|(rule, value)| rule.match_path(path, value))
+            && !self
+                .excludes
+                .iter()
+                .any(|(rule, value)| rule.match_path(path, value))
     }
 
     /// Evaluates all registered rules against a string.
@@ -448,5 +850,228 @@ impl<'a> MatchRules<'a> {
         self.rules
             .iter()
             .any(|(rule, value)| rule.match_string(text, value))
+            && !self
+                .excludes
+                .iter()
+                .any(|(rule, value)| rule.match_string(text, value))
+    }
+
+    /// Compiles the rules for repeated evaluation.
+    ///
+    /// Doc:
+    /// Normalizes every rule value once and returns a
+    /// `CompiledRules` that gives the same answers as this
+    /// collection.
+    ///
+    /// Design:
+    /// Scanners evaluate the same rules against hundreds of
+    /// thousands of paths. Compiling them outside the matcher
+    /// closure avoids normalizing every needle for every path.
+    pub fn compile(&self) -> CompiledRules {
+        let mut compiled = CompiledRules::from_rules(&self.rules);
+
+        if !self.excludes.is_empty() {
+            compiled.exclude = Some(Box::new(CompiledRules::from_rules(&self.excludes)));
+        }
+
+        compiled
+    }
+}
+
+/// Precompiled matching rules.
+///
+/// Doc:
+/// Produced by `MatchRules::compile()`. Evaluates the same OR
+/// semantics as `MatchRules`, with:
+///
+/// - Needles normalized once at construction.
+/// - Each candidate normalized once per check.
+/// - Exact rules answered by a hash lookup.
+/// - Substring rules answered by a single Aho-Corasick pass
+///   over the candidate, whatever the number of needles.
+/// - Token rules answered against the candidate tokens, split
+///   once per check and only when token rules exist.
+///
+/// Design:
+/// The compiled form owns its normalized values, so it can be
+/// built once per scan and shared between scanner threads.
+///
+/// Prefix, suffix, glob, and regex rules are checked one by one
+/// after the two fast paths, and exclude rules are compiled the
+/// same way and checked last.
+///
+/// Note:
+/// When the automaton cannot be built, substring rules fall back
+/// to checking each needle in turn.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledRules {
+    equal: HashSet<String>,
+    needles: Vec<String>,
+    automaton: Option<AhoCorasick>,
+    tokens: Vec<Vec<String>>,
+    prefixes: Vec<String>,
+    suffixes: Vec<String>,
+    patterns: Vec<(Regex, String)>,
+    exclude: Option<Box<CompiledRules>>,
+}
+
+impl CompiledRules {
+    fn from_rules(rules: &[(Rules, &str)]) -> Self {
+        let mut compiled = Self::default();
+
+        for (rule, value) in rules {
+            match rule {
+                Rules::Equal => {
+                    compiled.equal.insert(normalize_name(value));
+                }
+                Rules::Contain => compiled.needles.push(normalize_name(value)),
+                Rules::Token => compiled.tokens.push(tokenize_name(value)),
+                Rules::Prefix => compiled.prefixes.push(normalize_name(value)),
+                Rules::Suffix => compiled.suffixes.push(normalize_name(value)),
+                Rules::Glob(pattern) | Rules::Regex(pattern) => {
+                    compiled.patterns.push((pattern.clone(), value.to_string()))
+                }
+            }
+        }
+
+        compiled.automaton = (!compiled.needles.is_empty())
+            .then(|| AhoCorasick::new(&compiled.needles).ok())
+            .flatten();
+
+        compiled
+    }
+
+    /// Returns whether no include rules were compiled.
+    pub fn is_empty(&self) -> bool {
+        self.equal.is_empty()
+            && self.needles.is_empty()
+            && self.tokens.is_empty()
+            && self.prefixes.is_empty()
+            && self.suffixes.is_empty()
+            && self.patterns.is_empty()
+    }
+
+    /// Evaluates the rules against the final path component.
+    ///
+    /// Note:
+    /// Paths whose file name is not valid UTF-8 never match.
+    pub fn check_path(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|name| self.check_text(name, &normalize_name(name)))
+            .unwrap_or(false)
+    }
+
+    /// Evaluates the rules against a string.
+    pub fn check_string(&self, text: &str) -> bool {
+        self.check_text(text, &normalize_name(text))
+    }
+
+    /// Describes the rule accepting the final path component.
+    ///
+    /// Doc:
+    /// Returns `None` when `check_path()` is false, otherwise
+    /// the first include rule that matched, such as
+    /// `name contains "com.example.cafe"`.
+    pub fn explain_path(&self, path: &Path) -> Option<String> {
+        let name = path.file_name()?.to_str()?;
+
+        self.explain_text(name, &normalize_name(name))
+            .map(|rule| format!("name {}", rule))
+    }
+
+    /// Describes the rule accepting a string.
+    ///
+    /// Doc:
+    /// Returns `None` when `check_string()` is false.
+    pub fn explain_string(&self, text: &str) -> Option<String> {
+        self.explain_text(text, &normalize_name(text))
+    }
+
+    fn explain_text(&self, text: &str, normalized: &str) -> Option<String> {
+        let excluded = self
+            .exclude
+            .as_ref()
+            .is_some_and(|exclude| exclude.includes(text, normalized));
+
+        (!excluded)
+            .then(|| self.matching_rule(text, normalized))
+            .flatten()
+    }
+
+    fn check_text(&self, text: &str, normalized: &str) -> bool {
+        self.includes(text, normalized)
+            && !self
+                .exclude
+                .as_ref()
+                .is_some_and(|exclude| exclude.includes(text, normalized))
+    }
+
+    fn includes(&self, text: &str, normalized: &str) -> bool {
+        self.matching_rule(text, normalized).is_some()
+    }
+
+    /// Describes the first include rule accepting a candidate.
+    ///
+    /// Doc:
+    /// Exact and substring rules are tried before word and
+    /// pattern rules, so the most specific rule is described.
+    /// The description quotes the normalized rule value:
+    ///
+    /// ```text
+    /// equals "cafe"
+    /// contains "com.example.cafe"
+    /// has the words "arc"
+    /// ```
+    fn matching_rule(&self, text: &str, normalized: &str) -> Option<String> {
+        if self.equal.contains(normalized) {
+            return Some(format!("equals \"{}\"", normalized));
+        }
+
+        let needle = match &self.automaton {
+            Some(automaton) => automaton
+                .find(normalized)
+                .map(|found| self.needles[found.pattern().as_usize()].as_str()),
+            None => self
+                .needles
+                .iter()
+                .find(|needle| normalized.contains(needle.as_str()))
+                .map(String::as_str),
+        };
+        if let Some(needle) = needle {
+            return Some(format!("contains \"{}\"", needle));
+        }
+
+        if let Some(prefix) = self
+            .prefixes
+            .iter()
+            .find(|prefix| normalized.starts_with(prefix.as_str()))
+        {
+            return Some(format!("starts with \"{}\"", prefix));
+        }
+
+        if let Some(suffix) = self
+            .suffixes
+            .iter()
+            .find(|suffix| normalized.ends_with(suffix.as_str()))
+        {
+            return Some(format!("ends with \"{}\"", suffix));
+        }
+
+        if !self.tokens.is_empty() {
+            let candidate = tokenize_name(text);
+            if let Some(needle) = self
+                .tokens
+                .iter()
+                .find(|needle| contains_tokens(&candidate, needle))
+            {
+                return Some(format!("has the words \"{}\"", needle.join(" ")));
+            }
+        }
+
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(normalized))
+            .map(|(_, value)| format!("matches \"{}\"", value))
     }
 }
@@ -0,0 +1,691 @@
+// Copyright 2026 ziprangga
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc:
+//! Persistent index of the scan locations.
+//!
+//! Every scan walks the same set of locations, most of which do
+//! not change between two scans. The index records every entry
+//! found below the scan roots so later scans can query it instead
+//! of walking the disk again.
+//!
+//! The module is built around:
+//!
+//! - `ScanIndex`, the recorded entries and the roots they cover.
+//! - `IndexEntry`, a single recorded path.
+//! - `RefreshStats`, the work performed by a refresh.
+//! - `IndexedFileSystem`, a `FileSystem` answering from the index.
+//!
+//! Design:
+//! The index is refreshed incrementally. A directory is listed
+//! again only when its modification time changed, which happens
+//! whenever an entry is added, removed, or renamed inside it.
+//! Unchanged directories only cost a single `stat`.
+//!
+//! The index is stored as a binary property list, using the
+//! `plist` crate already required for bundle metadata.
+//!
+//! Symbolic links below a root are recorded as links and never
+//! descended into, like the `WalkDir` traversal used without
+//! the index, so indexed and direct scans agree and link loops
+//! cannot recurse.
+//!
+//! Note:
+//! File content is never indexed. Reads through
+//! `IndexedFileSystem` always go to the underlying filesystem.
+//!..
+
+use plist::{Dictionary, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use mini_logger::debug;
+
+use crate::errors::{ErrorKind, Result};
+use crate::utility::file_system::{FileSystem, FsEntry, FsMetadata, FsWalk};
+
+/// Indexed filesystem entry.
+///
+/// Doc:
+/// Stores:
+///
+/// - The entry path.
+/// - The modification time, when available.
+/// - Whether the entry is a directory or a symbolic link, and for
+///   directories whether their children are recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    is_dir: bool,
+    is_symlink: bool,
+    listed: bool,
+}
+
+impl IndexEntry {
+    fn new(path: &Path, metadata: &FsMetadata) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            modified: metadata.modified(),
+            is_dir: metadata.is_dir(),
+            is_symlink: metadata.is_symlink(),
+            listed: false,
+        }
+    }
+
+    fn metadata(&self) -> FsMetadata {
+        FsMetadata::new(self.is_dir, 0)
+            .with_modified(self.modified)
+            .with_symlink(self.is_symlink)
+    }
+
+    /// get entry path reference
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// get the copy of modification time
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// get the copy of directory flag
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// get the copy of symbolic link flag
+    pub fn is_symlink(&self) -> bool {
+        self.is_symlink
+    }
+}
+
+/// Work performed by `ScanIndex::refresh`.
+///
+/// Doc:
+/// - `listed`: directories read because they changed or were new.
+/// - `reused`: directories whose recorded children were kept.
+/// - `removed`: entries dropped because they no longer exist.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshStats {
+    listed: usize,
+    reused: usize,
+    removed: usize,
+}
+
+impl RefreshStats {
+    /// get the copy of listed directory count
+    pub fn listed(&self) -> usize {
+        self.listed
+    }
+
+    /// get the copy of reused directory count
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    /// get the copy of removed entry count
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+}
+
+/// Persistent index of scan roots.
+///
+/// Doc:
+/// Records every entry below a set of roots, each root walked to
+/// its own maximum depth like `scan_general()` would.
+///
+/// Typical usage:
+///
+/// ```text
+/// let mut index = ScanIndex::load(&path).unwrap_or_default();
+/// index.set_roots(PathEntry::scan_roots());
+/// index.refresh(&RealFileSystem::new());
+/// let fs = IndexedFileSystem::new(&index, &RealFileSystem::new());
+/// // scan through `fs`
+/// index.save(&path)?;
+/// ```
+///
+/// Design:
+/// Roots often overlap, for example `~/Library` and
+/// `~/Library/Application Support`. Entries are therefore keyed
+/// by path and shared between roots, and each directory is
+/// listed at most once per refresh.
+///
+/// Note:
+/// The index is only as fresh as its last refresh.
+#[derive(Debug, Default, Clone)]
+pub struct ScanIndex {
+    roots: BTreeMap<PathBuf, usize>,
+    entries: BTreeMap<PathBuf, IndexEntry>,
+}
+
+impl ScanIndex {
+    /// Format version of the stored index.
+    const VERSION: u64 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a root walked to the provided depth.
+    ///
+    /// Note:
+    /// When a root is added twice, the larger depth is kept.
+    pub fn with_root(mut self, root: impl Into<PathBuf>, max_depth: usize) -> Self {
+        self.add_root(root.into(), max_depth);
+        self
+    }
+
+    /// Replaces the indexed roots.
+    ///
+    /// Doc:
+    /// Entries outside the new roots are dropped on the next
+    /// refresh. Entries inside them are kept and revalidated.
+    pub fn set_roots(&mut self, roots: impl IntoIterator<Item = (PathBuf, usize)>) {
+        self.roots.clear();
+
+        for (root, max_depth) in roots {
+            self.add_root(root, max_depth);
+        }
+    }
+
+    fn add_root(&mut self, root: PathBuf, max_depth: usize) {
+        let depth = self.roots.entry(root).or_insert(max_depth);
+        *depth = (*depth).max(max_depth);
+    }
+
+    /// get the indexed roots with their depth
+    pub fn roots(&self) -> impl Iterator<Item = (&Path, usize)> {
+        self.roots
+            .iter()
+            .map(|(root, depth)| (root.as_path(), *depth))
+    }
+
+    /// get every indexed entry, in path order
+    pub fn entries(&self) -> impl Iterator<Item = &IndexEntry> {
+        self.entries.values()
+    }
+
+    /// get indexed entry reference
+    pub fn get(&self, path: &Path) -> Option<&IndexEntry> {
+        self.entries.get(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Brings the index up to date with the filesystem.
+    ///
+    /// Doc:
+    /// Every recorded directory is checked with a single
+    /// `symlink_metadata` call. Its children are listed again only
+    /// when:
+    ///
+    /// - It is new.
+    /// - Its modification time changed or is unavailable.
+    /// - A deeper root now requires its children.
+    ///
+    /// Entries that disappeared are removed together with
+    /// everything recorded below them.
+    ///
+    /// Note:
+    /// Roots are followed when they are symbolic links, as
+    /// `WalkDir` does. Links below a root are recorded but not
+    /// descended into.
+    pub fn refresh<FS>(&mut self, fs: &FS) -> RefreshStats
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let mut stats = RefreshStats::default();
+        let roots: Vec<(PathBuf, usize)> = self
+            .roots
+            .iter()
+            .map(|(root, depth)| (root.clone(), *depth))
+            .collect();
+
+        let before = self.entries.len();
+        self.entries
+            .retain(|path, _| roots.iter().any(|(root, _)| path.starts_with(root)));
+        stats.removed += before - self.entries.len();
+
+        // Directories already refreshed in this pass, with the
+        // depth they were refreshed to.
+        let mut visited: BTreeMap<PathBuf, usize> = BTreeMap::new();
+
+        for (root, depth) in &roots {
+            let metadata = fs.metadata(root);
+            self.refresh_dir(fs, root, metadata, *depth, &mut visited, &mut stats);
+        }
+
+        debug!(
+            "scan index refreshed: {} entries, {} listed, {} reused, {} removed",
+            self.entries.len(),
+            stats.listed,
+            stats.reused,
+            stats.removed
+        );
+
+        stats
+    }
+
+    /// Refreshes a directory described by `metadata`, which is
+    /// read by the caller so roots can follow links.
+    fn refresh_dir<FS>(
+        &mut self,
+        fs: &FS,
+        dir: &Path,
+        metadata: Option<FsMetadata>,
+        remaining: usize,
+        visited: &mut BTreeMap<PathBuf, usize>,
+        stats: &mut RefreshStats,
+    ) where
+        FS: FileSystem + ?Sized,
+    {
+        if visited.get(dir).is_some_and(|done| *done >= remaining) {
+            return;
+        }
+        let first_visit = visited.insert(dir.to_path_buf(), remaining).is_none();
+
+        let Some(metadata) = metadata else {
+            stats.removed += self.remove_subtree(dir);
+            return;
+        };
+
+        if !metadata.is_dir() {
+            self.remove_children(dir, stats);
+            self.entries
+                .insert(dir.to_path_buf(), IndexEntry::new(dir, &metadata));
+            return;
+        }
+
+        let cached = self.entries.get(dir);
+        let unchanged = cached.is_some_and(|entry| {
+            entry.is_dir && metadata.modified().is_some() && entry.modified == metadata.modified()
+        });
+        // On a revisit the modification time was already updated by
+        // the first visit, so the recorded listing state decides.
+        let listed = cached.is_some_and(|entry| entry.listed) && (unchanged || !first_visit);
+
+        if remaining == 0 {
+            if !listed {
+                self.remove_children(dir, stats);
+            }
+            let mut entry = IndexEntry::new(dir, &metadata);
+            entry.listed = listed;
+            self.entries.insert(dir.to_path_buf(), entry);
+            return;
+        }
+
+        let child_dirs = if listed {
+            stats.reused += 1;
+            self.child_paths(dir)
+                .into_iter()
+                .filter(|path| self.entries.get(path).is_some_and(|entry| entry.is_dir))
+                .collect::<Vec<_>>()
+        } else {
+            stats.listed += 1;
+            self.list_dir(fs, dir, stats)
+        };
+
+        let mut entry = IndexEntry::new(dir, &metadata);
+        entry.listed = true;
+        self.entries.insert(dir.to_path_buf(), entry);
+
+        for child in child_dirs {
+            let metadata = fs.symlink_metadata(&child);
+            self.refresh_dir(fs, &child, metadata, remaining - 1, visited, stats);
+        }
+    }
+
+    /// Reads the children of a directory into the index and
+    /// returns the child directories.
+    fn list_dir<FS>(&mut self, fs: &FS, dir: &Path, stats: &mut RefreshStats) -> Vec<PathBuf>
+    where
+        FS: FileSystem + ?Sized,
+    {
+        let current = fs.read_dir(dir);
+
+        for stale in self.child_paths(dir) {
+            if !current.contains(&stale) {
+                stats.removed += self.remove_subtree(&stale);
+            }
+        }
+
+        let mut child_dirs = Vec::new();
+
+        for child in current {
+            let Some(metadata) = fs.symlink_metadata(&child) else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                // Directory entries are written by `refresh_dir`,
+                // which compares the previous modification time.
+                if !self.entries.contains_key(&child) {
+                    self.entries
+                        .insert(child.clone(), IndexEntry::new(&child, &metadata));
+                }
+                child_dirs.push(child);
+            } else {
+                self.remove_children(&child, stats);
+                self.entries
+                    .insert(child.clone(), IndexEntry::new(&child, &metadata));
+            }
+        }
+
+        child_dirs
+    }
+
+    fn child_paths(&self, dir: &Path) -> Vec<PathBuf> {
+        self.entries
+            .range(dir.to_path_buf()..)
+            .skip(1)
+            .map(|(path, _)| path)
+            .take_while(|path| path.starts_with(dir))
+            .filter(|path| path.parent() == Some(dir))
+            .cloned()
+            .collect()
+    }
+
+    fn remove_children(&mut self, dir: &Path, stats: &mut RefreshStats) {
+        for child in self.child_paths(dir) {
+            stats.removed += self.remove_subtree(&child);
+        }
+    }
+
+    /// Removes an entry and everything below it. Returns the
+    /// number of removed entries.
+    fn remove_subtree(&mut self, path: &Path) -> usize {
+        let removed: Vec<PathBuf> = self
+            .entries
+            .range(path.to_path_buf()..)
+            .map(|(entry, _)| entry)
+            .take_while(|entry| entry.starts_with(path))
+            .cloned()
+            .collect();
+
+        for entry in &removed {
+            self.entries.remove(entry);
+        }
+
+        removed.len()
+    }
+
+    /// Returns whether the index can answer for `path` and its
+    /// descendants up to `depth` levels below it.
+    fn covers(&self, path: &Path, depth: usize) -> bool {
+        self.roots.iter().any(|(root, max_depth)| {
+            path.strip_prefix(root)
+                .map(|relative| relative.components().count() + depth <= *max_depth)
+                .unwrap_or(false)
+        })
+    }
+
+    // ==================Persistence==================
+
+    /// Loads an index stored by `save()`.
+    ///
+    /// Returns an error if:
+    ///
+    /// - The file cannot be read or parsed.
+    /// - The file was written by an incompatible version.
+    pub fn load(path: &Path) -> Result<Self> {
+        let invalid = |reason: String| {
+            ErrorKind::failed()
+                .with_summary("Invalid scan index")
+                .with_reason(format!("{}: {}", path.display(), reason))
+        };
+
+        let value = Value::from_file(path).map_err(|e| invalid(e.to_string()))?;
+        let dict = value
+            .as_dictionary()
+            .ok_or_else(|| invalid("root is not a dictionary".to_string()))?;
+
+        let version = dict
+            .get("Version")
+            .and_then(|v| v.as_unsigned_integer())
+            .unwrap_or_default();
+
+        if version != Self::VERSION {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+
+        let mut index = Self::new();
+
+        for root in dict
+            .get("Roots")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_dictionary())
+        {
+            let Some(root_path) = root.get("Path").and_then(|v| v.as_string()) else {
+                continue;
+            };
+            let depth = root
+                .get("Depth")
+                .and_then(|v| v.as_unsigned_integer())
+                .unwrap_or_default();
+            index.add_root(PathBuf::from(root_path), depth as usize);
+        }
+
+        for entry in dict
+            .get("Entries")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_dictionary())
+        {
+            let Some(entry_path) = entry.get("Path").and_then(|v| v.as_string()) else {
+                continue;
+            };
+            let flag = |key: &str| entry.get(key).and_then(|v| v.as_boolean()) == Some(true);
+            let number = |key: &str| entry.get(key).and_then(|v| v.as_unsigned_integer());
+
+            let modified = number("ModifiedSeconds").map(|secs| {
+                SystemTime::UNIX_EPOCH
+                    + Duration::new(secs, number("ModifiedNanos").unwrap_or_default() as u32)
+            });
+
+            let entry_path = PathBuf::from(entry_path);
+            let metadata = FsMetadata::new(flag("Directory"), 0)
+                .with_modified(modified)
+                .with_symlink(flag("Symlink"));
+            let mut indexed = IndexEntry::new(&entry_path, &metadata);
+            indexed.listed = flag("Listed");
+            index.entries.insert(entry_path, indexed);
+        }
+
+        Ok(index)
+    }
+
+    /// Stores the index as a binary property list.
+    ///
+    /// Note:
+    /// Missing parent directories are created.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let failed = |reason: String| {
+            ErrorKind::failed()
+                .with_summary("Failed to save scan index")
+                .with_reason(format!("{}: {}", path.display(), reason))
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| failed(e.to_string()))?;
+        }
+
+        let roots = self
+            .roots
+            .iter()
+            .map(|(root, depth)| {
+                let mut dict = Dictionary::new();
+                dict.insert("Path".into(), root.to_string_lossy().into_owned().into());
+                dict.insert("Depth".into(), (*depth as u64).into());
+                Value::Dictionary(dict)
+            })
+            .collect();
+
+        let entries = self
+            .entries
+            .values()
+            .map(|entry| {
+                let mut dict = Dictionary::new();
+                dict.insert(
+                    "Path".into(),
+                    entry.path.to_string_lossy().into_owned().into(),
+                );
+                dict.insert("Directory".into(), entry.is_dir.into());
+                dict.insert("Symlink".into(), entry.is_symlink.into());
+                dict.insert("Listed".into(), entry.listed.into());
+
+                if let Some(since_epoch) = entry
+                    .modified
+                    .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+                {
+                    dict.insert("ModifiedSeconds".into(), since_epoch.as_secs().into());
+                    dict.insert(
+                        "ModifiedNanos".into(),
+                        u64::from(since_epoch.subsec_nanos()).into(),
+                    );
+                }
+
+                Value::Dictionary(dict)
+            })
+            .collect();
+
+        let mut root = Dictionary::new();
+        root.insert("Version".into(), Self::VERSION.into());
+        root.insert("Roots".into(), Value::Array(roots));
+        root.insert("Entries".into(), Value::Array(entries));
+
+        Value::Dictionary(root)
+            .to_file_binary(path)
+            .map_err(|e| failed(e.to_string()))
+    }
+}
+
+/// Filesystem answering directory queries from a `ScanIndex`.
+///
+/// Doc:
+/// `read_dir`, `metadata`, and `walk` are answered from the index
+/// when the requested path and depth fall inside an indexed root.
+/// Every other request, every file read, and every extended
+/// attribute read is forwarded to the underlying filesystem.
+///
+/// Design:
+/// Scanners already read through `FileSystem`, so the index is
+/// used without any scanner-specific code path.
+///
+/// Note:
+/// Only directory metadata is answered from the index. Files and
+/// symbolic links are forwarded, since their length and content
+/// change without changing the modification time of their
+/// directory.
+pub struct IndexedFileSystem<'a, FS: ?Sized> {
+    index: &'a ScanIndex,
+    inner: &'a FS,
+}
+
+impl<'a, FS> IndexedFileSystem<'a, FS>
+where
+    FS: FileSystem + ?Sized,
+{
+    pub fn new(index: &'a ScanIndex, inner: &'a FS) -> Self {
+        Self { index, inner }
+    }
+}
+
+impl<FS> FileSystem for IndexedFileSystem<'_, FS>
+where
+    FS: FileSystem + ?Sized,
+{
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        if !self.index.covers(path, 1) {
+            return self.inner.read_dir(path);
+        }
+
+        self.index.child_paths(path)
+    }
+
+    fn metadata(&self, path: &Path) -> Option<FsMetadata> {
+        if !self.index.covers(path, 0) {
+            return self.inner.metadata(path);
+        }
+
+        match self.index.get(path) {
+            Some(entry) if entry.is_dir => Some(entry.metadata()),
+            Some(_) => self.inner.metadata(path),
+            None => None,
+        }
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> Option<FsMetadata> {
+        if !self.index.covers(path, 0) {
+            return self.inner.symlink_metadata(path);
+        }
+
+        match self.index.get(path) {
+            Some(entry) if entry.is_dir => Some(entry.metadata()),
+            Some(_) => self.inner.symlink_metadata(path),
+            None => None,
+        }
+    }
+
+    fn walk(&self, root: &Path, max_depth: usize) -> FsWalk<'_> {
+        if !self.index.covers(root, max_depth) {
+            return self.inner.walk(root, max_depth);
+        }
+
+        let root = root.to_path_buf();
+        let root_depth = root.components().count();
+
+        Box::new(
+            self.index
+                .entries
+                .range(root.clone()..)
+                .take_while(move |(path, _)| path.starts_with(&root))
+                .filter_map(move |(path, entry)| {
+                    let depth = path.components().count() - root_depth;
+                    (depth <= max_depth).then(|| {
+                        FsEntry::new(path.clone(), depth, entry.is_dir)
+                            .with_symlink(entry.is_symlink)
+                    })
+                }),
+        )
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        self.inner.read(path)
+    }
+
+    fn read_range(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.inner.read_range(path, offset, len)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        self.inner.canonicalize(path)
+    }
+
+    fn xattr(&self, path: &Path, name: &str) -> Option<Vec<u8>> {
+        self.inner.xattr(path, name)
+    }
+}
@@ -30,7 +30,8 @@
 //! Scanning responsibilities are intentionally separated into
 //! three stages:
 //!
-//! - Discovery (`scan_general`, `scan_container`).
+//! - Discovery (`scan_general`, `scan_general_segments_with`,
+//!   `scan_container`).
 //! - Matching (caller-provided predicates).
 //! - Result normalization (`construct_scanner_result`).
 //!
@@ -55,11 +56,14 @@
 
 use rayon::prelude::*;
 use std::collections::HashSet;
+use std::env;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use walkdir::WalkDir;
+
+use crate::utility::file_system::{FileSystem, RealFileSystem};
+use crate::utility::locations::Location;
 
 /// Post-process scanner results.
 ///
@@ -131,7 +135,7 @@ where
 ///
 /// Capabilities:
 /// - Parallel directory traversal using Rayon.
-/// - Recursive scanning using WalkDir.
+/// - Recursive scanning through a `FileSystem` walk.
 /// - Configurable maximum depth.
 /// - Custom match logic through a closure.
 /// - Custom result construction through a closure.
@@ -141,7 +145,7 @@ where
 /// Scan flow:
 ///   Locations
 ///       ↓
-///   WalkDir
+///   FileSystem walk
 ///       ↓
 ///   Match Rules
 ///       ↓
@@ -185,52 +189,411 @@ where
     FProgress: Fn(usize, &Path) + Send + Sync,
     FMatch: Fn(&Path) -> bool + Send + Sync,
     FBuild: Fn(PathBuf) -> T + Send + Sync,
+{
+    scan_general_with(
+        &RealFileSystem::new(),
+        locations,
+        max_depth,
+        progress,
+        is_match,
+        build,
+    )
+}
+
+/// Generic filesystem scanner over a provided filesystem.
+///
+/// Doc:
+/// Same as `scan_general()`, reading locations through the
+/// provided `FileSystem`.
+pub fn scan_general_with<FS, T, FProgress, FMatch, FBuild>(
+    fs: &FS,
+    locations: &[PathBuf],
+    max_depth: usize,
+    progress: FProgress,
+    is_match: FMatch,
+    build: FBuild,
+) -> Vec<T>
+where
+    FS: FileSystem + ?Sized,
+    T: Send,
+    FProgress: Fn(usize, &Path) + Send + Sync,
+    FMatch: Fn(&Path) -> bool + Send + Sync,
+    FBuild: Fn(PathBuf) -> T + Send + Sync,
+{
+    scan_general_find_with(fs, locations, max_depth, progress, |path| {
+        is_match(path).then(|| build(path.to_path_buf()))
+    })
+}
+
+/// Generic filesystem scanner matching and building in one step.
+///
+/// Doc:
+/// Same as `scan_general_with()`, except that a single closure
+/// returns the result for a matching entry and `None` for any
+/// other entry.
+///
+/// Design:
+/// Matching may produce data the result needs, such as a parsed
+/// job definition or the rule that matched. Returning the result
+/// from the matcher keeps that work to once per entry.
+pub fn scan_general_find_with<FS, T, FProgress, FFind>(
+    fs: &FS,
+    locations: &[PathBuf],
+    max_depth: usize,
+    progress: FProgress,
+    find: FFind,
+) -> Vec<T>
+where
+    FS: FileSystem + ?Sized,
+    T: Send,
+    FProgress: Fn(usize, &Path) + Send + Sync,
+    FFind: Fn(&Path) -> Option<T> + Send + Sync,
 {
     let counter = Arc::new(AtomicUsize::new(0));
     let progress = Arc::new(progress);
 
     locations
         .par_iter()
-        .filter(|base| base.exists())
+        .filter(|base| fs.exists(base))
         .flat_map_iter(|base| {
-            WalkDir::new(base)
-                .max_depth(max_depth)
-                .into_iter()
-                .filter_map(|e| e.ok())
+            fs.walk(base, max_depth)
                 .flat_map(|entry| {
-                    let path_buf = entry.path().to_path_buf();
+                    let path_buf = entry.into_path();
 
                     let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
                     if n.is_multiple_of(256) {
                         progress(n, &path_buf);
                     }
 
-                    if is_match(&path_buf) {
-                        Some(build(path_buf))
-                    } else {
-                        None
+                    find(&path_buf)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Names ignored when deciding whether a directory holds only
+/// matched entries.
+const IGNORED_NAMES: [&str; 2] = [".DS_Store", ".localized"];
+
+/// Generic filesystem scanner matching every path segment.
+///
+/// Doc:
+/// Same as `scan_general_with()`, except that:
+///
+/// - An entry matches when any of its components below the scan
+///   root matches, and only the highest matching component is
+///   reported.
+/// - The reported path is the highest unique owning directory
+///   of that component.
+///
+/// A directory owns a match when every other entry it contains
+/// also matches. Walking up stops below the scan root, at any
+/// of the scan `locations`, and below any direct child of
+/// `~/Library`, `/Library`, or `/private/var`:
+///
+/// ```text
+/// ~/Library/Application Support/Vendor Inc/AppName
+///
+/// Vendor Inc = { AppName, .DS_Store }           -> Vendor Inc
+/// Vendor Inc = { AppName, OtherApp }            -> AppName
+/// ```
+///
+/// Design:
+/// `scan_general()` only looks at the final component, so a
+/// vendor directory holding nothing but the application's data
+/// is left behind after cleanup. Proposing the owning directory
+/// lets `construct_and_deduplicate_paths()` remove the whole
+/// vendor directory in that case.
+///
+/// Note:
+/// `.DS_Store` and `.localized` are ignored when checking what a
+/// directory contains.
+pub fn scan_general_segments_with<FS, T, FProgress, FMatch, FBuild>(
+    fs: &FS,
+    locations: &[PathBuf],
+    max_depth: usize,
+    progress: FProgress,
+    is_match: FMatch,
+    build: FBuild,
+) -> Vec<T>
+where
+    FS: FileSystem + ?Sized,
+    T: Send,
+    FProgress: Fn(usize, &Path) + Send + Sync,
+    FMatch: Fn(&Path) -> bool + Send + Sync,
+    FBuild: Fn(PathBuf) -> T + Send + Sync,
+{
+    let counter = Arc::new(AtomicUsize::new(0));
+    let progress = Arc::new(progress);
+
+    locations
+        .par_iter()
+        .filter(|base| fs.exists(base))
+        .flat_map_iter(|base| {
+            fs.walk(base, max_depth)
+                .filter(|entry| entry.depth() > 0)
+                .filter_map(|entry| {
+                    let path_buf = entry.into_path();
+
+                    let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                    if n.is_multiple_of(256) {
+                        progress(n, &path_buf);
                     }
+
+                    // Report each match once, from the entry of its
+                    // highest matching component.
+                    let higher_match = path_buf
+                        .ancestors()
+                        .skip(1)
+                        .take_while(|ancestor| *ancestor != base.as_path())
+                        .any(&is_match);
+
+                    if higher_match || !is_match(&path_buf) {
+                        return None;
+                    }
+
+                    let owner = owning_directory(fs, base, locations, path_buf, &is_match);
+                    Some(build(owner))
                 })
                 .collect::<Vec<_>>()
         })
         .collect()
 }
 
+/// Generic filesystem scanner splitting matches from other
+/// findings in one walk.
+///
+/// Doc:
+/// Entries accepted by `is_match` are built as in
+/// `scan_general_with()`, or as in `scan_general_segments_with()`
+/// when `segments` is set. Entries below such a match are not
+/// visited.
+///
+/// Every other entry is offered to `find_other`, whose results
+/// are returned separately:
+///
+/// ```text
+/// Caches/com.example.cafe          -> is_match, subtree skipped
+/// Caches/thumbnails.db             -> find_other
+/// ```
+///
+/// Design:
+/// `find_other` may be expensive, such as reading extended
+/// attributes. Sharing the walk and skipping matched subtrees
+/// avoids a second traversal and checking the ancestors of every
+/// entry again.
+///
+/// Note:
+/// Walks list a directory before its contents, so the entries
+/// below a match directly follow it.
+#[allow(clippy::too_many_arguments)]
+pub fn scan_general_split_with<FS, T, FProgress, FMatch, FBuild, FOther>(
+    fs: &FS,
+    locations: &[PathBuf],
+    max_depth: usize,
+    segments: bool,
+    progress: FProgress,
+    is_match: FMatch,
+    build: FBuild,
+    find_other: FOther,
+) -> (Vec<T>, Vec<T>)
+where
+    FS: FileSystem + ?Sized,
+    T: Send,
+    FProgress: Fn(usize, &Path) + Send + Sync,
+    FMatch: Fn(&Path) -> bool + Send + Sync,
+    FBuild: Fn(PathBuf) -> T + Send + Sync,
+    FOther: Fn(&Path) -> Option<T> + Send + Sync,
+{
+    let counter = Arc::new(AtomicUsize::new(0));
+    let progress = Arc::new(progress);
+
+    let found: Vec<(Vec<T>, Vec<T>)> = locations
+        .par_iter()
+        .filter(|base| fs.exists(base))
+        .map(|base| {
+            let mut matches = Vec::new();
+            let mut others = Vec::new();
+            let mut matched: Option<PathBuf> = None;
+
+            for entry in fs.walk(base, max_depth) {
+                let depth = entry.depth();
+                let path_buf = entry.into_path();
+
+                let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                if n.is_multiple_of(256) {
+                    progress(n, &path_buf);
+                }
+
+                if matched
+                    .as_ref()
+                    .is_some_and(|matched| path_buf.starts_with(matched))
+                {
+                    continue;
+                }
+
+                // The scan root itself is never proposed as an
+                // owning directory.
+                if (depth > 0 || !segments) && is_match(&path_buf) {
+                    matched = Some(path_buf.clone());
+
+                    let path = if segments {
+                        owning_directory(fs, base, locations, path_buf, &is_match)
+                    } else {
+                        path_buf
+                    };
+                    matches.push(build(path));
+                } else if let Some(other) = find_other(&path_buf) {
+                    others.push(other);
+                }
+            }
+
+            (matches, others)
+        })
+        .collect();
+
+    found.into_iter().fold(
+        (Vec::new(), Vec::new()),
+        |(mut all_matches, mut all_others), (matches, others)| {
+            all_matches.extend(matches);
+            all_others.extend(others);
+            (all_matches, all_others)
+        },
+    )
+}
+
+/// Returns the highest directory owning a matched path.
+///
+/// Doc:
+/// Walks up from `path` while the parent lies below `base`, is
+/// not one of `locations`, and contains nothing but the current
+/// owner, matching entries, and ignored names.
+///
+/// Walking up also stops below any direct child of
+/// `~/Library`, `/Library`, and `/private/var`, so a category
+/// directory such as `Application Support` is never proposed.
+///
+/// Note:
+/// A parent listing no entries could not be read, since it
+/// holds at least the current owner. It is treated as not
+/// exclusive.
+fn owning_directory<FS, FMatch>(
+    fs: &FS,
+    base: &Path,
+    locations: &[PathBuf],
+    path: PathBuf,
+    is_match: &FMatch,
+) -> PathBuf
+where
+    FS: FileSystem + ?Sized,
+    FMatch: Fn(&Path) -> bool,
+{
+    let home_library = env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/Users/Unknown"))
+        .join("Library");
+    let protected = [
+        home_library.as_path(),
+        Path::new("/Library"),
+        Path::new("/private/var"),
+    ];
+
+    let mut owner = path;
+
+    while let Some(parent) = owner.parent() {
+        if parent == base
+            || !parent.starts_with(base)
+            || locations.iter().any(|location| location == parent)
+            || protected
+                .iter()
+                .any(|root| parent == *root || parent.parent() == Some(*root))
+        {
+            break;
+        }
+
+        let children = fs.read_dir(parent);
+        let exclusive = !children.is_empty()
+            && children.iter().all(|child| {
+                *child == owner
+                    || is_match(child)
+                    || child
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|name| IGNORED_NAMES.contains(&name))
+                        .unwrap_or(false)
+            });
+
+        if !exclusive {
+            break;
+        }
+
+        owner = parent.to_path_buf();
+    }
+
+    owner
+}
+
+/// File written by `containermanagerd` at the root of every
+/// sandbox and group container.
+pub const CONTAINER_METADATA_FILE: &str = ".com.apple.containermanagerd.metadata.plist";
+
+/// Reads the owning identifier of a sandbox container.
+///
+/// Doc:
+/// Returns `MCMMetadataIdentifier` from the container metadata
+/// file: the bundle identifier of an application container, or
+/// the group identifier of a group container.
+///
+/// Note:
+/// Returns `None` when the metadata is missing, unreadable, or
+/// does not declare an identifier.
+pub fn container_identifier_with<FS>(fs: &FS, container_dir: &Path) -> Option<String>
+where
+    FS: FileSystem + ?Sized,
+{
+    let plist = fs
+        .read_plist(&container_dir.join(CONTAINER_METADATA_FILE))
+        .ok()?;
+
+    plist
+        .as_dictionary()?
+        .get("MCMMetadataIdentifier")?
+        .as_string()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
 /// Scans sandbox container directories.
 ///
 /// Doc:
 /// Searches sandbox containers for files associated with an
 /// application.
 ///
+/// Ownership of each container is resolved in order:
+///
+/// 1. `MCMMetadataIdentifier` from the container metadata file,
+///    checked with `is_identifier_match`.
+/// 2. The container directory name, checked with
+///    `is_container_match`.
+/// 3. Files inside the location's patterns, checked with
+///    `is_file_match`.
+///
 /// Design:
 /// Sandbox containers require specialized scanning because
 /// application ownership is often represented by files stored
 /// inside the container rather than by the container directory
 /// name itself.
 ///
-/// Instead of recursively traversing the entire container,
-/// the scanner inspects a predefined set of known locations
-/// inside each container.
+/// The metadata identifier is authoritative: a container that
+/// declares another owner is never attributed by its name or
+/// contents. Containers with UUID names are attributed exactly
+/// this way.
+///
+/// When the metadata is unavailable, the scanner inspects the
+/// patterns of the location the container belongs to instead
+/// of recursively traversing the entire container.
 ///
 /// This significantly reduces filesystem traversal while
 /// still providing reliable application identification.
@@ -239,11 +602,11 @@ where
 /// Container scanning is intentionally separate from
 /// `scan_general()` because its discovery strategy differs
 /// substantially from normal filesystem traversal.
-pub fn scan_container<T, FProgress, FContainerMatch, FFileMatch, FBuild>(
-    locations: &[PathBuf],
+pub fn scan_container<T, FProgress, FIdentifierMatch, FContainerMatch, FFileMatch, FBuild>(
+    locations: &[Location],
     max_depth: usize,
-    patterns: &[PathBuf],
     progress: FProgress,
+    is_identifier_match: FIdentifierMatch,
     is_container_match: FContainerMatch,
     is_file_match: FFileMatch,
     build: FBuild,
@@ -251,6 +614,53 @@ pub fn scan_container<T, FProgress, FContainerMatch, FFileMatch, FBuild>(
 where
     T: Send,
     FProgress: Fn(usize, &Path) + Send + Sync,
+    FIdentifierMatch: Fn(&str) -> bool + Send + Sync,
+    FContainerMatch: Fn(&Path) -> bool + Send + Sync,
+    FFileMatch: Fn(&Path) -> bool + Send + Sync,
+    FBuild: Fn(&Path, &Path) -> T + Send + Sync,
+{
+    scan_container_with(
+        &RealFileSystem::new(),
+        locations,
+        max_depth,
+        progress,
+        is_identifier_match,
+        is_container_match,
+        is_file_match,
+        build,
+    )
+}
+
+/// Scans sandbox container directories over a provided
+/// filesystem.
+///
+/// Doc:
+/// Same as `scan_container()`, reading containers through the
+/// provided `FileSystem`.
+#[allow(clippy::too_many_arguments)]
+pub fn scan_container_with<
+    FS,
+    T,
+    FProgress,
+    FIdentifierMatch,
+    FContainerMatch,
+    FFileMatch,
+    FBuild,
+>(
+    fs: &FS,
+    locations: &[Location],
+    max_depth: usize,
+    progress: FProgress,
+    is_identifier_match: FIdentifierMatch,
+    is_container_match: FContainerMatch,
+    is_file_match: FFileMatch,
+    build: FBuild,
+) -> Vec<T>
+where
+    FS: FileSystem + ?Sized,
+    T: Send,
+    FProgress: Fn(usize, &Path) + Send + Sync,
+    FIdentifierMatch: Fn(&str) -> bool + Send + Sync,
     FContainerMatch: Fn(&Path) -> bool + Send + Sync,
     FFileMatch: Fn(&Path) -> bool + Send + Sync,
     FBuild: Fn(&Path, &Path) -> T + Send + Sync,
@@ -260,44 +670,42 @@ where
 
     locations
         .par_iter()
-        .filter(|base| base.exists())
-        .flat_map_iter(|base| {
-            WalkDir::new(base)
-                .max_depth(max_depth)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|entry| entry.depth() == 1 && entry.file_type().is_dir())
+        .filter(|location| fs.exists(location.as_root()))
+        .flat_map_iter(|location| {
+            fs.walk(location.as_root(), max_depth)
+                .filter(|entry| entry.depth() == 1 && entry.is_dir())
                 .filter_map(|entry| {
-                    let container_dir = entry.path().to_path_buf();
+                    let container_dir = entry.into_path();
 
                     let n = counter.fetch_add(1, Ordering::Relaxed) + 1;
                     if n.is_multiple_of(256) {
                         progress(n, &container_dir);
                     }
 
+                    if let Some(identifier) = container_identifier_with(fs, &container_dir) {
+                        return is_identifier_match(&identifier).then(|| {
+                            build(&container_dir, &container_dir.join(CONTAINER_METADATA_FILE))
+                        });
+                    }
+
                     if is_container_match(&container_dir) {
                         return Some(build(&container_dir, &container_dir));
                     }
 
-                    patterns.par_iter().find_map_any(|pattern| {
+                    location.as_patterns().par_iter().find_map_any(|pattern| {
                         let pattern_dir = container_dir.join(pattern);
 
-                        if !pattern_dir.is_dir() {
+                        if !fs.is_dir(&pattern_dir) {
                             return None;
                         }
 
-                        std::fs::read_dir(&pattern_dir)
-                            .ok()?
-                            .filter_map(|e| e.ok())
-                            .find_map(|file| {
-                                let file_path = file.path();
-
-                                if is_file_match(&file_path) {
-                                    Some(build(&container_dir, &file_path))
-                                } else {
-                                    None
-                                }
-                            })
+                        fs.read_dir(&pattern_dir).into_iter().find_map(|file_path| {
+                            if is_file_match(&file_path) {
+                                Some(build(&container_dir, &file_path))
+                            } else {
+                                None
+                            }
+                        })
                     })
                 })
                 .collect::<Vec<_>>()
@@ -54,6 +54,327 @@ fn test_app_metadata_from_temp_path() -> cleaner::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_app_metadata_rich_info_plist() -> cleaner::Result<()> {
+    let app_path = std::env::temp_dir().join("test_rich_metadata.app");
+    fs::create_dir_all(app_path.join("Contents")).map_err(|e| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    })?;
+
+    let plist_content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.vendor.rich</string>
+    <key>CFBundleExecutable</key>
+    <string>rich</string>
+    <key>CFBundleName</key>
+    <string>Rich Editor</string>
+    <key>CFBundleShortVersionString</key>
+    <string>2.1.0</string>
+    <key>CFBundleVersion</key>
+    <string>2100</string>
+    <key>LSMinimumSystemVersion</key>
+    <string>12.0</string>
+    <key>CFBundleURLTypes</key>
+    <array>
+        <dict>
+            <key>CFBundleURLSchemes</key>
+            <array>
+                <string>rich</string>
+                <string>rich-auth</string>
+            </array>
+        </dict>
+    </array>
+    <key>CFBundleDocumentTypes</key>
+    <array>
+        <dict>
+            <key>CFBundleTypeName</key>
+            <string>Rich Document</string>
+            <key>LSItemContentTypes</key>
+            <array>
+                <string>com.vendor.rich.document</string>
+                <string>com.vendorcorp.rich.document</string>
+                <string>public.plain-text</string>
+            </array>
+            <key>CFBundleTypeExtensions</key>
+            <array>
+                <string>rich</string>
+            </array>
+        </dict>
+    </array>
+    <key>SMPrivilegedExecutables</key>
+    <dict>
+        <key>com.vendor.rich.helper</key>
+        <string>identifier "com.vendor.rich.helper" and anchor apple generic and certificate leaf[subject.OU] = "ABCDE12345"</string>
+    </dict>
+</dict>
+</plist>
+"#;
+    fs::write(app_path.join("Contents/Info.plist"), plist_content).map_err(|e| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    })?;
+
+    let metadata = cleaner::Metadata::from_path(&app_path)?;
+
+    assert_eq!(metadata.as_name(), "Rich Editor");
+    assert_eq!(metadata.as_version_string(), "2.1.0 (2100)");
+    assert_eq!(metadata.as_minimum_system_version(), "12.0");
+    assert_eq!(metadata.as_url_schemes(), ["rich", "rich-auth"]);
+    assert_eq!(metadata.as_document_types().len(), 1);
+    assert_eq!(metadata.as_document_types()[0].as_extensions(), ["rich"]);
+    assert_eq!(
+        metadata.as_privileged_executables(),
+        ["com.vendor.rich.helper"]
+    );
+    assert_eq!(metadata.as_team_identifier(), "ABCDE12345");
+    assert_eq!(
+        metadata.extra_identifiers(),
+        ["com.vendor.rich.helper", "com.vendor.rich.document"]
+    );
+
+    // A two-part bundle identifier has no namespace of its own.
+    let short_id = plist_content.replace(
+        "<string>com.vendor.rich</string>",
+        "<string>com.vendor</string>",
+    );
+    let fs_short = cleaner::MemoryFileSystem::new()
+        .with_file("/Applications/Rich.app/Contents/Info.plist", short_id);
+    let metadata = cleaner::Metadata::from_path_with(
+        &fs_short,
+        std::path::Path::new("/Applications/Rich.app"),
+    )?;
+    assert_eq!(metadata.extra_identifiers(), ["com.vendor.rich.helper"]);
+
+    let _ = fs::remove_dir_all(&app_path);
+
+    Ok(())
+}
+
+fn write_bundle_plist(bundle: &std::path::Path, bundle_id: &str, executable: &str) {
+    fs::create_dir_all(bundle.join("Contents")).expect("create bundle");
+    let plist_content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>{bundle_id}</string>
+    <key>CFBundleExecutable</key>
+    <string>{executable}</string>
+</dict>
+</plist>
+"#
+    );
+    fs::write(bundle.join("Contents/Info.plist"), plist_content).expect("write plist");
+}
+
+#[test]
+fn test_app_metadata_nested_bundles() -> cleaner::Result<()> {
+    let app_path = std::env::temp_dir().join("test_nested_bundles.app");
+    let _ = fs::remove_dir_all(&app_path);
+
+    let contents = app_path.join("Contents");
+    write_bundle_plist(&app_path, "com.vendor.app", "App");
+    write_bundle_plist(
+        &contents.join("Library/LoginItems/App Launcher.app"),
+        "com.vendor.app.launcher",
+        "App Launcher",
+    );
+    write_bundle_plist(
+        &contents.join("PlugIns/Share.appex"),
+        "com.vendor.app.share",
+        "Share",
+    );
+    write_bundle_plist(
+        &contents.join("XPCServices/Renderer.xpc"),
+        "com.vendor.renderer",
+        "Renderer",
+    );
+    write_bundle_plist(
+        &contents.join("Frameworks/App Framework.framework/Versions/A/Helpers/App Helper.app"),
+        "com.vendor.app.helper",
+        "App Helper",
+    );
+    fs::create_dir_all(contents.join("Library/LaunchServices")).expect("create dir");
+    fs::write(
+        contents.join("Library/LaunchServices/com.vendor.app.privileged"),
+        b"\xcf\xfa\xed\xfe",
+    )
+    .expect("write helper");
+
+    let metadata = cleaner::Metadata::from_path(&app_path)?;
+
+    let mut ids = metadata.nested_bundle_ids();
+    ids.sort_unstable();
+    assert_eq!(
+        ids,
+        [
+            "com.vendor.app.helper",
+            "com.vendor.app.launcher",
+            "com.vendor.app.privileged",
+            "com.vendor.app.share",
+            "com.vendor.renderer",
+        ]
+    );
+
+    let helper = metadata
+        .as_nested_bundles()
+        .iter()
+        .find(|nested| nested.as_bundle_id() == "com.vendor.app.helper")
+        .expect("helper bundle");
+    assert_eq!(helper.kind(), cleaner::NestedBundleKind::Helper);
+    assert!(metadata.nested_executable_names().contains(&"App Launcher"));
+
+    let _ = fs::remove_dir_all(&app_path);
+
+    Ok(())
+}
+
+#[test]
+fn test_launchd_plist_references_bundle() -> cleaner::Result<()> {
+    let dir = std::env::temp_dir().join("test_launchd_plist");
+    fs::create_dir_all(&dir).map_err(|e| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    })?;
+
+    let updater = dir.join("com.vendorcorp.updater.plist");
+    fs::write(
+        &updater,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.vendorcorp.updater</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>/Applications/Vendor.app/Contents/MacOS/Updater</string>
+        <string>--daemon</string>
+    </array>
+</dict>
+</plist>
+"#,
+    )
+    .expect("write updater plist");
+
+    let agent = dir.join("com.vendor.app.agent.plist");
+    fs::write(
+        &agent,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.vendor.app.agent</string>
+    <key>BundleProgram</key>
+    <string>Contents/Resources/agent</string>
+    <key>AssociatedBundleIdentifiers</key>
+    <string>com.vendor.app</string>
+</dict>
+</plist>
+"#,
+    )
+    .expect("write agent plist");
+
+    let bundle = std::path::Path::new("/Applications/Vendor.app");
+
+    let job = cleaner::utility::LaunchdPlist::from_path(&updater)?;
+    assert_eq!(job.as_label(), "com.vendorcorp.updater");
+    assert_eq!(
+        job.executable(),
+        Some("/Applications/Vendor.app/Contents/MacOS/Updater")
+    );
+    assert!(job.references_bundle(bundle, "com.vendor.app"));
+    assert!(!job.references_bundle(
+        std::path::Path::new("/Applications/Vendor Lite.app"),
+        "com.vendor.lite"
+    ));
+
+    let job = cleaner::utility::LaunchdPlist::from_path(&agent)?;
+    assert_eq!(job.as_associated_bundle_ids(), ["com.vendor.app"]);
+    assert!(job.references_bundle(bundle, "com.vendor.app"));
+    assert!(!job.references_bundle(bundle, "com.other.app"));
+
+    let _ = fs::remove_dir_all(&dir);
+
+    Ok(())
+}
+
+#[test]
+fn test_service_jobs_unloaded_with_recording_control() -> cleaner::Result<()> {
+    use cleaner::control::{
+        RecordingServiceControl, ServiceControl, ServiceDomain, ServiceJob, ServiceOutcome,
+    };
+    use cleaner::path_data::PathData;
+
+    let dir = std::env::temp_dir().join("test_service_control");
+    let agents = dir.join("LaunchAgents");
+    let daemons = dir.join("LaunchDaemons");
+    fs::create_dir_all(&agents).expect("create LaunchAgents");
+    fs::create_dir_all(&daemons).expect("create LaunchDaemons");
+
+    let agent = agents.join("com.vendor.app.agent.plist");
+    fs::write(
+        &agent,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.vendor.app.agent-job</string>
+</dict>
+</plist>
+"#,
+    )
+    .expect("write agent plist");
+
+    // Unparseable definitions fall back to the file stem.
+    let daemon = daemons.join("com.vendor.app.daemon.plist");
+    fs::write(&daemon, "not a plist").expect("write daemon plist");
+
+    let other = dir.join("com.vendor.app.btm");
+    fs::write(&other, "").expect("write other file");
+
+    let path_data = |path: &PathBuf| {
+        PathData::new(
+            path.clone(),
+            path.file_name().unwrap().to_string_lossy().into_owned(),
+        )
+    };
+
+    let agent_job = ServiceJob::from_path(&path_data(&agent)).expect("agent job");
+    assert_eq!(agent_job.as_label(), "com.vendor.app.agent-job");
+    assert_eq!(agent_job.domain(), ServiceDomain::Gui);
+    assert_eq!(agent_job.domain().target(501), "gui/501");
+
+    let daemon_job = ServiceJob::from_path(&path_data(&daemon)).expect("daemon job");
+    assert_eq!(daemon_job.as_label(), "com.vendor.app.daemon");
+    assert_eq!(daemon_job.domain(), ServiceDomain::System);
+
+    assert!(ServiceJob::from_path(&path_data(&other)).is_none());
+
+    let control = RecordingServiceControl::new()
+        .with_outcome("com.vendor.app.daemon", ServiceOutcome::RequiresAdmin);
+
+    assert_eq!(control.unload(&agent_job), ServiceOutcome::Unloaded);
+    assert_eq!(control.unload(&daemon_job), ServiceOutcome::RequiresAdmin);
+    assert_eq!(control.requests(), vec![agent_job, daemon_job.clone()]);
+
+    let entry = cleaner::control::ControlEntry::new(
+        Vec::new(),
+        vec![(daemon_job.clone(), ServiceOutcome::RequiresAdmin)],
+    );
+    assert_eq!(entry.services_requiring_admin(), vec![&daemon_job]);
+
+    let _ = fs::remove_dir_all(&dir);
+
+    Ok(())
+}
+
 #[test]
 fn test_running_processes_mock() -> cleaner::Result<()> {
     // Create temporary .app folder
@@ -97,9 +418,48 @@ fn test_running_processes_mock() -> cleaner::Result<()> {
 
     let mut cleaner = cleaner::Cleaner::new(app_profile);
 
-    // Call find_app_processess; since nothing is really running, we just check it doesn't panic
-    let _cleaner_ref = cleaner.find_app_process(None::<fn(std::borrow::Cow<'static, str>)>)?;
-    // assert!(processes.is_empty());
+    let executable = app_path.join("Contents/MacOS/test");
+    let process = |pid: i32, parent: i32, name: &str, command: &str| {
+        cleaner::ProcessInfo::new(pid, Some(parent), name.to_string(), command.to_string())
+    };
+
+    let source = cleaner::StaticProcessSource::new(vec![
+        process(1, 0, "launchd", "/sbin/launchd"),
+        process(100, 1, "test", &executable.to_string_lossy()).with_exe(Some(executable.clone())),
+        process(101, 100, "python3", "python3 worker.py").with_exe(Some("/usr/bin/python3".into())),
+        process(102, 101, "sh", "sh -c true"),
+        process(200, 1, "grep", "grep com.example.test"),
+        process(300, 1, "Finder", "/System/Library/CoreServices/Finder.app"),
+    ]);
+
+    cleaner.find_app_process_with(&source, None::<fn(std::borrow::Cow<'static, str>)>)?;
+
+    let found: Vec<(i32, cleaner::MatchReason)> = cleaner
+        .as_app_profile()
+        .as_process_entry()
+        .list()
+        .iter()
+        .map(|p| (p.pid(), p.match_reason()))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            (100, cleaner::MatchReason::ExecutablePath),
+            (101, cleaner::MatchReason::ChildOf(100)),
+            (102, cleaner::MatchReason::ChildOf(101)),
+            (200, cleaner::MatchReason::NameOrCommand),
+        ]
+    );
+
+    let depths: Vec<(usize, i32)> = cleaner
+        .as_app_profile()
+        .as_process_entry()
+        .tree()
+        .into_iter()
+        .map(|(depth, p)| (depth, p.pid()))
+        .collect();
+    assert_eq!(depths, vec![(0, 100), (1, 101), (2, 102), (0, 200)]);
 
     // Optional cleanup
     let _ = fs::remove_dir_all(&app_path);
@@ -107,37 +467,263 @@ fn test_running_processes_mock() -> cleaner::Result<()> {
     Ok(())
 }
 
-// Optional: test kill_processes
-// Be careful: this can kill actual running processes, so usually skipped in automated tests
 #[test]
-#[ignore]
-fn test_kill_processes_safe() -> cleaner::Result<()> {
-    // let cleaner = cleaner::Cleaner::default();
-    // Use a dummy .app path
+fn test_kill_processes_children_first() -> cleaner::Result<()> {
+    use cleaner::control::{
+        ProcessSignal, RecordingProcessControl, TerminationOutcome, TerminationPolicy,
+    };
+
     let app_path: PathBuf = PathBuf::from("/Applications/NonExistent.app");
     let app_metadata = cleaner::Metadata::new(
         app_path.clone(),
         "NonExistent.app".to_string(),
-        "com.example.test".to_string(),
+        "com.example.nonexistent".to_string(),
         "NonExistent".to_string(),
         "example".to_string(),
-        "ex".to_string(),
+        "nonexistent".to_string(),
     );
-    let path_entry = cleaner::PathEntry::from_metadata(&app_metadata);
-
     let app_profile = cleaner::AppProfile::new(
         app_metadata,
         cleaner::ProcessEntry::default(),
-        // cleaner::AppLogReceipt::default(),
-        path_entry,
+        cleaner::PathEntry::default(),
     );
 
+    let executable = app_path.join("Contents/MacOS/NonExistent");
+    let helper = app_path.join("Contents/Frameworks/NonExistent Helper.app/Contents/MacOS/Helper");
+    let process = |pid: i32, parent: i32, exe: &PathBuf| {
+        cleaner::ProcessInfo::new(
+            pid,
+            Some(parent),
+            exe.file_name().unwrap().to_string_lossy().into_owned(),
+            exe.to_string_lossy().into_owned(),
+        )
+        .with_exe(Some(exe.clone()))
+    };
+    let source = cleaner::StaticProcessSource::new(vec![
+        process(10, 1, &executable),
+        process(11, 10, &helper),
+        process(12, 10, &helper),
+        process(13, 10, &helper),
+        process(14, 10, &helper),
+        // Only mentions the bundle identifier.
+        cleaner::ProcessInfo::new(
+            20,
+            Some(1),
+            "grep".to_string(),
+            "grep com.example.nonexistent".to_string(),
+        )
+        .with_exe(Some(PathBuf::from("/usr/bin/grep"))),
+    ]);
+
+    let control = RecordingProcessControl::new()
+        .with_ignored_terminate(11)
+        .with_protected(12)
+        .with_exited(13);
+    let policy = TerminationPolicy::new()
+        .with_grace_period(std::time::Duration::from_millis(20))
+        .with_poll_interval(std::time::Duration::from_millis(5));
+
     let mut cleaner_instant = cleaner::Cleaner::new(app_profile);
-    cleaner_instant.find_app_process(None::<fn(std::borrow::Cow<'static, str>)>)?;
-    cleaner_instant.kill_app_process(None::<fn(usize, usize)>)?; // Safe: no processes exist
+    cleaner_instant.find_app_process_with(&source, None::<fn(std::borrow::Cow<'static, str>)>)?;
+
+    let result = cleaner_instant.kill_app_process_with(&control, &policy, None::<fn(usize, usize)>);
+    assert!(result.is_err(), "protected helper must be reported");
+
+    // Helpers are signaled before the main process.
+    assert_eq!(
+        control.signals(),
+        vec![
+            (11, ProcessSignal::Terminate),
+            (14, ProcessSignal::Terminate),
+            (11, ProcessSignal::Kill),
+            (10, ProcessSignal::Terminate),
+        ]
+    );
+
+    let outcomes: Vec<(i32, TerminationOutcome)> = cleaner_instant
+        .as_control_entry()
+        .processes()
+        .iter()
+        .map(|(p, outcome)| (p.pid(), outcome.clone()))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (20, TerminationOutcome::Unconfirmed),
+            (11, TerminationOutcome::Escalated),
+            (12, TerminationOutcome::PermissionDenied),
+            (13, TerminationOutcome::AlreadyExited),
+            (14, TerminationOutcome::Exited),
+            (10, TerminationOutcome::Exited),
+        ]
+    );
+
+    // Low confidence matches are only stopped once confirmed.
+    let pending: Vec<i32> = cleaner_instant
+        .as_app_profile()
+        .as_process_entry()
+        .requiring_confirmation()
+        .into_iter()
+        .map(|p| p.pid())
+        .collect();
+    assert_eq!(pending, vec![20]);
+
+    cleaner_instant.confirm_processes(pending);
+    let _ = cleaner_instant.kill_app_process_with(&control, &policy, None::<fn(usize, usize)>);
+    assert_eq!(
+        control.signals().last(),
+        Some(&(20, ProcessSignal::Terminate))
+    );
+
     Ok(())
 }
 
+#[test]
+fn test_terminate_processes_escalates() {
+    use cleaner::control::{
+        SystemProcessControl, TerminationOutcome, TerminationPolicy, terminate_processes,
+    };
+    use std::process::Command;
+    use std::time::Duration;
+
+    let mut graceful = Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("spawn sleep");
+    // Ignored signal dispositions survive `exec`.
+    let mut stubborn = Command::new("sh")
+        .args(["-c", "trap '' TERM; exec sleep 30"])
+        .spawn()
+        .expect("spawn stubborn sleep");
+
+    std::thread::sleep(Duration::from_millis(300));
+
+    let process = |child: &std::process::Child, name: &str| {
+        cleaner::ProcessData::new(child.id() as i32, name.to_string(), name.to_string())
+            .with_match_reason(cleaner::MatchReason::ExecutablePath)
+    };
+
+    let policy = TerminationPolicy::new()
+        .with_grace_period(Duration::from_millis(500))
+        .with_poll_interval(Duration::from_millis(20))
+        .with_escalation(false);
+
+    let results = terminate_processes(
+        &[
+            process(&graceful, "graceful"),
+            process(&stubborn, "stubborn"),
+        ],
+        &[],
+        &policy,
+        &SystemProcessControl::new(),
+        None::<fn(usize, usize)>,
+    );
+
+    assert_eq!(results[0].1, TerminationOutcome::Exited);
+    assert_eq!(results[1].1, TerminationOutcome::StillRunning);
+
+    let results = terminate_processes(
+        &[process(&stubborn, "stubborn")],
+        &[],
+        &policy.with_escalation(true),
+        &SystemProcessControl::new(),
+        None::<fn(usize, usize)>,
+    );
+
+    assert_eq!(results[0].1, TerminationOutcome::Escalated);
+
+    let _ = graceful.wait();
+    let _ = stubborn.wait();
+
+    // Reaped processes no longer exist.
+    let results = terminate_processes(
+        &[process(&graceful, "graceful")],
+        &[],
+        &policy,
+        &SystemProcessControl::new(),
+        None::<fn(usize, usize)>,
+    );
+
+    assert_eq!(results[0].1, TerminationOutcome::AlreadyExited);
+}
+
+#[test]
+fn test_find_processes_by_executable_path() {
+    use cleaner::MatchReason;
+    use std::process::Command;
+
+    let app_path = std::env::temp_dir().join("test_process_match/Fake Proc App.app");
+    let macos = app_path.join("Contents/MacOS");
+    fs::create_dir_all(&macos).expect("create bundle");
+
+    // A renamed shell stands in for an application executable
+    // that spawns a child outside the bundle.
+    let executable = macos.join("FakeProcMain");
+    fs::copy("/bin/sh", &executable).expect("copy executable");
+
+    let mut app = Command::new(&executable)
+        .args(["-c", "sleep 30; true"])
+        .spawn()
+        .expect("spawn app");
+
+    // Mentions the bundle identifier only in its arguments.
+    let mut bystander = Command::new("sh")
+        .args(["-c", "sleep 30; true", "com.test.fakeprocapp"])
+        .spawn()
+        .expect("spawn bystander");
+
+    std::thread::sleep(std::time::Duration::from_millis(300));
+
+    let metadata = cleaner::Metadata::new(
+        app_path.clone(),
+        "Fake Proc App".to_string(),
+        "com.test.fakeprocapp".to_string(),
+        "FakeProcMain".to_string(),
+        "test".to_string(),
+        "fakeprocapp".to_string(),
+    );
+
+    let entry = cleaner::ProcessEntry::find_app_processes(&metadata);
+    let reason_of = |pid: u32| {
+        entry
+            .list()
+            .iter()
+            .find(|p| p.pid() == pid as i32)
+            .map(|p| p.match_reason())
+    };
+
+    assert_eq!(reason_of(app.id()), Some(MatchReason::ExecutablePath));
+    assert_eq!(reason_of(bystander.id()), Some(MatchReason::NameOrCommand));
+    assert!(
+        entry
+            .list()
+            .iter()
+            .any(|p| p.match_reason() == MatchReason::ChildOf(app.id() as i32))
+    );
+    assert!(entry.list()[0].match_reason() == MatchReason::ExecutablePath);
+
+    // The child shows up below the application in the tree.
+    let tree = entry.tree();
+    let app_index = tree
+        .iter()
+        .position(|(_, p)| p.pid() == app.id() as i32)
+        .expect("app in tree");
+    let (depth, app_process) = tree[app_index];
+    let (child_depth, child) = tree[app_index + 1];
+    assert_eq!(child_depth, depth + 1);
+    assert_eq!(child.parent_pid(), Some(app.id() as i32));
+    assert_eq!(app_process.as_children(), [child.pid()]);
+    assert_eq!(app_process.as_exe(), Some(executable.as_path()));
+    assert!(app_process.start_time() > 0);
+    assert!(app_process.memory() > 0);
+
+    let _ = app.kill();
+    let _ = bystander.kill();
+    let _ = app.wait();
+    let _ = bystander.wait();
+    let _ = fs::remove_dir_all(app_path.parent().unwrap());
+}
+
 #[test]
 fn test_remove_child_when_parent_exists() {
     use std::path::PathBuf;
@@ -172,3 +758,2697 @@ fn test_remove_child_when_parent_exists() {
     assert_eq!(filtered.len(), 1);
     assert_eq!(filtered[0].0, PathBuf::from("folderA/folderB"));
 }
+
+#[test]
+fn test_memory_file_system_fixture() -> cleaner::Result<()> {
+    use cleaner::{FileSystem, MemoryFileSystem};
+    use std::path::Path;
+
+    let fixture = r#"
+/Users/tester/Library:
+  Preferences:
+    com.example.app.plist: "<plist/>"
+    empty.plist: ~
+  Caches:
+    com.example.app:
+      Cache.db: ~
+"#;
+
+    let fs = MemoryFileSystem::from_yaml(fixture)?;
+    let library = Path::new("/Users/tester/Library");
+
+    assert!(fs.is_dir(Path::new("/Users")));
+    assert!(fs.is_file(&library.join("Preferences/empty.plist")));
+    assert_eq!(
+        fs.metadata(&library.join("Preferences/com.example.app.plist"))
+            .map(|m| m.len()),
+        Some(8)
+    );
+    assert!(!fs.exists(&library.join("Logs")));
+
+    assert_eq!(
+        fs.read_dir(&library.join("Preferences")),
+        vec![
+            library.join("Preferences/com.example.app.plist"),
+            library.join("Preferences/empty.plist"),
+        ]
+    );
+
+    // Same depth semantics as WalkDir: the root is depth 0.
+    let walked: Vec<(PathBuf, usize)> = fs
+        .walk(library, 2)
+        .map(|entry| (entry.as_path().to_path_buf(), entry.depth()))
+        .collect();
+    assert_eq!(walked.first(), Some(&(library.to_path_buf(), 0)));
+    assert!(walked.contains(&(library.join("Caches/com.example.app"), 2)));
+    assert!(!walked.iter().any(|(_, depth)| *depth > 2));
+
+    assert!(fs.read(&library.join("Caches")).is_err());
+    assert!(MemoryFileSystem::from_yaml("relative/root: ~").is_err());
+    assert!(MemoryFileSystem::from_yaml("/root:\n  file: 3").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_path_entry_on_memory_fixture() -> cleaner::Result<()> {
+    use cleaner::path_data::PathData;
+    use cleaner::{MemoryFileSystem, Metadata, PathEntry};
+    use std::path::Path;
+
+    // Scan locations are derived from $HOME, so the fixture is
+    // placed under the real home path without touching the disk.
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let home = Path::new(&home);
+
+    // `Café` is the decomposed (NFD) spelling of the app
+    // name, as produced by some archive tools and older HFS+ copies.
+    let fixture = r#"
+/Applications/Café.app:
+  Contents:
+    Info.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>CFBundleIdentifier</key>
+          <string>com.example.cafe</string>
+          <key>CFBundleName</key>
+          <string>Café</string>
+          <key>CFBundleExecutable</key>
+          <string>Café</string>
+      </dict>
+      </plist>
+    MacOS:
+      Café: ~
+      Updater: ~
+$HOME/Library:
+  Application Support:
+    "Café":
+      cache:
+        data.bin: ~
+    Other App: {}
+  Caches:
+    COM.EXAMPLE.CAFE: {}
+  Preferences:
+    com.example.cafe.plist: ~
+    com.other.app.plist: ~
+  LaunchAgents:
+    com.vendor.updater.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>Label</key>
+          <string>com.vendor.updater</string>
+          <key>ProgramArguments</key>
+          <array>
+              <string>/Applications/Café.app/Contents/MacOS/Updater</string>
+          </array>
+      </dict>
+      </plist>
+    com.example.cafe.unrelated.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>Label</key>
+          <string>com.other.job</string>
+          <key>Program</key>
+          <string>/usr/bin/true</string>
+      </dict>
+      </plist>
+  Containers:
+    com.example.cafe:
+      Data: {}
+    7F1C2D3E:
+      Data:
+        Library:
+          Preferences:
+            com.example.cafe.plist: ~
+    com.other.app:
+      Data: {}
+  Group Containers:
+    TEAM1234.com.example.cafe: {}
+/private/var/db/receipts:
+  com.example.cafe.pkg.bom: ~
+  com.example.cafe.pkg.plist: ~
+"#
+    .replace("$HOME", &home.to_string_lossy());
+
+    let fs = MemoryFileSystem::from_yaml(&fixture)?;
+    let metadata = Metadata::from_path_with(&fs, Path::new("/Applications/Café.app"))?;
+
+    assert_eq!(metadata.as_bundle_id(), "com.example.cafe");
+    assert_eq!(metadata.as_name(), "Café");
+
+    let mut entry = PathEntry::from_metadata(&metadata);
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    let paths = |items: &[cleaner::path_data::PathData]| -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = items.iter().map(|p| p.as_path().to_path_buf()).collect();
+        paths.sort();
+        paths
+    };
+
+    let library = home.join("Library");
+
+    // The NFD folder matches the NFC name, the upper-case cache
+    // folder matches case-insensitively, and the folder's children
+    // are collapsed into the folder itself.
+    let general = paths(entry.as_general_associated_files());
+    assert!(general.contains(&library.join("Application Support/Cafe\u{301}")));
+    assert!(general.contains(&library.join("Caches/COM.EXAMPLE.CAFE")));
+    assert!(general.contains(&library.join("Preferences/com.example.cafe.plist")));
+    assert!(
+        !general
+            .iter()
+            .any(|p| p.starts_with(library.join("Application Support/Cafe\u{301}/cache")))
+    );
+    assert!(
+        !general
+            .iter()
+            .any(|p| p.to_string_lossy().contains("com.other"))
+    );
+    assert!(!general.contains(&library.join("Application Support/Other App")));
+
+    // Job definitions are attributed by content: the vendor-named
+    // updater runs from the bundle, while the job merely named
+    // after the bundle identifier runs something else.
+    assert_eq!(
+        paths(entry.as_background_task_files()),
+        vec![
+            library.join("LaunchAgents/com.vendor.updater.plist"),
+            library.join("Preferences/com.example.cafe.plist"),
+        ]
+    );
+    let reason = |found: &[PathData], path: PathBuf| {
+        found
+            .iter()
+            .find(|p| p.as_path() == path)
+            .and_then(|p| p.as_reason())
+            .map(str::to_string)
+    };
+    assert_eq!(
+        reason(
+            entry.as_background_task_files(),
+            library.join("LaunchAgents/com.vendor.updater.plist")
+        )
+        .as_deref(),
+        Some("launchd job runs code from the application bundle")
+    );
+
+    assert_eq!(
+        paths(entry.as_sandbox_container()),
+        vec![
+            library.join("Containers/7F1C2D3E"),
+            library.join("Containers/com.example.cafe"),
+            library.join("Group Containers/TEAM1234.com.example.cafe"),
+        ]
+    );
+    assert!(
+        reason(
+            entry.as_sandbox_container(),
+            library.join("Containers/7F1C2D3E")
+        )
+        .is_some_and(|reason| reason.starts_with("Holds \"com.example.cafe.plist\" whose name"))
+    );
+    assert_eq!(
+        reason(
+            entry.as_sandbox_container(),
+            library.join("Containers/com.example.cafe")
+        )
+        .as_deref(),
+        Some("Container name contains \"com.example.cafe\"")
+    );
+
+    assert_eq!(
+        paths(entry.as_bom_files()),
+        vec![PathBuf::from(
+            "/private/var/db/receipts/com.example.cafe.pkg.bom"
+        )]
+    );
+
+    // User rules extend and restrict the name rules. Invalid rules
+    // are rejected and the previous configuration is kept.
+    use cleaner::{RuleConfig, RuleKind};
+
+    entry.set_rule_config(
+        RuleConfig::new()
+            .with_include(RuleKind::Contain, "other app")
+            .with_exclude(RuleKind::Equal, "com.example.cafe.plist"),
+    )?;
+    assert!(
+        entry
+            .set_rule_config(RuleConfig::new().with_include(RuleKind::Regex, "("))
+            .is_err()
+    );
+    assert!(!entry.as_rule_config().is_empty());
+
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    let general = paths(entry.as_general_associated_files());
+    assert!(general.contains(&library.join("Application Support/Other App")));
+    assert!(!general.contains(&library.join("Preferences/com.example.cafe.plist")));
+    assert!(general.contains(&library.join("Caches/COM.EXAMPLE.CAFE")));
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_index_incremental_refresh() -> cleaner::Result<()> {
+    use cleaner::utility::{MatchRules, scan_general_with};
+    use cleaner::{FileSystem, IndexedFileSystem, MemoryFileSystem, ScanIndex};
+    use std::path::Path;
+
+    let fixture = r#"
+/Users/tester/Library:
+  Application Support:
+    "Cafe\u0301":
+      cache:
+        data.bin: ~
+    Other App:
+      settings.json: ~
+  Preferences:
+    com.example.cafe.plist: ~
+"#;
+
+    let mut fs = MemoryFileSystem::from_yaml(fixture)?;
+    let library = Path::new("/Users/tester/Library");
+
+    let mut index = ScanIndex::new()
+        .with_root(library, 3)
+        .with_root(library.join("Application Support"), 3);
+
+    // Library, Application Support, Preferences, Cafe, Other App,
+    // and cache (reached through the deeper second root).
+    let first = index.refresh(&fs);
+    assert_eq!(first.listed(), 6);
+
+    let second = index.refresh(&fs);
+    assert_eq!(second.listed(), 0);
+    assert_eq!(second.removed(), 0);
+
+    // Only the directory that changed is listed again.
+    fs.create_file(
+        library.join("Preferences/com.example.cafe.helper.plist"),
+        "",
+    );
+    fs.remove(library.join("Application Support/Other App"));
+    let third = index.refresh(&fs);
+    assert_eq!(third.listed(), 2);
+    assert_eq!(third.removed(), 2);
+
+    let nfd = library.join("Application Support/Cafe\u{301}");
+    assert!(index.get(&nfd).is_some_and(|entry| entry.is_dir()));
+
+    // File lengths stay current although the directory did not
+    // change.
+    let data = library.join("Application Support/Cafe\u{301}/cache/data.bin");
+    fs.create_file(&data, vec![0u8; 42]);
+    index.refresh(&fs);
+
+    let indexed = IndexedFileSystem::new(&index, &fs);
+    assert_eq!(indexed.metadata(&data).map(|m| m.len()), Some(42));
+    assert_eq!(indexed.total_size(library), fs.total_size(library));
+
+    // Scans answered from the index match scans of the filesystem.
+    assert!(indexed.walk(library, 3).eq(fs.walk(library, 3)));
+    assert_eq!(
+        indexed.read_dir(&library.join("Preferences")),
+        fs.read_dir(&library.join("Preferences"))
+    );
+
+    let scan = |scan_fs: &dyn FileSystem| {
+        scan_general_with(
+            scan_fs,
+            &[library.to_path_buf()],
+            3,
+            |_, _| {},
+            |path: &Path| {
+                MatchRules::new()
+                    .equal("Café")
+                    .contain("com.example.cafe")
+                    .check_path(path)
+            },
+            |path: PathBuf| path,
+        )
+    };
+    let mut direct = scan(&fs);
+    let mut from_index = scan(&indexed);
+    direct.sort();
+    from_index.sort();
+    assert_eq!(direct, from_index);
+    assert_eq!(direct.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_index_save_and_load() -> cleaner::Result<()> {
+    use cleaner::{FileSystem, IndexedFileSystem, RealFileSystem, ScanIndex};
+
+    let base = std::env::temp_dir().join("test_scan_index_save_and_load");
+    let _ = fs::remove_dir_all(&base);
+
+    let root = base.join("Library");
+    fs::create_dir_all(root.join("Caches/com.example.cafe")).expect("create tree");
+    fs::write(root.join("Caches/com.example.cafe/Cache.db"), "").expect("write file");
+    // A link back to the root is recorded, not descended into.
+    std::os::unix::fs::symlink(&root, root.join("Caches/Loop")).expect("create link");
+
+    let disk = RealFileSystem::new();
+    let mut index = ScanIndex::new().with_root(&root, 2);
+    // The cache folder sits at the depth limit and is not listed.
+    assert_eq!(index.refresh(&disk).listed(), 2);
+    assert!(
+        index
+            .get(&root.join("Caches/Loop"))
+            .is_some_and(|entry| entry.is_symlink() && !entry.is_dir())
+    );
+
+    let indexed = IndexedFileSystem::new(&index, &disk);
+    let mut direct: Vec<_> = disk.walk(&root, 2).collect();
+    let mut from_index: Vec<_> = indexed.walk(&root, 2).collect();
+    direct.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+    from_index.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+    assert_eq!(direct, from_index);
+
+    let index_path = base.join("index/scan-index.plist");
+    index.save(&index_path)?;
+
+    let mut loaded = ScanIndex::load(&index_path)?;
+    assert_eq!(loaded.len(), index.len());
+    assert_eq!(
+        loaded.roots().collect::<Vec<_>>(),
+        vec![(root.as_path(), 2)]
+    );
+
+    // Nothing changed on disk, so the loaded index is reused as is.
+    let stats = loaded.refresh(&disk);
+    assert_eq!(stats.listed(), 0);
+    assert_eq!(stats.reused(), 2);
+
+    fs::write(base.join("index/scan-index.plist"), "not a plist").expect("corrupt index");
+    assert!(ScanIndex::load(&index_path).is_err());
+
+    let _ = fs::remove_dir_all(&base);
+
+    Ok(())
+}
+
+#[test]
+fn test_compiled_rules_match_like_rules() {
+    use cleaner::utility::MatchRules;
+    use std::path::Path;
+
+    let rules = MatchRules::new()
+        .equal("Café")
+        .equal("  ")
+        .contain("com.example.cafe")
+        .contain("Helper");
+    let compiled = rules.compile();
+
+    let candidates = [
+        "Café",
+        "CAFE\u{301}",
+        "Cafe",
+        "Café Cache",
+        "COM.EXAMPLE.CAFE.plist",
+        "group.com.example.cafe",
+        "com.example.caf",
+        "App helper",
+        "",
+    ];
+
+    for name in candidates {
+        let path = Path::new("/Users/tester/Library").join(name);
+        assert_eq!(
+            compiled.check_path(&path),
+            rules.check_path(&path),
+            "{name}"
+        );
+        assert_eq!(
+            compiled.check_string(name),
+            rules.check_string(name),
+            "{name}"
+        );
+    }
+
+    assert!(compiled.check_path(Path::new("/Caches/CAFE\u{301}")));
+    assert!(!compiled.check_path(Path::new("/Caches/Cafe")));
+    assert!(MatchRules::new().equal("").compile().is_empty());
+}
+
+#[test]
+fn test_extended_and_exclude_rules() -> cleaner::Result<()> {
+    use cleaner::utility::MatchRules;
+    use std::path::Path;
+
+    let rules = MatchRules::new()
+        .contain("zoom")
+        .prefix("Café")
+        .suffix(".savedState")
+        .glob("com.vendor.*.plist")?
+        .regex(r"^Helper \d+$")?
+        .exclude(MatchRules::new().contain("zoomit").glob("*.tmp")?);
+    let compiled = rules.compile();
+
+    let cases = [
+        ("us.zoom.xos", true),
+        ("ZoomIt", false),
+        ("zoom.tmp", false),
+        ("CAFE\u{301} Cache", true),
+        ("My Café", false),
+        ("com.example.app.savedstate", true),
+        ("com.vendor.sync.plist", true),
+        ("com.vendor.plist", false),
+        ("com.vendor.sync.plist.bak", false),
+        ("helper 42", true),
+        ("Helper 42 Extra", false),
+        ("unrelated", false),
+    ];
+
+    for (name, expected) in cases {
+        let path = Path::new("/Users/tester/Library").join(name);
+        assert_eq!(rules.check_path(&path), expected, "{name}");
+        assert_eq!(rules.check_string(name), expected, "{name}");
+        assert_eq!(compiled.check_path(&path), expected, "{name}");
+        assert_eq!(compiled.check_string(name), expected, "{name}");
+    }
+
+    let classes = MatchRules::new().glob("log[0-9].txt")?.glob("v[!0-9]")?;
+    assert!(classes.check_string("LOG7.txt"));
+    assert!(!classes.check_string("logx.txt"));
+    assert!(classes.check_string("vx"));
+    assert!(!classes.check_string("v1"));
+
+    // `&&`, `--` and `~~` are regex class operators and stay
+    // literal inside glob classes.
+    let operators = MatchRules::new()
+        .glob("a[&&b]")?
+        .glob("c[~~]")?
+        .glob("d[--]")?;
+    assert!(operators.check_string("a&"));
+    assert!(operators.check_string("ab"));
+    assert!(operators.check_string("c~"));
+    assert!(operators.check_string("d-"));
+    assert!(!operators.check_string("ax"));
+
+    assert!(MatchRules::new().glob("com.[vendor").is_err());
+    assert!(MatchRules::new().regex("(unclosed").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_config_from_yaml() -> cleaner::Result<()> {
+    use cleaner::utility::{RuleConfig, RuleKind};
+
+    let config = RuleConfig::from_yaml(
+        r#"
+include:
+  - contain: zoom
+  - glob: "com.vendor.*.plist"
+exclude:
+  - contain: zoomit
+"#,
+    )?;
+
+    assert_eq!(
+        config,
+        RuleConfig::new()
+            .with_include(RuleKind::Contain, "zoom")
+            .with_include(RuleKind::Glob, "com.vendor.*.plist")
+            .with_exclude(RuleKind::Contain, "zoomit")
+    );
+
+    let rules = config.to_match_rules()?;
+    assert!(rules.check_string("us.zoom.xos"));
+    assert!(rules.check_string("com.vendor.sync.plist"));
+    assert!(!rules.check_string("ZoomIt"));
+
+    let merged = cleaner::utility::MatchRules::new()
+        .equal("Zoom Helper")
+        .with_config(&config)?;
+    assert!(merged.check_string("zoom helper"));
+    assert!(!merged.check_string("zoomit helper"));
+
+    assert!(RuleConfig::from_yaml("")?.is_empty());
+    assert!(RuleConfig::from_yaml("include:\n  - starts: zoom\n").is_err());
+    assert!(RuleConfig::from_yaml("include:\n  - contain: zoom\n    equal: x\n").is_err());
+    assert!(RuleConfig::from_yaml("rules: []\n").is_err());
+    assert!(
+        RuleConfig::from_yaml("include:\n  - regex: \"(\"\n")?
+            .to_match_rules()
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_token_rules_for_short_app_names() {
+    use cleaner::utility::MatchRules;
+    use std::path::Path;
+
+    let cases: [(&str, &[&str], &[&str]); 6] = [
+        (
+            "Arc",
+            &[
+                "Arc",
+                "company.thebrowser.Arc",
+                "ArcHelper",
+                "arc-updater.log",
+            ],
+            &[
+                "search",
+                "com.apple.archiveutility",
+                "Archive.zip",
+                "Monarch",
+            ],
+        ),
+        (
+            "Bear",
+            &["net.shinyfrog.bear", "Bear Notes", "bear_backup"],
+            &["unbearable.log", "Beard", "forbearance"],
+        ),
+        (
+            "Notion",
+            &["Notion", "notion.id", "Notion Helper", "NotionCalendar"],
+            &["notional-values.txt", "Notions.db", "emotional"],
+        ),
+        (
+            "Things",
+            &["com.culturedcode.ThingsMac", "Things3", "things-sync"],
+            &["somethings.db", "Nothings", "everything"],
+        ),
+        (
+            "Fig",
+            &["io.fig.cursor", "Fig", "fig_cli"],
+            &["config", "Figma", "figures.plist"],
+        ),
+        (
+            "Craft",
+            &["com.lukilabs.lukiapp-Craft", "CraftDocs"],
+            &["Minecraft", "aircraft.log", "Crafty"],
+        ),
+    ];
+
+    for (name, matches, rejects) in cases {
+        let rules = MatchRules::new().contain_or_token(name);
+        let compiled = rules.compile();
+
+        for candidate in matches {
+            let path = Path::new("/Users/tester/Library/Caches").join(candidate);
+            assert!(rules.check_path(&path), "{name} should match {candidate}");
+            assert!(
+                compiled.check_path(&path),
+                "{name} should match {candidate}"
+            );
+            assert!(
+                rules.check_string(candidate),
+                "{name} should match {candidate}"
+            );
+        }
+
+        for candidate in rejects {
+            let path = Path::new("/Users/tester/Library/Caches").join(candidate);
+            assert!(!rules.check_path(&path), "{name} should reject {candidate}");
+            assert!(
+                !compiled.check_path(&path),
+                "{name} should reject {candidate}"
+            );
+            assert!(
+                !rules.check_string(candidate),
+                "{name} should reject {candidate}"
+            );
+        }
+    }
+
+    // Long needles keep substring semantics.
+    let rules = MatchRules::new().contain_or_token("com.example.cafe");
+    assert!(rules.check_string("group.com.example.cafe.shared"));
+    assert!(rules.check_string("xcom.example.cafe"));
+
+    // Multi-token needles match a contiguous run.
+    let rules = MatchRules::new().token("Day One");
+    assert!(rules.check_string("com.bloombuilt.DayOne"));
+    assert!(!rules.check_string("One Day"));
+}
+
+#[test]
+fn test_scan_general_segments_proposes_owning_directory() -> cleaner::Result<()> {
+    use cleaner::MemoryFileSystem;
+    use cleaner::utility::{
+        MatchRules, construct_and_deduplicate_paths, scan_general_segments_with,
+        scan_general_split_with, scan_general_with,
+    };
+    use std::path::Path;
+
+    let fs = MemoryFileSystem::from_yaml(
+        r#"
+/Users/tester/Library:
+  Application Support:
+    Vendor Inc:
+      Cafe:
+        data.bin: ~
+      Cafe Helper: {}
+      .DS_Store: ~
+    Shared Vendor:
+      Cafe:
+        data.bin: ~
+      Other App: {}
+    Other App: {}
+  Caches:
+    Cafe:
+      Cafe: ~
+/Library:
+  Cafe Support:
+    Cafe: {}
+"#,
+    )?;
+
+    let library = Path::new("/Users/tester/Library");
+    let locations = vec![
+        library.to_path_buf(),
+        library.join("Application Support"),
+        library.join("Caches"),
+        PathBuf::from("/Library"),
+    ];
+
+    let rules = MatchRules::new()
+        .equal("Cafe")
+        .contain("Cafe Helper")
+        .compile();
+    let matcher = |path: &Path| rules.check_path(path);
+
+    let scan = |segments: bool| {
+        let results = if segments {
+            scan_general_segments_with(&fs, &locations, 3, |_, _| {}, matcher, |p| p)
+        } else {
+            scan_general_with(&fs, &locations, 3, |_, _| {}, matcher, |p| p)
+        };
+        let mut results = construct_and_deduplicate_paths(results, |p: &PathBuf| p.as_path());
+        results.sort();
+        results
+    };
+
+    // The vendor directory holds only matching entries and is
+    // proposed as a whole. The shared vendor directory is not,
+    // and a scan location such as `Caches` or a direct child of
+    // `/Library` is never climbed into.
+    assert_eq!(
+        scan(true),
+        vec![
+            PathBuf::from("/Library/Cafe Support/Cafe"),
+            library.join("Application Support/Shared Vendor/Cafe"),
+            library.join("Application Support/Vendor Inc"),
+            library.join("Caches/Cafe"),
+        ]
+    );
+
+    // Final-component matching keeps the individual leaves.
+    assert_eq!(
+        scan(false),
+        vec![
+            PathBuf::from("/Library/Cafe Support/Cafe"),
+            library.join("Application Support/Shared Vendor/Cafe"),
+            library.join("Application Support/Vendor Inc/Cafe"),
+            library.join("Application Support/Vendor Inc/Cafe Helper"),
+            library.join("Caches/Cafe"),
+        ]
+    );
+
+    // One walk finds the same matches, and offers only entries
+    // outside every match to the second finder.
+    let offered = std::sync::Mutex::new(Vec::new());
+    for segments in [true, false] {
+        offered.lock().expect("offered").clear();
+        let (matches, others) = scan_general_split_with(
+            &fs,
+            &locations,
+            3,
+            segments,
+            |_, _| {},
+            matcher,
+            |p| p,
+            |path| {
+                offered.lock().expect("offered").push(path.to_path_buf());
+                (path.file_name()? == "Other App").then(|| path.to_path_buf())
+            },
+        );
+        let mut matches = construct_and_deduplicate_paths(matches, |p: &PathBuf| p.as_path());
+        matches.sort();
+        assert_eq!(matches, scan(segments));
+
+        let mut others = construct_and_deduplicate_paths(others, |p: &PathBuf| p.as_path());
+        others.sort();
+        assert_eq!(
+            others,
+            vec![
+                library.join("Application Support/Other App"),
+                library.join("Application Support/Shared Vendor/Other App"),
+            ]
+        );
+        assert!(
+            !offered
+                .lock()
+                .expect("offered")
+                .iter()
+                .any(|path| path.ends_with("data.bin") || path.ends_with("Caches/Cafe/Cafe"))
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_origin_from_xattrs() -> cleaner::Result<()> {
+    use cleaner::utility::{OPEN_WITH_XATTR, QUARANTINE_XATTR, WHERE_FROMS_XATTR};
+    use cleaner::{FileOrigin, get_xattr, set_xattr};
+
+    let base = std::env::temp_dir().join(format!("cleaner-xattr-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).map_err(|e| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    })?;
+
+    let file = base.join("report.pdf");
+    File::create(&file).map_err(|e| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    })?;
+
+    if let Err(err) = set_xattr(
+        &file,
+        QUARANTINE_XATTR,
+        b"0083;65a1b2c3;Caf\xc3\xa9;8F2A1C3D",
+    ) {
+        // Some filesystems, such as older tmpfs, reject user
+        // attributes. Nothing else can be checked there.
+        eprintln!("skipping: {}", err);
+        let _ = fs::remove_dir_all(&base);
+        return Ok(());
+    }
+
+    let to_binary = |value: plist::Value| {
+        let mut bytes = Vec::new();
+        value.to_writer_binary(&mut bytes).unwrap();
+        bytes
+    };
+
+    let mut open_with = plist::Dictionary::new();
+    open_with.insert("bundleidentifier".into(), "com.example.cafe".into());
+    open_with.insert("path".into(), "/Applications/Café.app".into());
+    open_with.insert("version".into(), 0.into());
+    set_xattr(
+        &file,
+        OPEN_WITH_XATTR,
+        &to_binary(plist::Value::Dictionary(open_with)),
+    )?;
+    set_xattr(
+        &file,
+        WHERE_FROMS_XATTR,
+        &to_binary(plist::Value::Array(vec![
+            "https://example.com/report.pdf".into(),
+            "https://example.com/".into(),
+        ])),
+    )?;
+
+    let origin = FileOrigin::from_path(&file);
+    assert_eq!(origin.as_quarantine_agent(), Some("Café"));
+    assert_eq!(origin.as_origin_bundle_id(), Some("com.example.cafe"));
+    assert_eq!(
+        origin.as_where_froms(),
+        [
+            "https://example.com/report.pdf".to_string(),
+            "https://example.com/".to_string()
+        ]
+    );
+
+    // Attributes are stored as `user.<name>` on Linux but read
+    // back under their macOS names.
+    assert!(get_xattr(&file, "com.example.missing")?.is_none());
+    assert!(get_xattr(&base.join("missing"), QUARANTINE_XATTR).is_err());
+    assert!(FileOrigin::from_path(&base).is_empty());
+
+    assert_eq!(FileOrigin::parse_quarantine_agent(b"0081;65a1b2c3;;"), None);
+    assert!(FileOrigin::parse_open_with(b"not a plist").is_none());
+
+    let _ = fs::remove_dir_all(&base);
+
+    Ok(())
+}
+
+#[test]
+fn test_general_scan_matches_origin_attributes() -> cleaner::Result<()> {
+    use cleaner::utility::{FsMetadata, FsWalk, OPEN_WITH_XATTR, QUARANTINE_XATTR};
+    use cleaner::{FileSystem, MemoryFileSystem, Metadata, PathEntry};
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    // In-memory tree with extended attributes served from a map.
+    struct XattrFileSystem {
+        inner: MemoryFileSystem,
+        xattrs: HashMap<(PathBuf, &'static str), Vec<u8>>,
+    }
+
+    impl FileSystem for XattrFileSystem {
+        fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+            self.inner.read_dir(path)
+        }
+
+        fn metadata(&self, path: &Path) -> Option<FsMetadata> {
+            self.inner.metadata(path)
+        }
+
+        fn walk(&self, root: &Path, max_depth: usize) -> FsWalk<'_> {
+            self.inner.walk(root, max_depth)
+        }
+
+        fn read(&self, path: &Path) -> cleaner::Result<Vec<u8>> {
+            self.inner.read(path)
+        }
+
+        fn xattr(&self, path: &Path, name: &str) -> Option<Vec<u8>> {
+            self.xattrs
+                .iter()
+                .find(|((p, n), _)| p == path && *n == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let library = Path::new(&home).join("Library");
+
+    let fixture = r#"
+/Applications/Cafe.app:
+  Contents:
+    Info.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>CFBundleIdentifier</key>
+          <string>com.example.cafe</string>
+          <key>CFBundleName</key>
+          <string>Cafe</string>
+          <key>CFBundleExecutable</key>
+          <string>Cafe</string>
+      </dict>
+      </plist>
+$HOME/Library:
+  Caches:
+    thumbnails.db: ~
+    downloads.db: ~
+  Saved Application State:
+    A1B2C3.savedState: {}
+  Mobile Documents:
+    com~apple~CloudDocs:
+      invoice.pdf: ~
+"#
+    .replace("$HOME", &home);
+
+    let open_with = {
+        let mut dict = plist::Dictionary::new();
+        dict.insert("bundleidentifier".into(), "com.example.cafe".into());
+        let mut bytes = Vec::new();
+        plist::Value::Dictionary(dict)
+            .to_writer_binary(&mut bytes)
+            .unwrap();
+        bytes
+    };
+
+    let fs = XattrFileSystem {
+        inner: MemoryFileSystem::from_yaml(&fixture)?,
+        xattrs: HashMap::from([
+            (
+                (library.join("Caches/thumbnails.db"), QUARANTINE_XATTR),
+                b"0083;65a1b2c3;Cafe;".to_vec(),
+            ),
+            (
+                (library.join("Caches/downloads.db"), QUARANTINE_XATTR),
+                b"0083;65a1b2c3;Safari;".to_vec(),
+            ),
+            (
+                (
+                    library.join("Mobile Documents/com~apple~CloudDocs/invoice.pdf"),
+                    QUARANTINE_XATTR,
+                ),
+                b"0083;65a1b2c3;Cafe;".to_vec(),
+            ),
+            (
+                (
+                    library.join("Saved Application State/A1B2C3.savedState"),
+                    OPEN_WITH_XATTR,
+                ),
+                open_with,
+            ),
+        ]),
+    };
+
+    let metadata = Metadata::from_path_with(&fs, Path::new("/Applications/Cafe.app"))?;
+    let mut entry = PathEntry::from_metadata(&metadata);
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    // Origin matches are reported apart and never proposed for
+    // removal. Documents in iCloud Drive are never attributed.
+    assert!(entry.as_general_associated_files().is_empty());
+
+    let mut origin: Vec<PathBuf> = entry
+        .as_origin_files()
+        .iter()
+        .map(|p| p.as_path().to_path_buf())
+        .collect();
+    origin.sort();
+
+    assert_eq!(
+        origin,
+        vec![
+            library.join("Caches/thumbnails.db"),
+            library.join("Saved Application State/A1B2C3.savedState"),
+        ]
+    );
+    assert!(entry.as_origin_files().iter().any(|p| {
+        p.as_path() == library.join("Caches/thumbnails.db")
+            && p.as_reason() == Some("Downloaded by \"Cafe\"")
+    }));
+    assert!(
+        !entry
+            .all_paths()
+            .iter()
+            .any(|p| origin.contains(&p.as_path().to_path_buf()))
+    );
+
+    Ok(())
+}
+
+fn macho_fixture(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/macho")
+        .join(name);
+    fs::read(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+#[test]
+fn test_entitlements_from_mach_o_fixtures() -> cleaner::Result<()> {
+    use cleaner::macho::{
+        CPU_TYPE_ARM64, CPU_TYPE_X86_64, Entitlements, MachFile, SliceHeader, SuperBlob,
+    };
+
+    let thin = macho_fixture("cafe_x86_64");
+    let file = MachFile::parse(&thin)?;
+    assert!(!file.is_fat());
+    assert_eq!(file.slices().len(), 1);
+    assert_eq!(file.slices()[0].cpu_type(), CPU_TYPE_X86_64);
+
+    let entitlements = Entitlements::from_mach_o(&thin)?.expect("signed fixture");
+    assert_eq!(
+        entitlements.as_application_groups(),
+        ["ABCDE12345.com.vendor.sync", "group.com.example.shared"]
+    );
+    assert_eq!(entitlements.as_team_identifier(), Some("ABCDE12345"));
+    assert_eq!(
+        entitlements.as_application_identifier(),
+        Some("ABCDE12345.com.example.cafe")
+    );
+
+    let universal = macho_fixture("cafe_universal");
+    let file = MachFile::parse(&universal)?;
+    assert!(file.is_fat());
+    assert_eq!(
+        file.slices()
+            .iter()
+            .map(|slice| slice.cpu_type())
+            .collect::<Vec<_>>(),
+        vec![CPU_TYPE_X86_64, CPU_TYPE_ARM64]
+    );
+    assert_eq!(Entitlements::from_mach_o(&universal)?, Some(entitlements));
+
+    let helper = Entitlements::from_mach_o(&macho_fixture("helper_arm64"))?.expect("signed");
+    assert_eq!(
+        helper.as_application_groups(),
+        ["ABCDE12345.com.vendor.helper-ipc"]
+    );
+
+    assert_eq!(
+        Entitlements::from_mach_o(&macho_fixture("unsigned_arm64"))?,
+        None
+    );
+
+    // Reading only the slice headers and signatures gives the
+    // same entitlements as parsing the whole file.
+    let mut fs = cleaner::MemoryFileSystem::new();
+    let executable = PathBuf::from("/Applications/Cafe.app/Contents/MacOS/Cafe");
+    fs.create_file(&executable, universal.clone());
+    assert_eq!(
+        Entitlements::from_executable_with(&fs, &executable)?,
+        Entitlements::from_mach_o(&universal)?
+    );
+    let headers = SliceHeader::read_with(&fs, &executable)?;
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[1].parse()?.cpu_type(), CPU_TYPE_ARM64);
+
+    // An entitlements blob shorter than its header is ignored.
+    let truncated: Vec<u8> = [0xfade_0cc0u32, 28, 1, 5, 20, 0xfade_7171, 4]
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .collect();
+    assert!(SuperBlob::parse(&truncated)?.entitlements_plist().is_none());
+
+    // Not Mach-O, and a Java class file sharing the fat magic.
+    assert!(!MachFile::is_mach_o(b"#!/bin/sh\n"));
+    assert!(Entitlements::from_mach_o(b"#!/bin/sh\nexit 0\n").is_err());
+    assert!(MachFile::parse(&[0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 0x34]).is_err());
+    assert!(MachFile::parse(&thin[..64]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_group_containers_from_entitlements() -> cleaner::Result<()> {
+    use cleaner::{MemoryFileSystem, Metadata, PathEntry};
+    use std::path::Path;
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let home = Path::new(&home);
+
+    let fixture = r#"
+/Applications/Cafe.app:
+  Contents:
+    Info.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>CFBundleIdentifier</key>
+          <string>com.example.cafe</string>
+          <key>CFBundleName</key>
+          <string>Cafe</string>
+          <key>CFBundleExecutable</key>
+          <string>Cafe</string>
+      </dict>
+      </plist>
+    MacOS: {}
+    Library:
+      LaunchServices: {}
+$HOME/Library:
+  Group Containers:
+    ABCDE12345.com.vendor.sync: {}
+    ABCDE12345.com.vendor.helper-ipc: {}
+    ABCDE12345.com.vendor.legacy: {}
+    ABCDE12345.com.example.cafe.shared: {}
+    FGHIJ67890.com.vendor.other: {}
+    group.com.example.shared: {}
+  LaunchAgents:
+    ABCDE12345.com.vendor.agent.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>Label</key>
+          <string>ABCDE12345.com.vendor.agent</string>
+          <key>Program</key>
+          <string>/Library/Application Support/Vendor/agent</string>
+      </dict>
+      </plist>
+    FGHIJ67890.com.vendor.agent.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>Label</key>
+          <string>FGHIJ67890.com.vendor.agent</string>
+          <key>Program</key>
+          <string>/Library/Application Support/Vendor/agent</string>
+      </dict>
+      </plist>
+"#
+    .replace("$HOME", &home.to_string_lossy());
+
+    let mut fs = MemoryFileSystem::from_yaml(&fixture)?;
+    let app = Path::new("/Applications/Cafe.app");
+    fs.create_file(
+        app.join("Contents/MacOS/Cafe"),
+        macho_fixture("cafe_universal"),
+    );
+    fs.create_file(
+        app.join("Contents/Library/LaunchServices/com.example.cafe.helper"),
+        macho_fixture("helper_arm64"),
+    );
+
+    let metadata = Metadata::from_path_with(&fs, app)?;
+    let mut entry = PathEntry::from_metadata(&metadata);
+    let groups = home.join("Library/Group Containers");
+
+    // Another installed application entitled to the helper group
+    // keeps its group container.
+    let mut shared_fs = fs.clone();
+    let other = Path::new("/Applications/Vendor Sync.app/Contents");
+    shared_fs.create_file(
+        other.join("Info.plist"),
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.vendor.sync</string>
+    <key>CFBundleExecutable</key>
+    <string>Sync</string>
+</dict>
+</plist>"#
+            .to_vec(),
+    );
+    shared_fs.create_file(other.join("MacOS/Sync"), macho_fixture("helper_arm64"));
+
+    entry.scan_path_entry_with(&shared_fs, &metadata, |_, _| {});
+    assert!(
+        !entry
+            .as_sandbox_container()
+            .iter()
+            .any(|p| p.as_path() == groups.join("ABCDE12345.com.vendor.helper-ipc"))
+    );
+    assert!(
+        entry
+            .as_sandbox_container()
+            .iter()
+            .any(|p| p.as_path() == groups.join("ABCDE12345.com.vendor.sync"))
+    );
+
+    assert_eq!(metadata.as_team_identifier(), "ABCDE12345");
+    assert_eq!(metadata.team_prefix(), "ABCDE12345.");
+    assert!(
+        metadata
+            .as_signing_info()
+            .is_some_and(|info| info.is_hardened_runtime())
+    );
+    assert_eq!(
+        metadata.as_application_groups(),
+        [
+            "ABCDE12345.com.vendor.sync",
+            "group.com.example.shared",
+            "ABCDE12345.com.vendor.helper-ipc",
+        ]
+    );
+
+    // Executables are never read whole, only their headers and
+    // code signatures.
+    struct HeaderOnlyFileSystem(MemoryFileSystem);
+
+    impl cleaner::FileSystem for HeaderOnlyFileSystem {
+        fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+            self.0.read_dir(path)
+        }
+
+        fn metadata(&self, path: &Path) -> Option<cleaner::utility::FsMetadata> {
+            self.0.metadata(path)
+        }
+
+        fn walk(&self, root: &Path, max_depth: usize) -> cleaner::utility::FsWalk<'_> {
+            self.0.walk(root, max_depth)
+        }
+
+        fn read(&self, path: &Path) -> cleaner::Result<Vec<u8>> {
+            if path.extension().is_none() {
+                panic!("whole executable read: {}", path.display());
+            }
+            self.0.read(path)
+        }
+
+        fn read_range(&self, path: &Path, offset: u64, len: usize) -> cleaner::Result<Vec<u8>> {
+            self.0.read_range(path, offset, len)
+        }
+    }
+
+    let header_only = Metadata::from_path_with(&HeaderOnlyFileSystem(fs.clone()), app)?;
+    assert_eq!(header_only.as_signing_info(), metadata.as_signing_info());
+    assert_eq!(
+        header_only.as_application_groups(),
+        metadata.as_application_groups()
+    );
+
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    let mut containers: Vec<PathBuf> = entry
+        .as_sandbox_container()
+        .iter()
+        .map(|p| p.as_path().to_path_buf())
+        .collect();
+    containers.sort();
+
+    // Entitled groups match exactly and team prefixed groups
+    // match only with the bundle identifier. Other applications
+    // of the same team and other teams never match.
+    assert_eq!(metadata.team_bundle_id(), "ABCDE12345.com.example.cafe");
+    assert_eq!(
+        containers,
+        vec![
+            groups.join("ABCDE12345.com.example.cafe.shared"),
+            groups.join("ABCDE12345.com.vendor.helper-ipc"),
+            groups.join("ABCDE12345.com.vendor.sync"),
+            groups.join("group.com.example.shared"),
+        ]
+    );
+
+    // A vendor agent labelled only with the team is not claimed.
+    assert!(entry.as_background_task_files().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_sandbox_containers_from_container_metadata() -> cleaner::Result<()> {
+    use cleaner::utility::{CONTAINER_METADATA_FILE, container_identifier_with};
+    use cleaner::{MemoryFileSystem, Metadata, PathEntry};
+    use std::path::Path;
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let home = Path::new(&home);
+
+    let container_metadata = |identifier: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>MCMMetadataIdentifier</key>
+    <string>{}</string>
+    <key>MCMMetadataContentClass</key>
+    <integer>2</integer>
+</dict>
+</plist>"#,
+            identifier
+        )
+    };
+
+    let fixture = r#"
+/Applications/Cafe.app:
+  Contents:
+    Info.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>CFBundleIdentifier</key>
+          <string>com.example.cafe</string>
+          <key>CFBundleName</key>
+          <string>Cafe</string>
+          <key>CFBundleExecutable</key>
+          <string>Cafe</string>
+      </dict>
+      </plist>
+$HOME/Library:
+  Containers:
+    0B7A63C2-5C1E-4F0B-9A51-3D2B2C1E8F10: {}
+    9C4D2E1F-7A6B-4C3D-9E8F-0A1B2C3D4E5F: {}
+    5E0F8A1D-2B3C-4D5E-8F90-A1B2C3D4E5F6:
+      Data:
+        Library:
+          Preferences:
+            com.example.cafe.plist: ~
+    Cafe Importer: {}
+  Group Containers:
+    TEAM1234.shared:
+      Library:
+        Preferences:
+          com.example.cafe.plist: ~
+    TEAM1234.unrelated:
+      Library:
+        Preferences:
+          com.other.app.plist: ~
+"#
+    .replace("$HOME", &home.to_string_lossy());
+
+    let mut fs = MemoryFileSystem::from_yaml(&fixture)?;
+    let containers = home.join("Library/Containers");
+
+    // The UUID container belongs to the application, while the
+    // one holding a copy of its preferences and the one named
+    // after it both declare another owner.
+    let owned = containers.join("0B7A63C2-5C1E-4F0B-9A51-3D2B2C1E8F10");
+    fs.create_file(
+        owned.join(CONTAINER_METADATA_FILE),
+        container_metadata("com.example.cafe"),
+    );
+    fs.create_file(
+        containers
+            .join("5E0F8A1D-2B3C-4D5E-8F90-A1B2C3D4E5F6")
+            .join(CONTAINER_METADATA_FILE),
+        container_metadata("com.other.sync"),
+    );
+    fs.create_file(
+        containers
+            .join("Cafe Importer")
+            .join(CONTAINER_METADATA_FILE),
+        container_metadata("com.other.importer"),
+    );
+    // An identifier merely extending the bundle identifier names
+    // another application.
+    fs.create_file(
+        containers
+            .join("9C4D2E1F-7A6B-4C3D-9E8F-0A1B2C3D4E5F")
+            .join(CONTAINER_METADATA_FILE),
+        container_metadata("com.example.cafe2"),
+    );
+
+    assert_eq!(
+        container_identifier_with(&fs, &owned).as_deref(),
+        Some("com.example.cafe")
+    );
+    assert_eq!(
+        container_identifier_with(&fs, &containers.join("missing")),
+        None
+    );
+
+    let metadata = Metadata::from_path_with(&fs, Path::new("/Applications/Cafe.app"))?;
+    let mut entry = PathEntry::from_metadata(&metadata);
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    let mut found: Vec<PathBuf> = entry
+        .as_sandbox_container()
+        .iter()
+        .map(|p| p.as_path().to_path_buf())
+        .collect();
+    found.sort();
+
+    // Group containers without metadata fall back to their own
+    // `Library/Preferences` pattern.
+    assert!(entry.as_sandbox_container().iter().any(|p| {
+        p.as_path() == owned
+            && p.as_reason()
+                .is_some_and(|reason| reason.starts_with("Container metadata identifier equals"))
+    }));
+    assert_eq!(
+        found,
+        vec![owned, home.join("Library/Group Containers/TEAM1234.shared")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_signing_info_from_mach_o_fixtures() -> cleaner::Result<()> {
+    use cleaner::macho::{CS_RUNTIME, SignatureKind, SigningInfo};
+
+    let signed = SigningInfo::from_mach_o(&macho_fixture("cafe_x86_64"))?;
+    assert_eq!(signed.kind(), SignatureKind::Signed);
+    assert_eq!(signed.as_identifier(), Some("com.example.cafe"));
+    assert_eq!(signed.as_team_identifier(), Some("ABCDE12345"));
+    assert_eq!(signed.flags() & CS_RUNTIME, CS_RUNTIME);
+    assert!(signed.is_hardened_runtime());
+
+    // Certificates are stored root first and reordered leaf
+    // first by following their issuers.
+    let authorities: Vec<String> = signed
+        .as_authorities()
+        .iter()
+        .map(|subject| subject.to_string())
+        .collect();
+    assert_eq!(
+        authorities,
+        vec![
+            "Developer ID Application: Example Corp (ABCDE12345)",
+            "Developer ID Certification Authority",
+            "Apple Root CA",
+        ]
+    );
+
+    // The intermediate stores its organization as a BMPString.
+    assert_eq!(
+        signed.as_authorities()[1].as_organization(),
+        Some("Apple Inc.")
+    );
+    assert_eq!(
+        signed.to_string(),
+        "Developer ID Application: Example Corp (ABCDE12345) · Team ABCDE12345 · Hardened runtime"
+    );
+
+    assert_eq!(
+        SigningInfo::from_mach_o(&macho_fixture("cafe_universal"))?,
+        signed
+    );
+
+    // Versions before 0x20200 carry no team in the
+    // CodeDirectory, so the certificate's unit is used.
+    let legacy = SigningInfo::from_mach_o(&macho_fixture("legacy_x86_64"))?;
+    assert_eq!(legacy.kind(), SignatureKind::Signed);
+    assert_eq!(legacy.as_team_identifier(), Some("ABCDE12345"));
+    assert!(!legacy.is_hardened_runtime());
+
+    let adhoc = SigningInfo::from_mach_o(&macho_fixture("adhoc_arm64"))?;
+    assert!(adhoc.is_signed() && adhoc.is_adhoc());
+    assert_eq!(adhoc.as_team_identifier(), None);
+    assert!(adhoc.as_authorities().is_empty());
+    assert_eq!(adhoc.to_string(), "Ad-hoc signed · cafe-tool");
+
+    let unsigned = SigningInfo::from_mach_o(&macho_fixture("unsigned_arm64"))?;
+    assert_eq!(unsigned.kind(), SignatureKind::Unsigned);
+    assert_eq!(unsigned.to_string(), "Unsigned");
+
+    // A truncated certificate list is rejected.
+    let mut broken = macho_fixture("cafe_x86_64");
+    let cms = broken
+        .windows(4)
+        .position(|window| window == [0xfa, 0xde, 0x0b, 0x01])
+        .expect("CMS blob");
+    broken[cms + 8..]
+        .iter_mut()
+        .take(64)
+        .for_each(|byte| *byte = 0xff);
+    assert!(SigningInfo::from_mach_o(&broken).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_linked_libraries_from_mach_o_fixtures() -> cleaner::Result<()> {
+    use cleaner::macho::{LinkedLibraries, is_package_manager_path, is_system_path};
+    use std::path::Path;
+
+    // Both slices link the same libraries, which are listed once.
+    let libraries = LinkedLibraries::from_mach_o(&macho_fixture("linked_universal"))?;
+    assert_eq!(libraries.as_install_names().len(), 6);
+    assert_eq!(
+        libraries.as_rpaths(),
+        ["@executable_path/../Frameworks", "/Library/Frameworks"]
+    );
+
+    let bundle = Path::new("/Applications/Cafe.app");
+    let executable = bundle.join("Contents/MacOS/Cafe");
+
+    // `@rpath` takes the first existing candidate in run-path
+    // order, so a bundled Shared.framework shadows the installed
+    // one. Bundle-relative, system and package manager libraries
+    // are dropped.
+    let bundled = libraries.external_paths(&executable, bundle, |_| true);
+    assert_eq!(
+        bundled,
+        vec![PathBuf::from("/Library/Frameworks/Vendor.framework")]
+    );
+
+    // Without a bundled copy the installed framework is loaded.
+    let installed = libraries.external_paths(&executable, bundle, |path| {
+        path.starts_with("/Library/Frameworks")
+    });
+    assert_eq!(
+        installed,
+        vec![
+            PathBuf::from("/Library/Frameworks/Vendor.framework"),
+            PathBuf::from("/Library/Frameworks/Shared.framework"),
+        ]
+    );
+
+    // `@rpath` names found nowhere are dropped.
+    let missing = libraries.external_paths(&executable, bundle, |_| false);
+    assert_eq!(missing, bundled);
+
+    assert!(is_system_path(Path::new("/usr/lib/libSystem.B.dylib")));
+    assert!(!is_system_path(Path::new("/usr/local/lib/libvendor.dylib")));
+    assert!(is_package_manager_path(Path::new(
+        "/usr/local/lib/libvendor.dylib"
+    )));
+    assert!(is_package_manager_path(Path::new(
+        "/opt/homebrew/lib/libssl.3.dylib"
+    )));
+    assert!(!is_package_manager_path(Path::new(
+        "/Library/Frameworks/Vendor.framework"
+    )));
+
+    // Reading only the load commands gives the same libraries.
+    let mut fs = cleaner::MemoryFileSystem::new();
+    fs.create_file(&executable, macho_fixture("linked_universal"));
+    assert_eq!(
+        LinkedLibraries::from_executable_with(&fs, &executable)?,
+        libraries
+    );
+
+    let unlinked = LinkedLibraries::from_mach_o(&macho_fixture("unsigned_arm64"))?;
+    assert!(unlinked.as_install_names().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_external_libraries_shared_with_other_apps() -> cleaner::Result<()> {
+    use cleaner::{MemoryFileSystem, Metadata, PathEntry};
+    use std::path::Path;
+
+    let fixture = r#"
+/Applications:
+  Cafe.app:
+    Contents:
+      Info.plist: |
+        <?xml version="1.0" encoding="UTF-8"?>
+        <plist version="1.0">
+        <dict>
+            <key>CFBundleIdentifier</key>
+            <string>com.vendor.cafe</string>
+            <key>CFBundleName</key>
+            <string>Cafe</string>
+            <key>CFBundleExecutable</key>
+            <string>Cafe</string>
+        </dict>
+        </plist>
+      MacOS: {}
+      Frameworks:
+        Internal.framework:
+          Versions:
+            A:
+              Internal: binary
+  Other.app:
+    Contents:
+      Info.plist: |
+        <?xml version="1.0" encoding="UTF-8"?>
+        <plist version="1.0">
+        <dict>
+            <key>CFBundleIdentifier</key>
+            <string>org.example.other</string>
+            <key>CFBundleName</key>
+            <string>Other</string>
+            <key>CFBundleExecutable</key>
+            <string>Other</string>
+        </dict>
+        </plist>
+      MacOS: {}
+/Library/Frameworks:
+  Vendor.framework:
+    Versions:
+      A:
+        Vendor: binary
+  Shared.framework:
+    Versions:
+      A:
+        Shared: binary
+/usr/local/lib:
+  libvendor.dylib: binary
+"#;
+
+    let mut fs = MemoryFileSystem::from_yaml(fixture)?;
+    let app = Path::new("/Applications/Cafe.app");
+    fs.create_file(
+        app.join("Contents/MacOS/Cafe"),
+        macho_fixture("linked_universal"),
+    );
+    fs.create_file(
+        "/Applications/Other.app/Contents/MacOS/Other",
+        macho_fixture("shared_user_arm64"),
+    );
+
+    let metadata = Metadata::from_path_with(&fs, app)?;
+
+    // The bundle ships no Shared.framework, so `@rpath` resolves
+    // to the installed copy. The Homebrew prefix is never listed.
+    assert_eq!(
+        metadata.as_external_libraries(),
+        [
+            PathBuf::from("/Library/Frameworks/Vendor.framework"),
+            PathBuf::from("/Library/Frameworks/Shared.framework"),
+        ]
+    );
+
+    let mut entry = PathEntry::from_metadata(&metadata);
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+
+    let owned: Vec<PathBuf> = entry
+        .as_external_libraries()
+        .iter()
+        .map(|p| p.as_path().to_path_buf())
+        .collect();
+    assert_eq!(
+        owned,
+        vec![PathBuf::from("/Library/Frameworks/Vendor.framework")]
+    );
+
+    // Shared.framework is not named after the vendor, and
+    // Other.app loads it too, so it is reported but never offered
+    // for removal.
+    let shared: Vec<&str> = entry
+        .as_shared_libraries()
+        .iter()
+        .map(|p| p.as_name())
+        .collect();
+    assert_eq!(shared, vec!["Shared.framework"]);
+
+    let all_paths: Vec<PathBuf> = entry
+        .all_paths()
+        .iter()
+        .map(|p| p.as_path().to_path_buf())
+        .collect();
+    assert!(!all_paths.iter().any(|path| path.starts_with("/usr/local")));
+    assert!(!all_paths.iter().any(|path| path.starts_with("/usr/lib")));
+
+    // Another application of the vendor loading Vendor.framework
+    // keeps it.
+    fs.create_file(
+        "/Applications/Other.app/Contents/MacOS/Other",
+        macho_fixture("linked_universal"),
+    );
+    entry.scan_path_entry_with(&fs, &metadata, |_, _| {});
+    assert!(entry.as_external_libraries().is_empty());
+
+    let mut shared: Vec<&str> = entry
+        .as_shared_libraries()
+        .iter()
+        .map(|p| p.as_name())
+        .collect();
+    shared.sort();
+    assert_eq!(shared, vec!["Shared.framework", "Vendor.framework"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_thin_universal_binaries() -> cleaner::Result<()> {
+    use cleaner::macho::{CPU_TYPE_ARM64, MachFile};
+    use cleaner::thin::{
+        BinaryStore, RecordingBinaryStore, ThinReport, TrashBinaryStore, thin_bundle_with,
+        thin_mach_o,
+    };
+    use cleaner::{MemoryFileSystem, RealFileSystem};
+    use std::path::Path;
+
+    let universal = macho_fixture("linked_universal");
+    let arm64_size = MachFile::parse(&universal)?.slices()[1].as_bytes().len() as u64;
+
+    let app = Path::new("/Applications/Cafe.app");
+    let fs = MemoryFileSystem::new()
+        .with_file(app.join("Contents/MacOS/Cafe"), universal.clone())
+        .with_file(
+            app.join("Contents/Library/LaunchServices/com.example.cafe.helper"),
+            macho_fixture("unsigned_arm64"),
+        )
+        .with_file(app.join("Contents/Resources/Cafe.icns"), universal.clone());
+
+    // Thin binaries and resources are not reported.
+    let report = ThinReport::scan_with(&fs, app)?;
+    assert_eq!(report.as_binaries().len(), 1);
+    assert!(!report.is_signed());
+    assert_eq!(report.architectures(), vec!["x86_64", "arm64"]);
+    assert_eq!(
+        report.reclaimable_bytes("arm64"),
+        universal.len() as u64 - arm64_size
+    );
+    assert_eq!(report.reclaimable_bytes("ppc"), 0);
+    assert_eq!(report.reclaimable_by_architecture().len(), 2);
+
+    let store = RecordingBinaryStore::new();
+    let trash = thin_bundle_with(&fs, &store, &report, "arm64", false)?;
+    assert_eq!(trash.moved_path().len(), 1);
+    assert!(trash.failed_path().is_empty());
+
+    let replacements = store.replacements();
+    assert_eq!(replacements.len(), 1);
+    assert_eq!(replacements[0].0, app.join("Contents/MacOS/Cafe"));
+
+    let thinned = MachFile::parse(&replacements[0].1)?;
+    assert!(!thinned.is_fat());
+    assert_eq!(thinned.slices()[0].cpu_type(), CPU_TYPE_ARM64);
+    assert_eq!(replacements[0].1.len() as u64, arm64_size);
+
+    // A missing architecture skips the binary instead of
+    // leaving it without runnable code.
+    let trash = thin_bundle_with(&fs, &store, &report, "ppc", false)?;
+    assert!(trash.moved_path().is_empty());
+    assert!(trash.failed_path()[0].1.is_skipped());
+    assert!(thin_mach_o(&macho_fixture("unsigned_arm64"), "arm64").is_err());
+
+    // Certificate-signed bundles are refused unless forced.
+    let signed = MemoryFileSystem::new().with_file(
+        app.join("Contents/MacOS/Cafe"),
+        macho_fixture("cafe_universal"),
+    );
+    let report = ThinReport::scan_with(&signed, app)?;
+    assert!(report.is_signed());
+
+    let store = RecordingBinaryStore::new();
+    assert!(thin_bundle_with(&signed, &store, &report, "x86_64", false).is_err());
+    assert!(store.replacements().is_empty());
+
+    let trash = thin_bundle_with(&signed, &store, &report, "x86_64", true)?;
+    assert_eq!(trash.moved_path().len(), 1);
+
+    // A bundle seal counts as a signature, even when the
+    // binaries themselves are only linker signed.
+    let sealed = fs.clone().with_file(
+        app.join("Contents/_CodeSignature/CodeResources"),
+        "<plist/>",
+    );
+    let report = ThinReport::scan_with(&sealed, app)?;
+    assert!(report.is_sealed());
+    assert!(report.is_signed());
+    assert!(thin_bundle_with(&sealed, &store, &report, "arm64", false).is_err());
+
+    // Framework links are skipped, so each binary is reported
+    // once under its real path and never replaced through a link.
+    let base = std::env::temp_dir().join("test_thin_framework_links.app");
+    let _ = fs::remove_dir_all(&base);
+    let version = base.join("Contents/Frameworks/Kit.framework/Versions/A");
+    fs::create_dir_all(&version).expect("create framework");
+    fs::write(version.join("Kit"), &universal).expect("write binary");
+    let framework = base.join("Contents/Frameworks/Kit.framework");
+    std::os::unix::fs::symlink("A", framework.join("Versions/Current")).expect("create link");
+    std::os::unix::fs::symlink("Versions/Current/Kit", framework.join("Kit")).expect("create link");
+
+    let report = ThinReport::scan_with(&RealFileSystem::new(), &base)?;
+    let binaries: Vec<&Path> = report
+        .as_binaries()
+        .iter()
+        .map(|binary| binary.as_path_data().as_path())
+        .collect();
+    assert_eq!(binaries, vec![version.join("Kit").as_path()]);
+    assert!(!report.is_sealed());
+
+    let link = cleaner::path_data::PathData::new(framework.join("Kit"), "Kit".to_string());
+    assert!(TrashBinaryStore::new().replace(&link, b"thin").is_err());
+    assert!(
+        fs::symlink_metadata(framework.join("Kit"))
+            .is_ok_and(|metadata| metadata.file_type().is_symlink())
+    );
+    let _ = fs::remove_dir_all(&base);
+
+    assert!(ThinReport::scan_with(&RealFileSystem::new(), Path::new("/nonexistent.app")).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_localizations_in_bundle() -> cleaner::Result<()> {
+    use cleaner::{AppProfile, Localization, MemoryFileSystem};
+    use std::path::Path;
+
+    let fixture = r#"
+/Applications/Cafe.app:
+  Contents:
+    Info.plist: |
+      <?xml version="1.0" encoding="UTF-8"?>
+      <plist version="1.0">
+      <dict>
+          <key>CFBundleIdentifier</key>
+          <string>com.example.cafe</string>
+          <key>CFBundleName</key>
+          <string>Cafe</string>
+          <key>CFBundleExecutable</key>
+          <string>Cafe</string>
+          <key>CFBundleDevelopmentRegion</key>
+          <string>English</string>
+      </dict>
+      </plist>
+    MacOS: {}
+    Resources:
+      Base.lproj:
+        Main.nib: "0123456789"
+      English.lproj:
+        Localizable.strings: "hello"
+      de.lproj:
+        Localizable.strings: "hallo"
+        Help:
+          index.html: "0123456789"
+      pt_BR.lproj:
+        Localizable.strings: "ola"
+      zh_CN.lproj: {}
+      zh_TW.lproj: {}
+      zh-Hant.lproj: {}
+    Frameworks:
+      Kit.framework:
+        Resources:
+          Info.plist: |
+            <?xml version="1.0" encoding="UTF-8"?>
+            <plist version="1.0">
+            <dict>
+                <key>CFBundleIdentifier</key>
+                <string>com.example.kit</string>
+                <key>CFBundleDevelopmentRegion</key>
+                <string>ja</string>
+            </dict>
+            </plist>
+          ja.lproj:
+            Kit.strings: "konnichiwa"
+          zh-Hans.lproj: {}
+"#;
+
+    let mut fs = MemoryFileSystem::from_yaml(fixture)?;
+    let app = Path::new("/Applications/Cafe.app");
+    let profile = AppProfile::from_path_with(&fs, app)?;
+
+    let localizations = profile.find_localizations_with(&fs);
+    let summary: Vec<(&str, String, u64)> = localizations
+        .iter()
+        .map(|l| (l.as_name(), l.language(), l.size()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("ja", "ja".to_string(), 10),
+            ("zh-Hans", "zh".to_string(), 0),
+            ("Base", "base".to_string(), 10),
+            ("English", "en".to_string(), 5),
+            ("de", "de".to_string(), 15),
+            ("pt_BR", "pt".to_string(), 3),
+            ("zh-Hant", "zh".to_string(), 0),
+            ("zh_CN", "zh".to_string(), 0),
+            ("zh_TW", "zh".to_string(), 0),
+        ]
+    );
+    assert_eq!(localizations[0].as_development_region(), Some("ja"));
+    assert_eq!(localizations[2].as_development_region(), Some("English"));
+
+    // Codes keep every region and script, regional languages
+    // keep their script and region, and Base and each bundle's
+    // development region are always kept.
+    let unused_for = |keep: &[&str]| -> Vec<String> {
+        let keep: Vec<String> = keep.iter().map(|k| k.to_string()).collect();
+        let mut unused: Vec<String> = profile
+            .unused_localizations_with(&fs, &keep)
+            .iter()
+            .map(|l| l.as_path_data().as_name().to_string())
+            .collect();
+        unused.sort();
+        unused
+    };
+    assert_eq!(
+        unused_for(&["en", "PT-br"]),
+        vec![
+            "de.lproj",
+            "zh-Hans.lproj",
+            "zh-Hant.lproj",
+            "zh_CN.lproj",
+            "zh_TW.lproj"
+        ]
+    );
+    assert_eq!(
+        unused_for(&["en-US", "pt-PT", "zh-Hans-CN"]),
+        vec!["de.lproj", "pt_BR.lproj", "zh-Hant.lproj", "zh_TW.lproj"]
+    );
+    assert_eq!(
+        unused_for(&[]),
+        vec![
+            "de.lproj",
+            "pt_BR.lproj",
+            "zh-Hans.lproj",
+            "zh-Hant.lproj",
+            "zh_CN.lproj",
+            "zh_TW.lproj"
+        ]
+    );
+
+    // Without a readable preference only English is kept.
+    assert_eq!(Localization::preferred_languages_with(&fs), vec!["en"]);
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    fs.create_file(
+        Path::new(&home).join("Library/Preferences/.GlobalPreferences.plist"),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>AppleLanguages</key>
+    <array>
+        <string>de-DE</string>
+        <string>en-GB</string>
+    </array>
+</dict>
+</plist>"#,
+    );
+    assert_eq!(
+        Localization::preferred_languages_with(&fs),
+        vec!["de-DE", "en-GB"]
+    );
+
+    assert!(profile.signature_warning().is_none());
+
+    fs.create_file(
+        app.join("Contents/MacOS/Cafe"),
+        macho_fixture("cafe_x86_64"),
+    );
+    let signed = AppProfile::from_path_with(&fs, app)?;
+    assert!(
+        signed
+            .signature_warning()
+            .is_some_and(|warning| warning.contains("'Cafe'"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_size_breakdown_of_bundle_and_leftovers() -> cleaner::Result<()> {
+    use cleaner::macho::MachFile;
+    use cleaner::utility::format_size;
+    use cleaner::{AppProfile, BundleSection, FileSystem, MemoryFileSystem};
+    use std::path::Path;
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let info_plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.cafe</string>
+    <key>CFBundleName</key>
+    <string>Cafe</string>
+    <key>CFBundleExecutable</key>
+    <string>Cafe</string>
+</dict>
+</plist>"#;
+
+    let app = Path::new("/Applications/Cafe.app");
+    let universal = macho_fixture("linked_universal");
+    let support = Path::new(&home).join("Library/Application Support/com.example.cafe");
+
+    let mut fs = MemoryFileSystem::new()
+        .with_file(app.join("Contents/Info.plist"), info_plist)
+        .with_file(app.join("Contents/MacOS/Cafe"), universal.clone())
+        .with_file(
+            app.join("Contents/Frameworks/Kit.framework/Versions/A/Kit"),
+            vec![0u8; 3000],
+        )
+        .with_file(app.join("Contents/Resources/Cafe.icns"), vec![0u8; 2000])
+        .with_file(
+            app.join("Contents/Resources/de.lproj/Localizable.strings"),
+            vec![0u8; 400],
+        )
+        .with_file(
+            app.join("Contents/PlugIns/Share.appex/Contents/MacOS/Share"),
+            vec![0u8; 700],
+        )
+        .with_file(
+            app.join(
+                "Contents/Library/LoginItems/Helper.app/Contents/Resources/en.lproj/Help.strings",
+            ),
+            vec![0u8; 90],
+        )
+        .with_file(support.join("cache.db"), vec![0u8; 5000])
+        .with_file(support.join("logs/today.log"), vec![0u8; 120]);
+
+    let mut profile = AppProfile::from_path_with(&fs, app)?;
+    profile.find_path_entry_with(&fs, |_, _| {});
+
+    let breakdown = profile.size_breakdown_with(&fs);
+    let executables = universal.len() as u64;
+    let info = info_plist.len() as u64;
+
+    assert_eq!(breakdown.section(BundleSection::Executables), executables);
+    assert_eq!(breakdown.section(BundleSection::Frameworks), 3000);
+    assert_eq!(breakdown.section(BundleSection::Resources), 2000);
+    assert_eq!(breakdown.section(BundleSection::Localizations), 400);
+    assert_eq!(breakdown.section(BundleSection::PlugIns), 700);
+    // Nested apps take precedence over their localizations.
+    assert_eq!(breakdown.section(BundleSection::NestedApps), 90);
+    assert_eq!(breakdown.section(BundleSection::Other), info);
+    assert_eq!(
+        breakdown.bundle_size(),
+        executables + 3000 + 2000 + 400 + 700 + 90 + info
+    );
+    assert_eq!(breakdown.as_sections().len(), BundleSection::ALL.len());
+
+    let slices = MachFile::parse(&universal)?
+        .slices()
+        .iter()
+        .map(|slice| (slice.arch_name(), slice.as_bytes().len() as u64))
+        .collect::<Vec<_>>();
+    assert_eq!(breakdown.as_architectures(), slices.as_slice());
+
+    let largest: Vec<&str> = breakdown
+        .as_largest_files()
+        .iter()
+        .take(3)
+        .map(|(path, _)| path.as_name())
+        .collect();
+    assert_eq!(largest, vec!["Cafe", "Kit", "Cafe.icns"]);
+
+    assert_eq!(
+        breakdown.as_leftovers()[0],
+        (cleaner::PathCategory::AssociatedFiles, 5120)
+    );
+    assert_eq!(
+        breakdown
+            .as_leftovers()
+            .iter()
+            .map(|(category, _)| *category)
+            .collect::<Vec<_>>(),
+        cleaner::PathCategory::REMOVAL_ORDER
+    );
+    assert_eq!(breakdown.leftover_size(), 5120);
+    assert_eq!(fs.total_size(&support.join("cache.db")), 5000);
+    assert_eq!(fs.total_size(Path::new("/missing")), 0);
+
+    fs.remove(&support);
+    assert_eq!(profile.size_breakdown_with(&fs).leftover_size(), 0);
+
+    // Framework links are not followed, so the binary behind
+    // `Versions/Current` is counted once.
+    let base = std::env::temp_dir().join("test_size_breakdown_links.app");
+    let _ = fs::remove_dir_all(&base);
+    let framework = base.join("Contents/Frameworks/Kit.framework");
+    fs::create_dir_all(framework.join("Versions/A")).expect("create framework");
+    fs::write(framework.join("Versions/A/Kit"), vec![0u8; 4096]).expect("write binary");
+    std::os::unix::fs::symlink("A", framework.join("Versions/Current")).expect("create link");
+    std::os::unix::fs::symlink("Versions/Current/Kit", framework.join("Kit")).expect("create link");
+
+    let disk = cleaner::RealFileSystem::new();
+    assert_eq!(disk.total_size(&base), 4096);
+    assert_eq!(disk.total_size(&framework.join("Kit")), 0);
+    let breakdown = cleaner::SizeBreakdown::new_with(&disk, &base, profile.as_path_entry());
+    assert_eq!(breakdown.bundle_size(), 4096);
+    assert_eq!(breakdown.section(BundleSection::Frameworks), 4096);
+    let _ = fs::remove_dir_all(&base);
+
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_report_json_and_csv_export() -> cleaner::Result<()> {
+    use cleaner::path_data::PathData;
+    use cleaner::path_data::trash_entry::TrashItem;
+    use cleaner::report::REPORT_VERSION;
+    use cleaner::{Cleaner, ErrorKind, FileSystem, MemoryFileSystem, PathCategory, ProcessInfo};
+    use std::path::{Path, PathBuf};
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let app = Path::new("/Applications/Cafe.app");
+    let support = Path::new(&home).join("Library/Application Support/com.example.cafe");
+    let caches = Path::new(&home).join("Library/Caches/com.example.cafe");
+
+    let fs = MemoryFileSystem::new()
+        .with_file(
+            app.join("Contents/Info.plist"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.cafe</string>
+    <key>CFBundleName</key>
+    <string>Cafe</string>
+    <key>CFBundleExecutable</key>
+    <string>Cafe</string>
+    <key>CFBundleShortVersionString</key>
+    <string>2.1</string>
+</dict>
+</plist>"#,
+        )
+        .with_file(
+            app.join("Contents/MacOS/Cafe"),
+            macho_fixture("linked_universal"),
+        )
+        .with_file(support.join("beans, roasted \"dark\".db"), vec![0u8; 1200])
+        .with_file(caches.join("cache.db"), vec![0u8; 800]);
+
+    let mut cleaner =
+        Cleaner::new_profile_with(&fs, app, None::<fn(std::borrow::Cow<'static, str>)>)?;
+
+    let source = cleaner::StaticProcessSource::new(vec![
+        ProcessInfo::new(412, Some(1), "Cafe".to_string(), "Cafe".to_string())
+            .with_exe(Some(app.join("Contents/MacOS/Cafe"))),
+    ]);
+    cleaner.find_app_process_with(&source, None::<fn(std::borrow::Cow<'static, str>)>)?;
+    cleaner.scan_app_profile_with(&fs, |_, _| {})?;
+
+    let trashed = PathBuf::from(&home).join(".Trash/com.example.cafe");
+    cleaner
+        .as_trash_entry_mut()
+        .set_moved_path(vec![TrashItem::new(
+            PathData::new(caches.clone(), "=SUM(1,2)".to_string()),
+            trashed.clone(),
+        )]);
+    cleaner.as_trash_entry_mut().set_failed_path(vec![(
+        PathData::new(support.clone(), "@Cafe".to_string()),
+        ErrorKind::failed()
+            .with_summary("Permission denied")
+            .with_reason("operation not permitted"),
+    )]);
+
+    let report = cleaner.scan_report_with(&fs);
+
+    let application = report.paths_in(PathCategory::Application);
+    assert_eq!(application.len(), 1);
+    assert_eq!(application[0].size(), fs.total_size(app));
+
+    let associated = report.paths_in(PathCategory::AssociatedFiles);
+    let support_entry = associated
+        .iter()
+        .find(|path| path.as_path_data().as_path() == support)
+        .expect("support folder is reported");
+    assert_eq!(support_entry.size(), 1200);
+    assert_eq!(
+        support_entry.match_reason(),
+        "File name contains \"com.example.cafe\""
+    );
+    assert!(report.removable_size() >= 300 + 1200 + 800);
+
+    // JSON
+    let json: serde_json::Value =
+        serde_json::from_str(&report.to_json_string()).expect("valid JSON");
+
+    assert_eq!(json["version"], REPORT_VERSION);
+    assert_eq!(json["application"]["bundle_id"], "com.example.cafe");
+    assert_eq!(json["application"]["version"], "2.1");
+    assert_eq!(json["processes"][0]["pid"], 412);
+    assert_eq!(
+        json["processes"][0]["match_reason"],
+        "Executable inside bundle"
+    );
+
+    let paths = json["paths"].as_array().expect("paths array");
+    assert_eq!(paths.len(), report.as_paths().len());
+    assert!(paths.iter().any(|path| {
+        path["category"] == "Associated files"
+            && path["path"] == support.to_string_lossy().as_ref()
+            && path["size"] == 1200
+            && path["removed"] == true
+            && path["match_reason"] == "File name contains \"com.example.cafe\""
+    }));
+
+    assert_eq!(
+        json["trash"]["moved"][0]["trashed_path"],
+        trashed.to_string_lossy().as_ref()
+    );
+    assert_eq!(
+        json["trash"]["failed"][0]["error"]["summary"],
+        "Permission denied"
+    );
+    assert_eq!(
+        json["trash"]["failed"][0]["error"]["reason"],
+        "operation not permitted"
+    );
+
+    let breakdown = report.as_size_breakdown();
+    assert_eq!(
+        breakdown,
+        &cleaner.as_app_profile().size_breakdown_with(&fs)
+    );
+    assert_eq!(
+        json["size_breakdown"]["bundle_size"],
+        breakdown.bundle_size()
+    );
+    assert_eq!(
+        json["size_breakdown"]["sections"][0]["section"],
+        "Executables"
+    );
+    assert_eq!(
+        json["size_breakdown"]["sections"]
+            .as_array()
+            .expect("sections array")
+            .len(),
+        cleaner::BundleSection::ALL.len()
+    );
+    let architectures = json["size_breakdown"]["architectures"]
+        .as_array()
+        .expect("architectures array");
+    assert_eq!(architectures.len(), 2);
+    assert_eq!(
+        architectures[0]["architecture"],
+        breakdown.as_architectures()[0].0
+    );
+    assert_eq!(architectures[0]["size"], breakdown.as_architectures()[0].1);
+    assert_eq!(
+        json["size_breakdown"]["largest_files"][0]["path"],
+        "/Applications/Cafe.app/Contents/MacOS/Cafe"
+    );
+    assert_eq!(
+        json["size_breakdown"]["leftovers"][0],
+        serde_json::json!({ "category": "Associated files", "size": 2000 })
+    );
+    assert_eq!(json["size_breakdown"]["leftover_size"], 2000);
+
+    // CSV
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.split("\r\n").filter(|line| !line.is_empty()).collect();
+
+    assert_eq!(
+        lines[0],
+        "record,category,name,path,size,pid,match_reason,trashed_path,error"
+    );
+    assert_eq!(lines.len(), 1 + 1 + report.as_paths().len() + 2);
+    assert!(lines[1].starts_with("process,,Cafe,/Applications/Cafe.app/Contents/MacOS/Cafe,,412,"));
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("moved,") && line.contains(".Trash/com.example.cafe"))
+    );
+    assert!(lines.iter().any(|line| {
+        line.starts_with("failed,")
+            && line.ends_with("[Failed: Permission denied] - operation not permitted")
+    }));
+    assert!(csv.contains(",\"File name contains \"\"com.example.cafe\"\"\","));
+    // Fields a spreadsheet would evaluate are shown as text.
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("moved,,\"'=SUM(1,2)\","))
+    );
+    assert!(lines.iter().any(|line| line.starts_with("failed,,'@Cafe,")));
+
+    // Files
+    let dir = std::env::temp_dir().join(format!("cleaner_report_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+
+    report.save_json(&dir.join("report.json"))?;
+    report.save_csv(&dir.join("report.csv"))?;
+
+    assert_eq!(
+        std::fs::read_to_string(dir.join("report.csv")).expect("csv file"),
+        csv
+    );
+    assert!(
+        std::fs::read_to_string(dir.join("report.json"))
+            .expect("json file")
+            .contains("\"version\": 1")
+    );
+
+    std::fs::remove_dir_all(&dir).ok();
+
+    Ok(())
+}
+
+#[test]
+fn test_scan_report_html_and_markdown() -> cleaner::Result<()> {
+    use cleaner::path_data::PathData;
+    use cleaner::path_data::trash_entry::TrashItem;
+    use cleaner::utility::format_size;
+    use cleaner::{Cleaner, ErrorKind, IconCache, MemoryFileSystem, PathCategory};
+    use std::path::{Path, PathBuf};
+
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let app = Path::new("/Applications/Cafe.app");
+    let support = Path::new(&home).join("Library/Application Support/com.example.cafe");
+    let helper = PathBuf::from("/Library/PrivilegedHelperTools/com.example.cafe.helper");
+
+    let fs = MemoryFileSystem::new()
+        .with_file(
+            app.join("Contents/Info.plist"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.cafe</string>
+    <key>CFBundleName</key>
+    <string>Cafe &amp; &lt;Bar&gt;</string>
+    <key>CFBundleExecutable</key>
+    <string>Cafe</string>
+    <key>CFBundleShortVersionString</key>
+    <string>2.1</string>
+</dict>
+</plist>"#,
+        )
+        .with_file(
+            app.join("Contents/MacOS/Cafe"),
+            macho_fixture("linked_universal"),
+        )
+        .with_file(support.join("beans.db"), vec![0u8; 2048]);
+
+    let mut cleaner =
+        Cleaner::new_profile_with(&fs, app, None::<fn(std::borrow::Cow<'static, str>)>)?;
+    cleaner.scan_app_profile_with(&fs, |_, _| {})?;
+
+    let trashed = PathBuf::from(&home).join(".Trash/com.example.cafe");
+    cleaner
+        .as_trash_entry_mut()
+        .set_moved_path(vec![TrashItem::new(
+            PathData::new(support.clone(), "com.example.cafe".to_string()),
+            trashed.clone(),
+        )]);
+    cleaner.as_trash_entry_mut().set_failed_path(vec![(
+        PathData::new(helper.clone(), "com.example.cafe.helper".to_string()),
+        ErrorKind::failed()
+            .with_summary("Permission denied")
+            .with_reason("owned by root | needs <admin>"),
+    )]);
+
+    let icon = IconCache::from_rgba(app, 2, 2, vec![255u8; 16]);
+    let report = cleaner.scan_report_with(&fs).with_icon(&icon);
+
+    // HTML
+    let html = report.to_html();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<img src=\"data:image/png;base64,iVBORw0KGgo"));
+    assert!(html.contains("<h1>Cafe &amp; &lt;Bar&gt;</h1>"));
+    assert!(!html.contains("<Bar>"));
+    assert!(html.contains("<td>com.example.cafe</td>"));
+    assert!(html.contains("<td>2.1</td>"));
+    assert!(html.contains(PathCategory::AssociatedFiles.match_reason()));
+    assert!(html.contains("<td>File name contains &quot;com.example.cafe&quot;</td>"));
+    assert!(html.contains("2.0 KB"));
+    assert!(html.contains(&trashed.display().to_string()));
+    assert!(
+        html.contains("<td>Permission denied</td><td>owned by root | needs &lt;admin&gt;</td>")
+    );
+    let breakdown = report.as_size_breakdown();
+    let (arch, arch_size) = breakdown.as_architectures()[0];
+    assert!(html.contains(&format!(
+        "<h2>Size breakdown</h2>\n<p class=\"muted\">Bundle {}, leftovers 2.0 KB.</p>",
+        format_size(breakdown.bundle_size())
+    )));
+    assert!(html.contains("<h3>Bundle sections</h3>"));
+    assert!(html.contains(&format!(
+        "<tr><td class=\"path\">{}</td><td class=\"size\">{}</td></tr>",
+        arch,
+        format_size(arch_size)
+    )));
+    assert!(html.contains(
+        "<h3>Largest files</h3>\n<table>\n<tr><td class=\"path\">/Applications/Cafe.app/Contents/MacOS/Cafe</td>"
+    ));
+    assert!(html.contains(
+        "<h3>Leftovers</h3>\n<table>\n<tr><td class=\"path\">Associated files</td><td class=\"size\">2.0 KB</td></tr>"
+    ));
+    assert!(!html.contains("src=\"http"));
+    assert!(!html.contains("href="));
+
+    // Reports without an icon stay valid.
+    let plain = cleaner.scan_report_with(&fs).to_html();
+    assert!(!plain.contains("<img"));
+
+    // Markdown
+    let md = report.to_markdown();
+
+    assert!(md.starts_with("# Cafe & \\<Bar\\> uninstall report"));
+    assert!(md.contains("| Bundle identifier | com.example.cafe |"));
+    assert!(md.contains("## Processes\n\nNone."));
+    assert!(md.contains("### Associated files (1, 2.0 KB)"));
+    assert!(md.contains("| File name contains \"com.example.cafe\" | 2.0 KB |"));
+    assert!(md.contains(&format!(
+        "| `{}` | `{}` |",
+        PathData::new(support.clone(), String::new()),
+        trashed.display()
+    )));
+    assert!(md.contains("| Permission denied | owned by root \\| needs \\<admin\\> |"));
+    assert!(md.contains(&format!(
+        "## Size breakdown\n\nBundle {}, leftovers 2.0 KB.",
+        format_size(breakdown.bundle_size())
+    )));
+    assert!(md.contains(&format!(
+        "### Bundle sections\n\n| | Size |\n|---|---:|\n| Executables | {} |",
+        format_size(breakdown.section(cleaner::BundleSection::Executables))
+    )));
+    assert!(md.contains(&format!(
+        "| {} | {} |",
+        arch.replace('_', "\\_"),
+        format_size(arch_size)
+    )));
+    assert!(md.contains("### Largest files\n\n| | Size |\n|---|---:|\n| /Applications/Cafe.app/Contents/MacOS/Cafe |"));
+    assert!(md.contains("### Leftovers\n\n| | Size |\n|---|---:|\n| Associated files | 2.0 KB |"));
+    assert!(!md.contains("data:image"));
+
+    Ok(())
+}
+
+#[test]
+fn test_removal_shell_script() -> cleaner::Result<()> {
+    use cleaner::path_data::PathData;
+    use cleaner::{
+        AppProfile, Cleaner, MemoryFileSystem, Metadata, PathEntry, ProcessEntry, ProcessInfo,
+        RealFileSystem,
+    };
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    let setup_error = |e: std::io::Error| {
+        cleaner::ErrorKind::failed()
+            .with_summary("Test setup failed")
+            .with_reason(e.to_string())
+    };
+
+    let info_plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.cafe</string>
+    <key>CFBundleName</key>
+    <string>Cafe</string>
+    <key>CFBundleExecutable</key>
+    <string>Cafe</string>
+</dict>
+</plist>"#;
+
+    // Commands, order and comments
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/Unknown".to_string());
+    let app = Path::new("/Applications/Cafe.app");
+    let prefs = Path::new(&home).join("Library/Preferences/com.example.cafe.plist");
+    let odd = Path::new(&home).join("Library/Application Support/Bob's Cafe $(rm -rf ~)");
+    let agent = Path::new(&home).join("Library/LaunchAgents/com.example.cafe.agent.plist");
+    let receipt = Path::new("/private/var/db/receipts/com.example.cafe.pkg.bom");
+
+    let fs = MemoryFileSystem::new()
+        .with_file(app.join("Contents/Info.plist"), info_plist)
+        .with_file(app.join("Contents/MacOS/Cafe"), vec![0u8; 300])
+        .with_file(&prefs, vec![0u8; 100])
+        .with_file(odd.join("data"), vec![0u8; 100])
+        .with_file(&agent, vec![0u8; 10])
+        .with_file(receipt, vec![0u8; 10]);
+
+    let mut cleaner =
+        Cleaner::new_profile_with(&fs, app, None::<fn(std::borrow::Cow<'static, str>)>)?;
+    let source = cleaner::StaticProcessSource::new(vec![
+        ProcessInfo::new(412, Some(1), "Cafe".to_string(), "Cafe".to_string())
+            .with_exe(Some(app.join("Contents/MacOS/Cafe"))),
+        ProcessInfo::new(
+            413,
+            Some(412),
+            "Cafe Helper".to_string(),
+            "helper".to_string(),
+        ),
+        ProcessInfo::new(
+            500,
+            Some(1),
+            "vim".to_string(),
+            "vim com.example.cafe.plist".to_string(),
+        ),
+    ]);
+    cleaner.find_app_process_with(&source, None::<fn(std::borrow::Cow<'static, str>)>)?;
+    cleaner.scan_app_profile_with(&fs, |_, _| {})?;
+
+    let report = cleaner.scan_report_with(&fs);
+    let script = report.to_shell_script(true);
+
+    assert!(script.starts_with("#!/bin/sh\n"));
+    assert!(script.contains("--dry-run"));
+
+    // Children are stopped before their parent.
+    let child = script
+        .find("stop_process 413 'Cafe Helper'")
+        .expect("child");
+    let parent = script.find("stop_process 412 'Cafe'").expect("parent");
+    assert!(child < parent);
+    assert!(script.contains("# Process Cafe · Executable inside bundle (High confidence)"));
+    assert!(script.contains(
+        "# Process vim · Name or command line (Low confidence)\n\
+         # Matched by name only, uncomment to stop it.\n\
+         # stop_process 500 'vim'\n"
+    ));
+
+    assert!(script.contains(&format!(
+        "unload_job gui 'com.example.cafe.agent' '{}'",
+        agent.display()
+    )));
+    assert!(script.contains(&format!(
+        "forget_receipt 'com.example.cafe.pkg' '{}'",
+        receipt.display()
+    )));
+    assert!(script.contains(&format!(
+        "# File name contains \"com.example.cafe\"\nquarantine '{}'",
+        prefs.display()
+    )));
+    // Quotes are escaped and nothing inside the quotes expands.
+    assert!(script.contains(&format!(
+        "quarantine '{}'",
+        odd.display().to_string().replace('\'', r"'\''")
+    )));
+
+    // Same order as `move_to_trash()`, application bundle last.
+    // Paths found by several scanners are moved once.
+    let app_position = script
+        .find("quarantine '/Applications/Cafe.app'")
+        .expect("app");
+    for path in [&prefs, &agent] {
+        let command = format!("quarantine '{}'", path.display());
+        assert_eq!(script.matches(&command).count(), 1);
+        assert!(script.find(&command).expect("path") < app_position);
+    }
+    assert!(script.find("forget_receipt").expect("receipt") < app_position);
+    assert!(
+        script.contains("if [ \"$FAILED\" -eq 0 ]; then\n    quarantine '/Applications/Cafe.app'")
+    );
+
+    assert!(
+        !report
+            .to_shell_script(false)
+            .contains("unload_job gui 'com.example")
+    );
+
+    // Running the script
+    if !Path::new("/bin/sh").exists() {
+        return Ok(());
+    }
+
+    let base = std::env::temp_dir().join(format!("cleaner_script_{}", std::process::id()));
+    let app = base.join("Applications/Tea.app");
+    let support = base.join("Library/Application Support/Tea's \"data\"");
+    let missing = base.join("Library/Caches/com.example.tea");
+    let quarantine = base.join("quarantine");
+    let injected = base.join("injected");
+
+    std::fs::create_dir_all(app.join("Contents")).map_err(setup_error)?;
+    std::fs::create_dir_all(&support).map_err(setup_error)?;
+    std::fs::write(app.join("Contents/Info.plist"), info_plist).map_err(setup_error)?;
+    std::fs::write(support.join("leaves.db"), b"leaves").map_err(setup_error)?;
+
+    let metadata = Metadata::from_path(&app)?;
+    let mut path_entry = PathEntry::from_metadata(&metadata);
+    path_entry.set_general_associated_files(vec![
+        PathData::new(support.clone(), "data".to_string())
+            .with_reason(format!("Holds data\ntouch '{}'", injected.display())),
+        PathData::new(missing.clone(), "com.example.tea".to_string()),
+    ]);
+    let cleaner = Cleaner::new(AppProfile::new(
+        metadata,
+        ProcessEntry::default(),
+        path_entry,
+    ));
+
+    let script_path = base.join("remove.sh");
+    cleaner
+        .scan_report_with(&RealFileSystem::new())
+        .save_shell_script(&script_path, true)?;
+
+    // Match reasons stay inside their comment line.
+    let text = std::fs::read_to_string(&script_path).map_err(setup_error)?;
+    assert!(text.contains(&format!("# Holds data touch '{}'\n", injected.display())));
+    assert!(
+        text.lines()
+            .filter(|line| line.contains("touch"))
+            .all(|line| line.trim_start().starts_with('#'))
+    );
+
+    let run = |args: &[&str]| {
+        Command::new("/bin/sh")
+            .arg(&script_path)
+            .args(args)
+            .env("QUARANTINE", &quarantine)
+            .output()
+            .expect("run script")
+    };
+
+    let syntax = Command::new("/bin/sh")
+        .arg("-n")
+        .arg(&script_path)
+        .status()
+        .expect("check script");
+    assert!(syntax.success());
+
+    let dry_run = run(&["--dry-run"]);
+    let stdout = String::from_utf8_lossy(&dry_run.stdout);
+    assert!(dry_run.status.success());
+    assert!(stdout.contains("[dry-run] mv"));
+    assert!(!stdout.contains("moved:"));
+    assert!(stdout.contains(&format!("missing: {}", missing.display())));
+    assert!(support.exists() && app.exists());
+    assert!(!quarantine.exists());
+
+    assert_eq!(run(&["--force"]).status.code(), Some(2));
+
+    let removal = run(&[]);
+    assert!(removal.status.success());
+    assert!(
+        String::from_utf8_lossy(&removal.stdout).contains(&format!("moved: {}", app.display()))
+    );
+    assert!(!support.exists() && !app.exists());
+
+    let moved = |path: &PathBuf| quarantine.join(path.strip_prefix("/").expect("absolute path"));
+    assert_eq!(
+        std::fs::read(moved(&support).join("leaves.db")).map_err(setup_error)?,
+        b"leaves"
+    );
+    assert!(moved(&app).join("Contents/Info.plist").exists());
+    assert!(!injected.exists());
+
+    // A loaded system job cannot be unloaded without root, which
+    // fails the run instead of being reported as not loaded.
+    let stubs = base.join("bin");
+    let daemon = base.join("Library/LaunchDaemons/com.example.tea.helper.plist");
+    std::fs::create_dir_all(&stubs).map_err(setup_error)?;
+    std::fs::create_dir_all(app.join("Contents")).map_err(setup_error)?;
+    std::fs::create_dir_all(daemon.parent().expect("parent")).map_err(setup_error)?;
+    std::fs::write(app.join("Contents/Info.plist"), info_plist).map_err(setup_error)?;
+    std::fs::write(&daemon, b"").map_err(setup_error)?;
+    std::fs::write(stubs.join("id"), "#!/bin/sh\necho 501\n").map_err(setup_error)?;
+    std::fs::write(stubs.join("launchctl"), "#!/bin/sh\n[ \"$1\" = print ]\n")
+        .map_err(setup_error)?;
+    for stub in ["id", "launchctl"] {
+        let status = Command::new("chmod")
+            .arg("+x")
+            .arg(stubs.join(stub))
+            .status()
+            .map_err(setup_error)?;
+        assert!(status.success());
+    }
+
+    let metadata = Metadata::from_path(&app)?;
+    let mut path_entry = PathEntry::from_metadata(&metadata);
+    path_entry.set_background_task_files(vec![PathData::new(
+        daemon.clone(),
+        "com.example.tea.helper.plist".to_string(),
+    )]);
+    let cleaner = Cleaner::new(AppProfile::new(
+        metadata,
+        ProcessEntry::default(),
+        path_entry,
+    ));
+    cleaner
+        .scan_report_with(&RealFileSystem::new())
+        .save_shell_script(&script_path, true)?;
+
+    let path = format!(
+        "{}:{}",
+        stubs.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let unprivileged = Command::new("/bin/sh")
+        .arg(&script_path)
+        .env("QUARANTINE", &quarantine)
+        .env("PATH", &path)
+        .output()
+        .expect("run script");
+    assert_eq!(unprivileged.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&unprivileged.stderr)
+            .contains("failed to unload: com.example.tea.helper (run with sudo)")
+    );
+    assert!(!String::from_utf8_lossy(&unprivileged.stdout).contains("not loaded"));
+    assert!(app.exists());
+
+    // Under sudo, agents are unloaded from the session of the
+    // user who ran sudo rather than from root's.
+    let agent = base.join("Library/LaunchAgents/com.example.tea.agent.plist");
+    std::fs::create_dir_all(agent.parent().expect("parent")).map_err(setup_error)?;
+    std::fs::write(&agent, b"").map_err(setup_error)?;
+    std::fs::write(stubs.join("id"), "#!/bin/sh\necho 0\n").map_err(setup_error)?;
+
+    let metadata = Metadata::from_path(&app)?;
+    let mut path_entry = PathEntry::from_metadata(&metadata);
+    path_entry.set_background_task_files(vec![PathData::new(
+        agent.clone(),
+        "com.example.tea.agent.plist".to_string(),
+    )]);
+    let cleaner = Cleaner::new(AppProfile::new(
+        metadata,
+        ProcessEntry::default(),
+        path_entry,
+    ));
+    cleaner
+        .scan_report_with(&RealFileSystem::new())
+        .save_shell_script(&script_path, true)?;
+
+    let sudo = Command::new("/bin/sh")
+        .arg(&script_path)
+        .arg("--dry-run")
+        .env("QUARANTINE", &quarantine)
+        .env("PATH", &path)
+        .env("SUDO_UID", "501")
+        .output()
+        .expect("run script");
+    let stdout = String::from_utf8_lossy(&sudo.stdout);
+    assert!(stdout.contains("[dry-run] launchctl 'bootout' 'gui/501/com.example.tea.agent'"));
+    assert!(!stdout.contains("gui/0/"));
+
+    std::fs::remove_dir_all(&base).ok();
+
+    Ok(())
+}